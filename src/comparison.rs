@@ -0,0 +1,116 @@
+use crate::geometry;
+use himewm_layout::Layout;
+
+/// Which variant states the comparison strip shows side by side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// Every manual variant state of the selected variant.
+    VariantStates,
+    /// The selected variant state of every variant that has as many.
+    Variants,
+}
+
+/// A variant state shown in the comparison strip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub variant_idx: usize,
+    pub variant_state_idx: usize,
+    pub selected: bool,
+}
+
+impl Comparison {
+    pub fn thumbnails(
+        self,
+        layout: &Layout,
+        selected_variant_idx: usize,
+        selected_variant_state_idx: usize,
+    ) -> Vec<Thumbnail> {
+        let thumbnail = |variant_idx: usize, variant_state_idx: usize| {
+            return Thumbnail {
+                variant_idx,
+                variant_state_idx,
+                selected: variant_idx == selected_variant_idx
+                    && variant_state_idx == selected_variant_state_idx,
+            };
+        };
+
+        return match self {
+            Comparison::VariantStates => {
+                let variant = match layout.get_variants().get(selected_variant_idx) {
+                    Some(val) => val,
+                    None => return Vec::new(),
+                };
+
+                (0..variant.manual_zones_until())
+                    .map(|variant_state_idx| thumbnail(selected_variant_idx, variant_state_idx))
+                    .collect()
+            }
+            Comparison::Variants => layout
+                .get_variants()
+                .iter()
+                .enumerate()
+                .filter(|(_, variant)| selected_variant_state_idx < variant.manual_zones_until())
+                .map(|(variant_idx, _)| thumbnail(variant_idx, selected_variant_state_idx))
+                .collect(),
+        };
+    }
+}
+
+/// Lays `count` thumbnails of a `monitor_w` by `monitor_h` monitor out in a
+/// grid filling (`x`, `y`, `w`, `h`), with `label_h` pixels below each for
+/// its label. The number of columns is picked to make the thumbnails as
+/// large as possible, and each thumbnail keeps the shape of the monitor.
+pub fn thumbnail_grid(
+    count: usize,
+    monitor_w: i32,
+    monitor_h: i32,
+    (x, y, w, h): (i32, i32, i32, i32),
+    spacing: i32,
+    label_h: i32,
+) -> Vec<(i32, i32, i32, i32)> {
+    if count == 0 || monitor_w <= 0 || monitor_h <= 0 {
+        return Vec::new();
+    }
+
+    let cell_size = |columns: i32| {
+        let rows = (count as i32 + columns - 1) / columns;
+
+        let cell_w = (w - (columns - 1) * spacing) / columns;
+
+        let cell_h = (h - (rows - 1) * spacing) / rows;
+
+        return (cell_w, cell_h);
+    };
+
+    let thumbnail_scale = |columns: i32| {
+        let (cell_w, cell_h) = cell_size(columns);
+
+        return f64::min(
+            cell_w as f64 / monitor_w as f64,
+            (cell_h - label_h) as f64 / monitor_h as f64,
+        );
+    };
+
+    let columns = (1..=count as i32)
+        .max_by(|&a, &b| thumbnail_scale(a).total_cmp(&thumbnail_scale(b)))
+        .unwrap();
+
+    let (cell_w, cell_h) = cell_size(columns);
+
+    return (0..count as i32)
+        .map(|i| {
+            let cell_x = x + (i % columns) * (cell_w + spacing);
+
+            let cell_y = y + (i / columns) * (cell_h + spacing);
+
+            return geometry::fit_aspect_ratio(
+                monitor_w,
+                monitor_h,
+                cell_x,
+                cell_y,
+                cell_w,
+                std::cmp::max(cell_h - label_h, 0),
+            );
+        })
+        .collect();
+}
@@ -0,0 +1,1126 @@
+use crate::{
+    gaps::{GapSettings, Gaps},
+    geometry::{self, ZoneEdge, ZoneSide},
+    history::{History, Snapshot, HISTORY_LIMIT},
+    repeating::{self, RepeatingSplitSpec},
+    rescale,
+};
+use himewm_layout::*;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug)]
+pub enum SwapDirection {
+    Previous,
+    Next,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditError {
+    VariantOutOfRange(usize),
+    VariantStateOutOfRange(usize),
+    ZoneOutOfRange(usize),
+    NoZoneSelected,
+    TwoZonesRequired,
+    TooManyZonesSelected,
+    ZonesNotMergeable,
+    SplitOutOfBounds(i32),
+    InvalidPartCount(i32),
+    EdgeOutOfBounds(i32),
+    MonitorBorder,
+    CannotSwap,
+    LastVariant,
+    LastVariantState,
+    NotDirectional,
+    InvalidMonitorSize(i32, i32),
+    NothingToUndo,
+    NothingToRedo,
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::VariantOutOfRange(idx) => write!(f, "there is no variant {idx}"),
+            EditError::VariantStateOutOfRange(idx) => {
+                write!(f, "there is no variant state {}", idx + 1)
+            }
+            EditError::ZoneOutOfRange(idx) => write!(f, "there is no zone {}", idx + 1),
+            EditError::NoZoneSelected => write!(f, "no zone is selected"),
+            EditError::TwoZonesRequired => write!(f, "two zones must be selected"),
+            EditError::TooManyZonesSelected => write!(f, "only one zone may be selected"),
+            EditError::ZonesNotMergeable => {
+                write!(f, "the selected zones do not form a rectangle")
+            }
+            EditError::SplitOutOfBounds(split_at) => {
+                write!(f, "cannot split at {split_at}, it is outside of the zone")
+            }
+            EditError::InvalidPartCount(parts) => {
+                write!(f, "cannot split a zone into {parts} parts")
+            }
+            EditError::EdgeOutOfBounds(position) => write!(
+                f,
+                "cannot move the edge to {position} without making a zone too small"
+            ),
+            EditError::MonitorBorder => {
+                write!(f, "the borders of the monitor cannot be moved")
+            }
+            EditError::CannotSwap => write!(f, "there is nothing to swap with"),
+            EditError::LastVariant => write!(f, "a layout needs at least one variant"),
+            EditError::LastVariantState => {
+                write!(f, "a variant needs at least one variant state")
+            }
+            EditError::NotDirectional => {
+                write!(f, "the variant does not use directional end tiling")
+            }
+            EditError::InvalidMonitorSize(w, h) => write!(f, "{w}x{h} is not a monitor size"),
+            EditError::NothingToUndo => write!(f, "there is nothing to undo"),
+            EditError::NothingToRedo => write!(f, "there is nothing to redo"),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+/// A single editing step. The GUI makes every edit through
+/// [`LayoutEditor::apply`], so sequences of edits can be replayed without a
+/// window and behave as they do in the GUI.
+#[derive(Clone)]
+pub enum Action {
+    SelectVariant(usize),
+    SelectVariantState(usize),
+    SelectZone(usize),
+    AddZoneToSelection(usize),
+    NewVariantState,
+    CloneVariantState,
+    DeleteVariantState,
+    SwapVariantState(SwapDirection),
+    Split(Direction, i32),
+    SplitEvenly(Direction, i32),
+    SplitZoneAt(usize, Direction, i32),
+    Swap,
+    Merge,
+    MoveZoneEdge(ZoneEdge, i32),
+    SetZoneSide(usize, ZoneSide, i32),
+    SetZoneSize(usize, Direction, i32),
+    NewVariant,
+    CloneVariant,
+    DeleteVariant,
+    SwapVariant(SwapDirection),
+    SetVariantAsDefault,
+    SetEndZoneIdx(usize),
+    SetEndTilingBehaviour(EndTilingBehaviour),
+    SetEndTilingDirection(Direction),
+    SetRepeatingSplits(Vec<RepeatingSplitSpec>),
+    SetFromZones(Option<usize>),
+    SetMonitorSize(i32, i32),
+    SetLayoutGaps(Gaps),
+    SetVariantGaps(Option<Gaps>),
+    Undo,
+    Redo,
+}
+
+/// An editable layout together with the selection the editing operations act
+/// on. Every successful edit is recorded and can be undone.
+///
+/// Operations that add a variant or a variant state leave the selection where
+/// it is, swaps move the selection along with the selected item, and
+/// deletions move it to the nearest remaining item.
+pub struct LayoutEditor {
+    layout: Layout,
+    gaps: GapSettings,
+    selected_variant_idx: usize,
+    selected_variant_state_idx: usize,
+    selected_zone_idx1: Option<usize>,
+    selected_zone_idx2: Option<usize>,
+    history: History,
+    revision: usize,
+    last_revision: usize,
+    saved_revision: Option<usize>,
+}
+
+impl LayoutEditor {
+    pub fn new(layout: Layout) -> Self {
+        let default_variant_idx = layout.default_variant_idx();
+
+        return LayoutEditor {
+            layout,
+            gaps: GapSettings::default(),
+            selected_variant_idx: default_variant_idx,
+            selected_variant_state_idx: 0,
+            selected_zone_idx1: None,
+            selected_zone_idx2: None,
+            history: History::new(HISTORY_LIMIT),
+            revision: 0,
+            last_revision: 0,
+            saved_revision: Some(0),
+        };
+    }
+
+    /// Starts editing with `gaps` in place of no gaps at all.
+    pub fn with_gaps(mut self, gaps: GapSettings) -> Self {
+        self.gaps = gaps;
+
+        return self;
+    }
+
+    pub fn layout(&self) -> &Layout {
+        return &self.layout;
+    }
+
+    pub fn gaps(&self) -> &GapSettings {
+        return &self.gaps;
+    }
+
+    /// Changes made through the returned reference are not recorded in the
+    /// history, and the selection is not checked against them.
+    pub fn layout_mut(&mut self) -> &mut Layout {
+        return &mut self.layout;
+    }
+
+    pub fn into_layout(self) -> Layout {
+        return self.layout;
+    }
+
+    /// Identifies the edited state of the layout. Every recorded edit gives
+    /// it a new revision, and undoing or redoing goes back to the revision
+    /// the restored state had.
+    pub fn revision(&self) -> usize {
+        return self.revision;
+    }
+
+    /// Remembers the current revision as the one last saved.
+    pub fn mark_saved(&mut self) {
+        self.saved_revision = Some(self.revision);
+    }
+
+    /// Forgets the saved revision, for layouts that have not been saved at
+    /// all, such as imported ones.
+    pub fn mark_unsaved(&mut self) {
+        self.saved_revision = None;
+    }
+
+    /// Whether the layout differs from when it was last saved. A new editor
+    /// counts as saved.
+    pub fn is_modified(&self) -> bool {
+        return self.saved_revision != Some(self.revision);
+    }
+
+    pub fn selected_variant_idx(&self) -> usize {
+        return self.selected_variant_idx;
+    }
+
+    pub fn selected_variant_state_idx(&self) -> usize {
+        return self.selected_variant_state_idx;
+    }
+
+    pub fn selected_zone_idx1(&self) -> Option<usize> {
+        return self.selected_zone_idx1;
+    }
+
+    pub fn selected_zone_idx2(&self) -> Option<usize> {
+        return self.selected_zone_idx2;
+    }
+
+    pub fn selected_variant(&self) -> &Variant {
+        return &self.layout.get_variants()[self.selected_variant_idx];
+    }
+
+    pub fn selected_zones(&self) -> &[Zone] {
+        return &self.selected_variant().get_zones()[self.selected_variant_state_idx];
+    }
+
+    pub fn select_variant(&mut self, idx: usize) -> Result<(), EditError> {
+        if idx >= self.layout.variants_len() {
+            return Err(EditError::VariantOutOfRange(idx));
+        }
+
+        self.selected_variant_idx = idx;
+
+        self.selected_variant_state_idx = 0;
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    pub fn select_variant_state(&mut self, idx: usize) -> Result<(), EditError> {
+        if idx >= self.selected_variant().get_zones().len() {
+            return Err(EditError::VariantStateOutOfRange(idx));
+        }
+
+        self.selected_variant_state_idx = idx;
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    /// Selects the zone at `idx`, or deselects it if it is already selected.
+    /// With `add`, the zone becomes the second selected zone instead of
+    /// replacing the selection.
+    pub fn select_zone(&mut self, idx: usize, add: bool) -> Result<(), EditError> {
+        if idx >= self.selected_zones().len() {
+            return Err(EditError::ZoneOutOfRange(idx));
+        }
+
+        if add {
+            if self.selected_zone_idx1 == None || self.selected_zone_idx1 == Some(idx) {
+                return Ok(());
+            }
+
+            if self.selected_zone_idx2 == Some(idx) {
+                self.selected_zone_idx2 = None;
+            } else {
+                self.selected_zone_idx2 = Some(idx);
+            }
+
+            return Ok(());
+        }
+
+        let mut new_selection = true;
+
+        if self.selected_zone_idx1 == Some(idx) {
+            self.selected_zone_idx1 = None;
+
+            new_selection = false;
+        }
+
+        if let Some(old_idx) = self.selected_zone_idx2.take() {
+            if old_idx == idx {
+                self.selected_zone_idx1 = Some(idx);
+
+                return Ok(());
+            }
+        }
+
+        if new_selection {
+            self.selected_zone_idx1 = Some(idx);
+        }
+
+        return Ok(());
+    }
+
+    pub fn clear_zone_selection(&mut self) {
+        self.selected_zone_idx1 = None;
+
+        self.selected_zone_idx2 = None;
+    }
+
+    pub fn can_merge_selected_zones(&self) -> bool {
+        return match (self.selected_zone_idx1, self.selected_zone_idx2) {
+            (Some(idx1), Some(idx2)) => {
+                self.selected_variant()
+                    .can_merge_zones(self.selected_variant_state_idx, idx1, idx2)
+            }
+            _ => false,
+        };
+    }
+
+    /// The exclusive upper bound of the offsets `split` accepts in
+    /// `direction`, or `None` if no zone is selected.
+    pub fn split_bound_max(&self, direction: Direction) -> Option<i32> {
+        let zone_idx1 = self.selected_zone_idx1?;
+
+        return Some(self.split_extent(zone_idx1, self.selected_zone_idx2, direction));
+    }
+
+    fn split_extent(
+        &self,
+        zone_idx1: usize,
+        zone_idx2: Option<usize>,
+        direction: Direction,
+    ) -> i32 {
+        let zones = self.selected_zones();
+
+        let zone1 = &zones[zone_idx1];
+
+        return match direction {
+            Direction::Horizontal => {
+                if let Some(idx) = zone_idx2 {
+                    let zone2 = &zones[idx];
+
+                    std::cmp::max(zone1.right, zone2.right) - std::cmp::min(zone1.left, zone2.left)
+                } else {
+                    zone1.w()
+                }
+            }
+            Direction::Vertical => {
+                if let Some(idx) = zone_idx2 {
+                    let zone2 = &zones[idx];
+
+                    std::cmp::max(zone1.bottom, zone2.bottom) - std::cmp::min(zone1.top, zone2.top)
+                } else {
+                    zone1.h()
+                }
+            }
+        };
+    }
+
+    /// Splits the selected zone `split_at` pixels from its left or top edge.
+    /// If two zones are selected they are merged first, and the two halves
+    /// take their places.
+    pub fn split(&mut self, direction: Direction, split_at: i32) -> Result<(), EditError> {
+        let zone_idx1 = match self.selected_zone_idx1 {
+            Some(val) => val,
+            None => return Err(EditError::NoZoneSelected),
+        };
+
+        if let Some(_) = self.selected_zone_idx2 {
+            if !self.can_merge_selected_zones() {
+                return Err(EditError::ZonesNotMergeable);
+            }
+        }
+
+        let split_bound_max = self.split_extent(zone_idx1, self.selected_zone_idx2, direction);
+
+        if split_at <= 0 || split_at >= split_bound_max {
+            return Err(EditError::SplitOutOfBounds(split_at));
+        }
+
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        let variant = &mut self.layout.get_variants_mut()[self.selected_variant_idx];
+
+        let (zone_idx, other_zone_idx) = match self.selected_zone_idx2 {
+            Some(zone_idx2) => {
+                variant.merge_zones(selected_variant_state_idx, zone_idx1, zone_idx2);
+
+                (
+                    std::cmp::min(zone_idx1, zone_idx2),
+                    Some(std::cmp::max(zone_idx1, zone_idx2)),
+                )
+            }
+            None => (zone_idx1, None),
+        };
+
+        let zone = &variant.get_zones()[selected_variant_state_idx][zone_idx];
+
+        let split_direction = match direction {
+            Direction::Horizontal => SplitDirection::Horizontal(zone.left + split_at),
+            Direction::Vertical => SplitDirection::Vertical(zone.top + split_at),
+        };
+
+        variant.split(selected_variant_state_idx, zone_idx, split_direction);
+
+        if let Some(idx) = other_zone_idx {
+            let zone = variant.get_zones_mut()[selected_variant_state_idx]
+                .pop()
+                .unwrap();
+
+            variant.get_zones_mut()[selected_variant_state_idx].insert(idx, zone);
+        }
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    /// Splits the selected zone into `parts` zones of equal size.
+    pub fn split_evenly(&mut self, direction: Direction, parts: i32) -> Result<(), EditError> {
+        let zone_idx = match self.selected_zone_idx1 {
+            Some(val) => val,
+            None => return Err(EditError::NoZoneSelected),
+        };
+
+        if let Some(_) = self.selected_zone_idx2 {
+            return Err(EditError::TooManyZonesSelected);
+        }
+
+        let zone = &self.selected_zones()[zone_idx];
+
+        let (start, extent) = match direction {
+            Direction::Horizontal => (zone.left, zone.w()),
+            Direction::Vertical => (zone.top, zone.h()),
+        };
+
+        if parts < 2 || extent < parts {
+            return Err(EditError::InvalidPartCount(parts));
+        }
+
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        let variant = &mut self.layout.get_variants_mut()[self.selected_variant_idx];
+
+        let mut split_idx = zone_idx;
+
+        for n in 1..parts {
+            let split_at = start + (extent as f64 * n as f64 / parts as f64).round() as i32;
+
+            let split_direction = match direction {
+                Direction::Horizontal => SplitDirection::Horizontal(split_at),
+                Direction::Vertical => SplitDirection::Vertical(split_at),
+            };
+
+            variant.split(selected_variant_state_idx, split_idx, split_direction);
+
+            // The remainder of a split zone is appended to the variant state
+            split_idx = variant.get_zones()[selected_variant_state_idx].len() - 1;
+        }
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    /// Splits the zone at `zone_idx` of the selected variant state at the
+    /// absolute coordinate `position`.
+    pub fn split_zone_at(
+        &mut self,
+        zone_idx: usize,
+        direction: Direction,
+        position: i32,
+    ) -> Result<(), EditError> {
+        if zone_idx >= self.selected_zones().len() {
+            return Err(EditError::ZoneOutOfRange(zone_idx));
+        }
+
+        let zone = &self.selected_zones()[zone_idx];
+
+        let (split_at, split_direction) = match direction {
+            Direction::Horizontal => (position - zone.left, SplitDirection::Horizontal(position)),
+            Direction::Vertical => (position - zone.top, SplitDirection::Vertical(position)),
+        };
+
+        if split_at <= 0 || split_at >= self.split_extent(zone_idx, None, direction) {
+            return Err(EditError::SplitOutOfBounds(split_at));
+        }
+
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        self.layout.get_variants_mut()[self.selected_variant_idx].split(
+            selected_variant_state_idx,
+            zone_idx,
+            split_direction,
+        );
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    pub fn merge(&mut self) -> Result<(), EditError> {
+        let (zone_idx1, zone_idx2) = match (self.selected_zone_idx1, self.selected_zone_idx2) {
+            (Some(idx1), Some(idx2)) => (idx1, idx2),
+            _ => return Err(EditError::TwoZonesRequired),
+        };
+
+        if !self.can_merge_selected_zones() {
+            return Err(EditError::ZonesNotMergeable);
+        }
+
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        self.layout.get_variants_mut()[self.selected_variant_idx].merge_zones(
+            selected_variant_state_idx,
+            zone_idx1,
+            zone_idx2,
+        );
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    pub fn swap(&mut self) -> Result<(), EditError> {
+        let (zone_idx1, zone_idx2) = match (self.selected_zone_idx1, self.selected_zone_idx2) {
+            (Some(idx1), Some(idx2)) => (idx1, idx2),
+            _ => return Err(EditError::TwoZonesRequired),
+        };
+
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        self.layout.get_variants_mut()[self.selected_variant_idx].swap_zones(
+            selected_variant_state_idx,
+            zone_idx1,
+            zone_idx2,
+        );
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    /// Moves `edge` of the selected variant state to `position`, resizing
+    /// every zone along it.
+    pub fn move_zone_edge(&mut self, edge: &ZoneEdge, position: i32) -> Result<(), EditError> {
+        let zones = self.selected_zones();
+
+        if let Some(&idx) = edge.zone_idxs.iter().find(|&&idx| idx >= zones.len()) {
+            return Err(EditError::ZoneOutOfRange(idx));
+        }
+
+        let (min, max) = geometry::edge_bounds(zones, edge);
+
+        if position < min || position > max {
+            return Err(EditError::EdgeOutOfBounds(position));
+        }
+
+        if position == edge.position {
+            return Ok(());
+        }
+
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        let variant = &mut self.layout.get_variants_mut()[self.selected_variant_idx];
+
+        geometry::move_edge(
+            &mut variant.get_zones_mut()[selected_variant_state_idx],
+            edge,
+            position,
+        );
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    /// Moves `side` of the zone at `zone_idx` to `position`, along with the
+    /// zones that share the edge, so that no gaps open up. Unlike
+    /// `move_zone_edge`, this keeps the zone selection.
+    pub fn set_zone_side(
+        &mut self,
+        zone_idx: usize,
+        side: ZoneSide,
+        position: i32,
+    ) -> Result<(), EditError> {
+        let zones = self.selected_zones();
+
+        if zone_idx >= zones.len() {
+            return Err(EditError::ZoneOutOfRange(zone_idx));
+        }
+
+        let edge = match geometry::side_edge(zones, zone_idx, side) {
+            Some(val) => val,
+            None => return Err(EditError::MonitorBorder),
+        };
+
+        let selection = (self.selected_zone_idx1, self.selected_zone_idx2);
+
+        self.move_zone_edge(&edge, position)?;
+
+        (self.selected_zone_idx1, self.selected_zone_idx2) = selection;
+
+        return Ok(());
+    }
+
+    /// Makes the zone at `zone_idx` `size` pixels wide or tall by moving its
+    /// right or bottom side, or its left or top side if the other one lies on
+    /// the border of the monitor.
+    pub fn set_zone_size(
+        &mut self,
+        zone_idx: usize,
+        direction: Direction,
+        size: i32,
+    ) -> Result<(), EditError> {
+        let zones = self.selected_zones();
+
+        let zone = match zones.get(zone_idx) {
+            Some(val) => val,
+            None => return Err(EditError::ZoneOutOfRange(zone_idx)),
+        };
+
+        let (start_side, end_side) = match direction {
+            Direction::Horizontal => (ZoneSide::Left, ZoneSide::Right),
+            Direction::Vertical => (ZoneSide::Top, ZoneSide::Bottom),
+        };
+
+        let start = start_side.position(zone);
+
+        let end = end_side.position(zone);
+
+        if let Some(_) = geometry::side_edge(zones, zone_idx, end_side) {
+            return self.set_zone_side(zone_idx, end_side, start + size);
+        }
+
+        return self.set_zone_side(zone_idx, start_side, end - size);
+    }
+
+    /// Appends a variant state containing a single zone covering the monitor,
+    /// and returns its index.
+    pub fn new_variant_state(&mut self) -> usize {
+        self.record();
+
+        let w = self.layout.get_monitor_rect().w();
+
+        let h = self.layout.get_monitor_rect().h();
+
+        let variant = &mut self.layout.get_variants_mut()[self.selected_variant_idx];
+
+        variant.new_zone_vec(w, h);
+
+        variant.set_end_zone_idx(0);
+
+        return variant.manual_zones_until() - 1;
+    }
+
+    /// Appends a copy of the selected variant state, and returns its index.
+    pub fn clone_variant_state(&mut self) -> usize {
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        let variant = &mut self.layout.get_variants_mut()[self.selected_variant_idx];
+
+        variant.clone_zone_vec(selected_variant_state_idx);
+
+        return variant.manual_zones_until() - 1;
+    }
+
+    pub fn delete_variant_state(&mut self) -> Result<(), EditError> {
+        if self.selected_variant().manual_zones_until() == 1 {
+            return Err(EditError::LastVariantState);
+        }
+
+        self.record();
+
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        let variant = &mut self.layout.get_variants_mut()[self.selected_variant_idx];
+
+        variant.delete_zones(selected_variant_state_idx);
+
+        if selected_variant_state_idx == variant.manual_zones_until() {
+            self.selected_variant_state_idx -= 1;
+        }
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    /// Swaps the selected variant state with its neighbour, and returns the
+    /// index it moved to.
+    pub fn swap_variant_state(
+        &mut self,
+        swap_direction: SwapDirection,
+    ) -> Result<usize, EditError> {
+        let selected_variant_state_idx = self.selected_variant_state_idx;
+
+        let swap_with = match swap_direction {
+            SwapDirection::Previous if selected_variant_state_idx != 0 => {
+                selected_variant_state_idx - 1
+            }
+            SwapDirection::Next
+                if selected_variant_state_idx
+                    != self.selected_variant().manual_zones_until() - 1 =>
+            {
+                selected_variant_state_idx + 1
+            }
+            _ => return Err(EditError::CannotSwap),
+        };
+
+        self.record();
+
+        self.layout.get_variants_mut()[self.selected_variant_idx]
+            .swap_zone_vectors(selected_variant_state_idx, swap_with);
+
+        self.selected_variant_state_idx = swap_with;
+
+        self.clear_zone_selection();
+
+        return Ok(swap_with);
+    }
+
+    /// Appends an empty variant, and returns its index.
+    pub fn new_variant(&mut self) -> usize {
+        self.record();
+
+        let w = self.layout.get_monitor_rect().w();
+
+        let h = self.layout.get_monitor_rect().h();
+
+        self.layout.get_variants_mut().push(Variant::new(w, h));
+
+        return self.layout.variants_len() - 1;
+    }
+
+    /// Appends a copy of the selected variant, including its own gaps if it
+    /// has any, and returns its index.
+    pub fn clone_variant(&mut self) -> usize {
+        self.record();
+
+        let variant = self.selected_variant().clone();
+
+        self.layout.get_variants_mut().push(variant);
+
+        let idx = self.layout.variants_len() - 1;
+
+        if let Some(gaps) = self.gaps.variants.get(&self.selected_variant_idx) {
+            self.gaps.variants.insert(idx, *gaps);
+        }
+
+        return idx;
+    }
+
+    pub fn delete_variant(&mut self) -> Result<(), EditError> {
+        if self.layout.variants_len() == 1 {
+            return Err(EditError::LastVariant);
+        }
+
+        self.record();
+
+        self.layout.delete_variant(self.selected_variant_idx);
+
+        self.gaps.variant_deleted(self.selected_variant_idx);
+
+        if self.selected_variant_idx == self.layout.variants_len() {
+            self.selected_variant_idx -= 1;
+        }
+
+        self.selected_variant_state_idx = 0;
+
+        self.clear_zone_selection();
+
+        return Ok(());
+    }
+
+    /// Swaps the selected variant with its neighbour, and returns the index it
+    /// moved to.
+    pub fn swap_variant(&mut self, swap_direction: SwapDirection) -> Result<usize, EditError> {
+        let selected_variant_idx = self.selected_variant_idx;
+
+        let swap_with = match swap_direction {
+            SwapDirection::Previous if selected_variant_idx != 0 => selected_variant_idx - 1,
+            SwapDirection::Next if selected_variant_idx != self.layout.variants_len() - 1 => {
+                selected_variant_idx + 1
+            }
+            _ => return Err(EditError::CannotSwap),
+        };
+
+        self.record();
+
+        self.layout.swap_variants(
+            std::cmp::min(selected_variant_idx, swap_with),
+            std::cmp::max(selected_variant_idx, swap_with),
+        );
+
+        self.gaps.variants_swapped(selected_variant_idx, swap_with);
+
+        self.selected_variant_idx = swap_with;
+
+        return Ok(swap_with);
+    }
+
+    pub fn set_default_variant(&mut self) {
+        if self.layout.default_variant_idx() == self.selected_variant_idx {
+            return;
+        }
+
+        self.record();
+
+        self.layout
+            .set_default_variant_idx(self.selected_variant_idx);
+    }
+
+    /// Sets the zone of the last manual variant state that new windows are
+    /// tiled into.
+    pub fn set_end_zone_idx(&mut self, idx: usize) -> Result<(), EditError> {
+        let variant = self.selected_variant();
+
+        if idx >= variant.get_zones()[variant.manual_zones_until() - 1].len() {
+            return Err(EditError::ZoneOutOfRange(idx));
+        }
+
+        if idx == variant.get_end_zone_idx() {
+            return Ok(());
+        }
+
+        self.record();
+
+        self.layout.get_variants_mut()[self.selected_variant_idx].set_end_zone_idx(idx);
+
+        return Ok(());
+    }
+
+    pub fn set_end_tiling_behaviour(&mut self, end_tiling_behaviour: EndTilingBehaviour) {
+        self.record();
+
+        self.layout.get_variants_mut()[self.selected_variant_idx]
+            .set_end_tiling_behaviour(end_tiling_behaviour);
+    }
+
+    pub fn set_end_tiling_direction(&mut self, direction: Direction) -> Result<(), EditError> {
+        let current = match self.selected_variant().get_end_tiling_direction() {
+            Some(val) => val,
+            None => return Err(EditError::NotDirectional),
+        };
+
+        if matches!(
+            (current, direction),
+            (Direction::Horizontal, Direction::Horizontal)
+                | (Direction::Vertical, Direction::Vertical)
+        ) {
+            return Ok(());
+        }
+
+        self.record();
+
+        self.layout.get_variants_mut()[self.selected_variant_idx]
+            .set_end_tiling_direction(direction);
+
+        return Ok(());
+    }
+
+    /// Replaces the repeating splits of the selected variant, switching it to
+    /// repeating end tiling if necessary.
+    pub fn set_repeating_splits(&mut self, splits: &[RepeatingSplitSpec]) {
+        self.record();
+
+        repeating::set_repeating_splits(
+            &mut self.layout.get_variants_mut()[self.selected_variant_idx],
+            splits,
+        );
+    }
+
+    /// Sets the variant state directional end tiling compresses zones from,
+    /// or lets the layout pick one if `variant_state_idx` is `None`.
+    pub fn set_from_zones(&mut self, variant_state_idx: Option<usize>) -> Result<(), EditError> {
+        let variant = self.selected_variant();
+
+        if let Some(idx) = variant_state_idx {
+            if idx >= variant.manual_zones_until() {
+                return Err(EditError::VariantStateOutOfRange(idx));
+            }
+        }
+
+        let (direction, zone_idx) = match variant.get_end_tiling_behaviour() {
+            EndTilingBehaviour::Directional {
+                direction,
+                from_zones: _,
+                zone_idx,
+            } => (direction.to_owned(), zone_idx.to_owned()),
+            EndTilingBehaviour::Repeating { .. } => return Err(EditError::NotDirectional),
+        };
+
+        let from_zones = variant_state_idx.map(|idx| variant.get_zones()[idx].to_owned());
+
+        self.record();
+
+        self.layout.get_variants_mut()[self.selected_variant_idx].set_end_tiling_behaviour(
+            EndTilingBehaviour::Directional {
+                direction,
+                from_zones,
+                zone_idx,
+            },
+        );
+
+        return Ok(());
+    }
+
+    /// The selected variant as extend would continue it, showing how windows
+    /// beyond its manual variant states are tiled. The layout itself is left
+    /// untouched.
+    pub fn preview_extend(&self, count: usize) -> Variant {
+        let mut variant = self.selected_variant().clone();
+
+        variant.update_from_zones();
+
+        for _i in 0..count {
+            variant.extend();
+        }
+
+        return variant;
+    }
+
+    /// Retargets the layout to a monitor of `w` by `h` pixels, scaling every
+    /// zone of every variant state proportionally.
+    pub fn set_monitor_size(&mut self, w: i32, h: i32) -> Result<(), EditError> {
+        if w <= 0 || h <= 0 {
+            return Err(EditError::InvalidMonitorSize(w, h));
+        }
+
+        let monitor_rect = self.layout.get_monitor_rect();
+
+        if monitor_rect.w() == w && monitor_rect.h() == h {
+            return Ok(());
+        }
+
+        self.record();
+
+        self.layout = rescale::rescale_layout(&self.layout, w, h);
+
+        return Ok(());
+    }
+
+    /// Sets the gaps of every variant that does not have gaps of its own.
+    pub fn set_layout_gaps(&mut self, gaps: Gaps) {
+        if self.gaps.layout == gaps {
+            return;
+        }
+
+        self.record();
+
+        self.gaps.layout = gaps;
+    }
+
+    /// Gives the selected variant gaps of its own, or makes it use those of
+    /// the layout again if `gaps` is `None`.
+    pub fn set_variant_gaps(&mut self, gaps: Option<Gaps>) {
+        if self.gaps.variants.get(&self.selected_variant_idx).copied() == gaps {
+            return;
+        }
+
+        self.record();
+
+        match gaps {
+            Some(val) => {
+                self.gaps.variants.insert(self.selected_variant_idx, val);
+            }
+            None => {
+                self.gaps.variants.remove(&self.selected_variant_idx);
+            }
+        }
+    }
+
+    pub fn apply(&mut self, action: Action) -> Result<(), EditError> {
+        match action {
+            Action::SelectVariant(idx) => return self.select_variant(idx),
+            Action::SelectVariantState(idx) => return self.select_variant_state(idx),
+            Action::SelectZone(idx) => return self.select_zone(idx, false),
+            Action::AddZoneToSelection(idx) => return self.select_zone(idx, true),
+            Action::NewVariantState => {
+                self.new_variant_state();
+            }
+            Action::CloneVariantState => {
+                self.clone_variant_state();
+            }
+            Action::DeleteVariantState => return self.delete_variant_state(),
+            Action::SwapVariantState(swap_direction) => {
+                self.swap_variant_state(swap_direction)?;
+            }
+            Action::Split(direction, split_at) => return self.split(direction, split_at),
+            Action::SplitEvenly(direction, parts) => return self.split_evenly(direction, parts),
+            Action::SplitZoneAt(zone_idx, direction, position) => {
+                return self.split_zone_at(zone_idx, direction, position);
+            }
+            Action::Swap => return self.swap(),
+            Action::Merge => return self.merge(),
+            Action::MoveZoneEdge(edge, position) => return self.move_zone_edge(&edge, position),
+            Action::SetZoneSide(zone_idx, side, position) => {
+                return self.set_zone_side(zone_idx, side, position);
+            }
+            Action::SetZoneSize(zone_idx, direction, size) => {
+                return self.set_zone_size(zone_idx, direction, size);
+            }
+            Action::NewVariant => {
+                self.new_variant();
+            }
+            Action::CloneVariant => {
+                self.clone_variant();
+            }
+            Action::DeleteVariant => return self.delete_variant(),
+            Action::SwapVariant(swap_direction) => {
+                self.swap_variant(swap_direction)?;
+            }
+            Action::SetVariantAsDefault => self.set_default_variant(),
+            Action::SetEndZoneIdx(idx) => return self.set_end_zone_idx(idx),
+            Action::SetEndTilingBehaviour(end_tiling_behaviour) => {
+                self.set_end_tiling_behaviour(end_tiling_behaviour);
+            }
+            Action::SetEndTilingDirection(direction) => {
+                return self.set_end_tiling_direction(direction);
+            }
+            Action::SetRepeatingSplits(splits) => self.set_repeating_splits(&splits),
+            Action::SetFromZones(variant_state_idx) => {
+                return self.set_from_zones(variant_state_idx);
+            }
+            Action::SetMonitorSize(w, h) => return self.set_monitor_size(w, h),
+            Action::SetLayoutGaps(gaps) => self.set_layout_gaps(gaps),
+            Action::SetVariantGaps(gaps) => self.set_variant_gaps(gaps),
+            Action::Undo => {
+                if !self.undo() {
+                    return Err(EditError::NothingToUndo);
+                }
+            }
+            Action::Redo => {
+                if !self.redo() {
+                    return Err(EditError::NothingToRedo);
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn can_undo(&self) -> bool {
+        return self.history.can_undo();
+    }
+
+    pub fn can_redo(&self) -> bool {
+        return self.history.can_redo();
+    }
+
+    /// Reverts the last edit. Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let current = self.snapshot();
+
+        return match self.history.undo(current) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+
+                true
+            }
+            None => false,
+        };
+    }
+
+    /// Reapplies the last undone edit. Returns `false` if there was nothing to
+    /// redo.
+    pub fn redo(&mut self) -> bool {
+        let current = self.snapshot();
+
+        return match self.history.redo(current) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+
+                true
+            }
+            None => false,
+        };
+    }
+
+    fn record(&mut self) {
+        let snapshot = self.snapshot();
+
+        self.history.record(snapshot);
+
+        self.last_revision += 1;
+
+        self.revision = self.last_revision;
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        return Snapshot {
+            layout: self.layout.clone(),
+            gaps: self.gaps.clone(),
+            selected_variant_idx: self.selected_variant_idx,
+            selected_variant_state_idx: self.selected_variant_state_idx,
+            revision: self.revision,
+        };
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.layout = snapshot.layout;
+
+        self.gaps = snapshot.gaps;
+
+        self.selected_variant_idx = snapshot.selected_variant_idx;
+
+        self.selected_variant_state_idx = snapshot.selected_variant_state_idx;
+
+        self.revision = snapshot.revision;
+
+        self.clear_zone_selection();
+    }
+}
@@ -0,0 +1,593 @@
+pub mod csv;
+pub mod fancyzones;
+pub mod i3;
+
+use crate::{
+    gaps::{GapSettings, Gaps},
+    geometry,
+};
+use himewm_layout::*;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The longer side of a variant state thumbnail, in pixels.
+const THUMBNAIL_SIZE: i32 = 240;
+
+const PADDING: i32 = 16;
+
+const ROW_LABEL_HEIGHT: i32 = 24;
+
+const CAPTION_HEIGHT: i32 = 20;
+
+const BACKGROUND: (u8, u8, u8) = (255, 255, 255);
+
+/// Gainsboro, the colour zones have in the editor.
+const ZONE_FILL: (u8, u8, u8) = (220, 220, 220);
+
+const EXTENDED_ZONE_FILL: (u8, u8, u8) = (240, 240, 240);
+
+const ZONE_BORDER: (u8, u8, u8) = (0, 0, 0);
+
+const EXTENDED_ZONE_BORDER: (u8, u8, u8) = (128, 128, 128);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Svg,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        return match extension.as_str() {
+            "svg" => Some(ImageFormat::Svg),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        };
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Units {
+    Pixels,
+    Percent,
+}
+
+/// Formats of other window managers and tools that variants can be exported
+/// to.
+#[derive(Clone, Copy, Debug)]
+pub enum Format {
+    FancyZones,
+    I3,
+    Csv(Units),
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    UnknownFormat,
+    Unsplittable { variant_state_idx: usize },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(err) => write!(f, "{err}"),
+            ExportError::UnknownFormat => write!(f, "images can only be exported as .svg or .png"),
+            ExportError::Unsplittable { variant_state_idx } => write!(
+                f,
+                "variant state {} cannot be described as a series of splits",
+                variant_state_idx + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(err: io::Error) -> Self {
+        return ExportError::Io(err);
+    }
+}
+
+/// A variant state placed on the contact sheet.
+struct Cell {
+    x: i32,
+    y: i32,
+    variant_state_idx: usize,
+    extended: bool,
+    zones: Vec<Zone>,
+    gaps: Gaps,
+}
+
+struct Row {
+    variant_idx: usize,
+    default: bool,
+    cells: Vec<Cell>,
+}
+
+/// Every variant of a layout laid out as one row of thumbnails, with one
+/// column per variant state.
+struct ContactSheet {
+    w: i32,
+    h: i32,
+    thumbnail_w: i32,
+    thumbnail_h: i32,
+    monitor_w: f64,
+    monitor_h: f64,
+    rows: Vec<Row>,
+}
+
+impl ContactSheet {
+    fn new(layout: &Layout, gaps: &GapSettings, extended_states: usize) -> Self {
+        let monitor_w = layout.get_monitor_rect().w();
+
+        let monitor_h = layout.get_monitor_rect().h();
+
+        let scale = THUMBNAIL_SIZE as f64 / std::cmp::max(monitor_w, monitor_h) as f64;
+
+        let thumbnail_w = (monitor_w as f64 * scale).round() as i32;
+
+        let thumbnail_h = (monitor_h as f64 * scale).round() as i32;
+
+        let row_h = ROW_LABEL_HEIGHT + thumbnail_h + CAPTION_HEIGHT + PADDING;
+
+        let mut rows = Vec::new();
+
+        let mut columns = 0;
+
+        for (variant_idx, variant) in layout.get_variants().iter().enumerate() {
+            let manual_zones_until = variant.manual_zones_until();
+
+            let mut variant = variant.clone();
+
+            if extended_states > 0 {
+                variant.update_from_zones();
+
+                for _i in 0..extended_states {
+                    variant.extend();
+                }
+            }
+
+            let y = PADDING + variant_idx as i32 * row_h + ROW_LABEL_HEIGHT;
+
+            let cells: Vec<Cell> = variant
+                .get_zones()
+                .iter()
+                .enumerate()
+                .map(|(variant_state_idx, zones)| Cell {
+                    x: PADDING + variant_state_idx as i32 * (thumbnail_w + PADDING),
+                    y,
+                    variant_state_idx,
+                    extended: variant_state_idx >= manual_zones_until,
+                    zones: zones.to_owned(),
+                    gaps: gaps.for_variant(variant_idx),
+                })
+                .collect();
+
+            columns = std::cmp::max(columns, cells.len() as i32);
+
+            rows.push(Row {
+                variant_idx,
+                default: variant_idx == layout.default_variant_idx(),
+                cells,
+            });
+        }
+
+        return ContactSheet {
+            w: PADDING + columns * (thumbnail_w + PADDING),
+            h: PADDING + rows.len() as i32 * row_h,
+            thumbnail_w,
+            thumbnail_h,
+            monitor_w: monitor_w as f64,
+            monitor_h: monitor_h as f64,
+            rows,
+        };
+    }
+
+    /// The position and size of every zone of `cell` on the sheet, less the
+    /// gaps around it, using the same scaling as the editor's variant state
+    /// display.
+    fn zone_rects(&self, cell: &Cell) -> Vec<(i32, i32, i32, i32)> {
+        return cell
+            .zones
+            .iter()
+            .map(|zone| {
+                geometry::scale_rect(
+                    cell.gaps
+                        .window_rect(zone, self.monitor_w as i32, self.monitor_h as i32),
+                    self.monitor_w,
+                    self.monitor_h,
+                    cell.x,
+                    cell.y,
+                    self.thumbnail_w,
+                    self.thumbnail_h,
+                )
+            })
+            .collect();
+    }
+}
+
+/// Renders every variant state of `layout`, followed by `extended_states`
+/// states produced by extending each variant, as an SVG document.
+pub fn svg(layout: &Layout, gaps: &GapSettings, extended_states: usize) -> String {
+    let sheet = ContactSheet::new(layout, gaps, extended_states);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\">\n",
+        w = sheet.w,
+        h = sheet.h
+    );
+
+    svg.push_str(&format!(
+        "  <rect width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+        sheet.w,
+        sheet.h,
+        hex(BACKGROUND)
+    ));
+
+    for row in &sheet.rows {
+        let y = match row.cells.first() {
+            Some(cell) => cell.y,
+            None => continue,
+        };
+
+        let default_text = if row.default { " (default)" } else { "" };
+
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" font-size=\"14\" font-weight=\"bold\">Variant {}{default_text}</text>\n",
+            PADDING,
+            y - 8,
+            row.variant_idx
+        ));
+
+        for cell in &row.cells {
+            let (fill, border, dash) = if cell.extended {
+                (
+                    EXTENDED_ZONE_FILL,
+                    EXTENDED_ZONE_BORDER,
+                    " stroke-dasharray=\"4 2\"",
+                )
+            } else {
+                (ZONE_FILL, ZONE_BORDER, "")
+            };
+
+            svg.push_str("  <g>\n");
+
+            for (i, (x, y, w, h)) in sheet.zone_rects(cell).into_iter().enumerate() {
+                svg.push_str(&format!(
+                    "    <rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"{}\" stroke=\"{}\"{dash}/>\n",
+                    hex(fill),
+                    hex(border)
+                ));
+
+                svg.push_str(&format!(
+                    "    <text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    x + w / 2,
+                    y + h / 2,
+                    (std::cmp::min(w, h) / 2).clamp(8, 36),
+                    i + 1
+                ));
+            }
+
+            let extended_text = if cell.extended { " (extended)" } else { "" };
+
+            svg.push_str(&format!(
+                "    <text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"middle\">State {}{extended_text}</text>\n",
+                cell.x + sheet.thumbnail_w / 2,
+                cell.y + sheet.thumbnail_h + CAPTION_HEIGHT - 4,
+                cell.variant_state_idx + 1
+            ));
+
+            svg.push_str("  </g>\n");
+        }
+    }
+
+    svg.push_str("</svg>\n");
+
+    return svg;
+}
+
+/// Renders the same contact sheet as [`svg`] as a PNG image. Only the zone
+/// numbers are drawn as text, the rows and columns follow the variants and
+/// variant states in order.
+pub fn png(layout: &Layout, gaps: &GapSettings, extended_states: usize) -> Vec<u8> {
+    let sheet = ContactSheet::new(layout, gaps, extended_states);
+
+    let mut canvas = Canvas::new(sheet.w, sheet.h, BACKGROUND);
+
+    for row in &sheet.rows {
+        for cell in &row.cells {
+            let (fill, border) = if cell.extended {
+                (EXTENDED_ZONE_FILL, EXTENDED_ZONE_BORDER)
+            } else {
+                (ZONE_FILL, ZONE_BORDER)
+            };
+
+            for (i, (x, y, w, h)) in sheet.zone_rects(cell).into_iter().enumerate() {
+                canvas.fill_rect(x, y, w, h, fill);
+
+                canvas.stroke_rect(x, y, w, h, border);
+
+                canvas.draw_number(
+                    i + 1,
+                    x + w / 2,
+                    y + h / 2,
+                    (std::cmp::min(w, h) / 16).clamp(1, 6),
+                    ZONE_BORDER,
+                );
+            }
+        }
+    }
+
+    return canvas.encode_png();
+}
+
+/// Writes the contact sheet of `layout` to `path`, as SVG or PNG depending
+/// on its extension.
+pub fn export_image(
+    layout: &Layout,
+    gaps: &GapSettings,
+    path: &Path,
+    extended_states: usize,
+) -> Result<(), ExportError> {
+    let data = match ImageFormat::from_path(path) {
+        Some(ImageFormat::Svg) => svg(layout, gaps, extended_states).into_bytes(),
+        Some(ImageFormat::Png) => png(layout, gaps, extended_states),
+        None => return Err(ExportError::UnknownFormat),
+    };
+
+    fs::write(path, data)?;
+
+    return Ok(());
+}
+
+/// Writes the manual variant states of a variant of `layout` to `path` in
+/// another format. An i3 layout holds a single arrangement, so each variant
+/// state goes to its own file, numbered after the name of `path`. Returns the
+/// paths of the files written.
+///
+/// Only FancyZones places windows at the exact positions it is given, so the
+/// gaps of the variant are taken off its zones. i3 and sway have gaps
+/// settings of their own, and CSV lists the zones as they are.
+pub fn export_layout(
+    layout: &Layout,
+    gaps: &GapSettings,
+    variant_idx: usize,
+    format: Format,
+    path: &Path,
+) -> Result<Vec<PathBuf>, ExportError> {
+    let files = match format {
+        Format::FancyZones => {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "himewm".to_string());
+
+            vec![(
+                path.to_path_buf(),
+                fancyzones::write(layout, gaps.for_variant(variant_idx), variant_idx, &name),
+            )]
+        }
+        Format::I3 => {
+            let documents = i3::write(layout, variant_idx)?;
+
+            if documents.len() == 1 {
+                vec![(path.to_path_buf(), documents[0].to_owned())]
+            } else {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+                let extension = path.extension().unwrap_or_default().to_string_lossy();
+
+                documents
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, document)| {
+                        let file_name = format!("{stem}-{}.{extension}", i + 1);
+
+                        (path.with_file_name(file_name), document)
+                    })
+                    .collect()
+            }
+        }
+        Format::Csv(units) => vec![(path.to_path_buf(), csv::write(layout, variant_idx, units))],
+    };
+
+    for (path, contents) in &files {
+        fs::write(path, contents)?;
+    }
+
+    return Ok(files.into_iter().map(|(path, _)| path).collect());
+}
+
+fn manual_variant_states(variant: &Variant) -> &[Vec<Zone>] {
+    return &variant.get_zones()[..variant.manual_zones_until()];
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    return format!("#{r:02x}{g:02x}{b:02x}");
+}
+
+/// 3x5 bitmaps of the digits 0-9, one row per byte with the leftmost pixel
+/// in the highest of the three bits.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+struct Canvas {
+    w: i32,
+    h: i32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(w: i32, h: i32, colour: (u8, u8, u8)) -> Self {
+        let pixels = [colour.0, colour.1, colour.2].repeat((w * h) as usize);
+
+        return Canvas { w, h, pixels };
+    }
+
+    fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, colour: (u8, u8, u8)) {
+        for pixel_y in std::cmp::max(y, 0)..std::cmp::min(y + h, self.h) {
+            for pixel_x in std::cmp::max(x, 0)..std::cmp::min(x + w, self.w) {
+                let idx = ((pixel_y * self.w + pixel_x) * 3) as usize;
+
+                self.pixels[idx..idx + 3].copy_from_slice(&[colour.0, colour.1, colour.2]);
+            }
+        }
+    }
+
+    fn stroke_rect(&mut self, x: i32, y: i32, w: i32, h: i32, colour: (u8, u8, u8)) {
+        self.fill_rect(x, y, w, 1, colour);
+
+        self.fill_rect(x, y + h - 1, w, 1, colour);
+
+        self.fill_rect(x, y, 1, h, colour);
+
+        self.fill_rect(x + w - 1, y, 1, h, colour);
+    }
+
+    /// Draws `number` centred on (`x`, `y`), with every font pixel drawn as a
+    /// `scale` by `scale` square.
+    fn draw_number(&mut self, number: usize, x: i32, y: i32, scale: i32, colour: (u8, u8, u8)) {
+        let digits: Vec<usize> = number
+            .to_string()
+            .bytes()
+            .map(|digit| (digit - b'0') as usize)
+            .collect();
+
+        let advance = 4 * scale;
+
+        let left = x - (digits.len() as i32 * advance - scale) / 2;
+
+        let top = y - 5 * scale / 2;
+
+        for (i, digit) in digits.iter().enumerate() {
+            for (row, bits) in DIGITS[*digit].iter().enumerate() {
+                for column in 0..3 {
+                    if bits & (0b100 >> column) == 0 {
+                        continue;
+                    }
+
+                    self.fill_rect(
+                        left + i as i32 * advance + column * scale,
+                        top + row as i32 * scale,
+                        scale,
+                        scale,
+                        colour,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Encodes the canvas as an 8-bit RGB PNG, compressed with stored
+    /// (uncompressed) deflate blocks.
+    fn encode_png(&self) -> Vec<u8> {
+        let row_len = (self.w * 3) as usize;
+
+        let mut raw = Vec::with_capacity((row_len + 1) * self.h as usize);
+
+        for row in self.pixels.chunks(row_len) {
+            raw.push(0);
+
+            raw.extend_from_slice(row);
+        }
+
+        let mut zlib = vec![0x78, 0x01];
+
+        let blocks: Vec<&[u8]> = raw.chunks(u16::MAX as usize).collect();
+
+        for (i, block) in blocks.iter().enumerate() {
+            let len = block.len() as u16;
+
+            zlib.push((i == blocks.len() - 1) as u8);
+
+            zlib.extend(len.to_le_bytes());
+
+            zlib.extend((!len).to_le_bytes());
+
+            zlib.extend_from_slice(block);
+        }
+
+        zlib.extend(adler32(&raw).to_be_bytes());
+
+        let mut header = Vec::new();
+
+        header.extend((self.w as u32).to_be_bytes());
+
+        header.extend((self.h as u32).to_be_bytes());
+
+        // Bit depth 8, colour type RGB, default compression, filter and
+        // interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+        png_chunk(&mut png, b"IHDR", &header);
+
+        png_chunk(&mut png, b"IDAT", &zlib);
+
+        png_chunk(&mut png, b"IEND", &[]);
+
+        return png;
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+
+    png.extend_from_slice(kind);
+
+    png.extend_from_slice(data);
+
+    let mut checked = kind.to_vec();
+
+    checked.extend_from_slice(data);
+
+    png.extend(crc32(&checked).to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffff_u32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+
+        for _i in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+
+    return !crc;
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let mut a = 1_u32;
+
+    let mut b = 0_u32;
+
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+
+        b = (b + a) % 65521;
+    }
+
+    return (b << 16) | a;
+}
@@ -0,0 +1,51 @@
+use super::{manual_variant_states, Units};
+use himewm_layout::*;
+
+const HEADER: &str = "state,zone,left,top,right,bottom,width,height";
+
+/// Lists every zone of the manual variant states of a variant, one zone per
+/// line, with states and zones numbered from 1 as in the editor. Percentages
+/// are relative to the size of the monitor.
+pub fn write(layout: &Layout, variant_idx: usize, units: Units) -> String {
+    let monitor_w = layout.get_monitor_rect().w() as f64;
+
+    let monitor_h = layout.get_monitor_rect().h() as f64;
+
+    let mut csv = format!("{HEADER}\n");
+
+    let variant = &layout.get_variants()[variant_idx];
+
+    for (variant_state_idx, zones) in manual_variant_states(variant).iter().enumerate() {
+        for (zone_idx, zone) in zones.iter().enumerate() {
+            let values = match units {
+                Units::Pixels => [
+                    zone.left,
+                    zone.top,
+                    zone.right,
+                    zone.bottom,
+                    zone.w(),
+                    zone.h(),
+                ]
+                .map(|value| value.to_string()),
+                Units::Percent => [
+                    zone.left as f64 / monitor_w,
+                    zone.top as f64 / monitor_h,
+                    zone.right as f64 / monitor_w,
+                    zone.bottom as f64 / monitor_h,
+                    zone.w() as f64 / monitor_w,
+                    zone.h() as f64 / monitor_h,
+                ]
+                .map(|value| format!("{:.2}", value * 100.0)),
+            };
+
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                variant_state_idx + 1,
+                zone_idx + 1,
+                values.join(",")
+            ));
+        }
+    }
+
+    return csv;
+}
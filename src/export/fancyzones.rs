@@ -0,0 +1,89 @@
+use super::manual_variant_states;
+use crate::gaps::Gaps;
+use himewm_layout::*;
+use serde_json::{json, Value};
+
+const SENSITIVITY_RADIUS: i32 = 20;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Writes the manual variant states of a variant as a FancyZones
+/// `custom-layouts.json`, one canvas layout per variant state, with `gaps`
+/// taken off every zone. Canvas layouts are sized for the monitor of `layout`
+/// and scaled by FancyZones to fit others.
+pub fn write(layout: &Layout, gaps: Gaps, variant_idx: usize, name: &str) -> String {
+    let monitor_w = layout.get_monitor_rect().w();
+
+    let monitor_h = layout.get_monitor_rect().h();
+
+    let variant = &layout.get_variants()[variant_idx];
+
+    let layouts: Vec<Value> = manual_variant_states(variant)
+        .iter()
+        .enumerate()
+        .map(|(variant_state_idx, zones)| {
+            let zones: Vec<Value> = zones
+                .iter()
+                .map(|zone| {
+                    let (left, top, right, bottom) = gaps.window_rect(zone, monitor_w, monitor_h);
+
+                    json!({
+                        "X": left,
+                        "Y": top,
+                        "width": right - left,
+                        "height": bottom - top,
+                    })
+                })
+                .collect();
+
+            json!({
+                "uuid": guid(name, variant_idx, variant_state_idx),
+                "name": format!("{name} ({} zones)", zones.len()),
+                "type": "canvas",
+                "info": {
+                    "ref-width": monitor_w,
+                    "ref-height": monitor_h,
+                    "zones": zones,
+                    "sensitivity-radius": SENSITIVITY_RADIUS,
+                },
+            })
+        })
+        .collect();
+
+    return serde_json::to_string_pretty(&json!({ "custom-layouts": layouts })).unwrap();
+}
+
+/// FancyZones identifies layouts by GUID. Deriving it from the layout keeps
+/// repeated exports from piling up copies of the same layout, so it is hashed
+/// with FNV-1a, which unlike the standard library's hasher never changes.
+fn guid(name: &str, variant_idx: usize, variant_state_idx: usize) -> String {
+    let mut key = name.as_bytes().to_vec();
+
+    // Cannot occur in UTF-8, so names cannot run into the indices
+    key.push(0xff);
+
+    key.extend((variant_idx as u64).to_le_bytes());
+
+    key.extend((variant_state_idx as u64).to_le_bytes());
+
+    let high = fnv1a(FNV_OFFSET_BASIS, &key);
+
+    let low = fnv1a(high, b"himewm");
+
+    return format!(
+        "{{{:08X}-{:04X}-{:04X}-{:04X}-{:012X}}}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    );
+}
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    return bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    });
+}
@@ -0,0 +1,71 @@
+use super::{manual_variant_states, ExportError};
+use crate::tiling_tree::{self, Container, Rect};
+use himewm_layout::*;
+use serde_json::{json, Value};
+
+/// Writes each manual variant state of a variant as a layout for i3's
+/// `append_layout`. Every zone becomes a placeholder that swallows any
+/// window, so windows opened afterwards fill the zones.
+pub fn write(layout: &Layout, variant_idx: usize) -> Result<Vec<String>, ExportError> {
+    let region = Rect {
+        left: 0.0,
+        top: 0.0,
+        right: layout.get_monitor_rect().w() as f64,
+        bottom: layout.get_monitor_rect().h() as f64,
+    };
+
+    let variant = &layout.get_variants()[variant_idx];
+
+    let mut documents = Vec::new();
+
+    for (variant_state_idx, zones) in manual_variant_states(variant).iter().enumerate() {
+        let rects = zones.iter().map(Rect::from_zone).collect();
+
+        let mut dropped = 0;
+
+        let container = tiling_tree::cut_rects(region, rects, &mut dropped);
+
+        if dropped > 0 {
+            return Err(ExportError::Unsplittable { variant_state_idx });
+        }
+
+        documents.push(serde_json::to_string_pretty(&node(&container)).unwrap());
+    }
+
+    return Ok(documents);
+}
+
+fn node(container: &Container) -> Value {
+    return match container {
+        Container::Window => json!({
+            "type": "con",
+            "swallows": [{ "class": "^.*$" }],
+        }),
+        Container::Split {
+            direction,
+            children,
+        } => {
+            let layout = match direction {
+                Direction::Horizontal => "splith",
+                Direction::Vertical => "splitv",
+            };
+
+            let nodes: Vec<Value> = children
+                .iter()
+                .map(|(share, child)| {
+                    let mut child = node(child);
+
+                    child["percent"] = json!(share);
+
+                    child
+                })
+                .collect();
+
+            json!({
+                "type": "con",
+                "layout": layout,
+                "nodes": nodes,
+            })
+        }
+    };
+}
@@ -0,0 +1,107 @@
+use crate::gaps::GapSettings;
+use himewm_layout::Layout;
+use serde_json::Value;
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// The key gaps are kept under in layout files, next to the keys of the
+/// layout itself.
+pub const GAPS_KEY: &str = "gaps";
+
+#[derive(Debug)]
+pub enum LayoutFileError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    InvalidGaps,
+}
+
+impl fmt::Display for LayoutFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutFileError::Io(err) => write!(f, "{err}"),
+            LayoutFileError::Json(err) => write!(f, "invalid layout file: {err}"),
+            LayoutFileError::InvalidGaps => {
+                write!(
+                    f,
+                    "invalid layout file: gaps must be non-negative whole numbers"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutFileError {}
+
+impl From<io::Error> for LayoutFileError {
+    fn from(err: io::Error) -> Self {
+        return LayoutFileError::Io(err);
+    }
+}
+
+impl From<serde_json::Error> for LayoutFileError {
+    fn from(err: serde_json::Error) -> Self {
+        return LayoutFileError::Json(err);
+    }
+}
+
+pub fn read_layout(path: &Path) -> Result<Layout, LayoutFileError> {
+    let (layout, _) = read_layout_with_gaps(path)?;
+
+    return Ok(layout);
+}
+
+/// Reads a layout file along with the gaps saved in it, which are all 0 if
+/// there are none.
+pub fn read_layout_with_gaps(path: &Path) -> Result<(Layout, GapSettings), LayoutFileError> {
+    let file = fs::File::open(path)?;
+
+    let mut value: Value = serde_json::from_reader(io::BufReader::new(file))?;
+
+    let gaps = match value
+        .as_object_mut()
+        .and_then(|object| object.remove(GAPS_KEY))
+    {
+        Some(gaps) => match GapSettings::from_json(&gaps) {
+            Some(val) => val,
+            None => return Err(LayoutFileError::InvalidGaps),
+        },
+        None => GapSettings::default(),
+    };
+
+    let layout = serde_json::from_value(value)?;
+
+    return Ok((layout, gaps));
+}
+
+pub fn write_layout(layout: &Layout, path: &Path) -> Result<(), LayoutFileError> {
+    return write_layout_with_gaps(layout, &GapSettings::default(), path);
+}
+
+/// Writes a layout file, with the gaps under `GAPS_KEY` unless there are
+/// none.
+pub fn write_layout_with_gaps(
+    layout: &Layout,
+    gaps: &GapSettings,
+    path: &Path,
+) -> Result<(), LayoutFileError> {
+    let mut value = serde_json::to_value(layout)?;
+
+    if !gaps.is_empty() {
+        if let Some(object) = value.as_object_mut() {
+            object.insert(GAPS_KEY.to_string(), gaps.to_json());
+        }
+    }
+
+    let file = fs::File::create(path)?;
+
+    let mut writer = io::BufWriter::new(file);
+
+    serde_json::to_writer_pretty(&mut writer, &value)?;
+
+    writer.flush()?;
+
+    return Ok(());
+}
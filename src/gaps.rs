@@ -0,0 +1,166 @@
+use himewm_layout::Zone;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// The space the window manager leaves around windows, in pixels: `inner`
+/// between neighbouring windows, and `outer` between windows and the borders
+/// of the monitor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Gaps {
+    pub inner: i32,
+    pub outer: i32,
+}
+
+impl Gaps {
+    /// The (left, top, right, bottom) of a window placed in `zone`. Sides on
+    /// the border of the monitor move in by `outer`, the others by half of
+    /// `inner`, so that neighbouring windows end up `inner` apart.
+    pub fn window_rect(&self, zone: &Zone, monitor_w: i32, monitor_h: i32) -> (i32, i32, i32, i32) {
+        let leading = |position: i32| {
+            if position <= 0 {
+                self.outer
+            } else {
+                self.inner - self.inner / 2
+            }
+        };
+
+        let trailing = |position: i32, extent: i32| {
+            if position >= extent {
+                self.outer
+            } else {
+                self.inner / 2
+            }
+        };
+
+        let left = zone.left + leading(zone.left);
+
+        let top = zone.top + leading(zone.top);
+
+        let right = zone.right - trailing(zone.right, monitor_w);
+
+        let bottom = zone.bottom - trailing(zone.bottom, monitor_h);
+
+        // Gaps larger than the zone leave a sliver rather than nothing
+        return (
+            left,
+            top,
+            std::cmp::max(right, left + 1),
+            std::cmp::max(bottom, top + 1),
+        );
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let object = value.as_object()?;
+
+        let mut gaps = Gaps::default();
+
+        for (key, size) in [("inner", &mut gaps.inner), ("outer", &mut gaps.outer)] {
+            if let Some(val) = object.get(key) {
+                *size = i32::try_from(val.as_i64()?).ok()?;
+            }
+
+            if *size < 0 {
+                return None;
+            }
+        }
+
+        return Some(gaps);
+    }
+
+    fn to_json(&self) -> Value {
+        return json!({
+            "inner": self.inner,
+            "outer": self.outer,
+        });
+    }
+}
+
+/// The gaps of a layout, and of the variants that use gaps of their own.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GapSettings {
+    pub layout: Gaps,
+    /// Gaps that replace those of the layout, by variant index.
+    pub variants: BTreeMap<usize, Gaps>,
+}
+
+impl GapSettings {
+    pub fn for_variant(&self, variant_idx: usize) -> Gaps {
+        return match self.variants.get(&variant_idx) {
+            Some(gaps) => *gaps,
+            None => self.layout,
+        };
+    }
+
+    /// Whether there are no gaps anywhere, in which case layout files are
+    /// written without them.
+    pub fn is_empty(&self) -> bool {
+        return self.layout == Gaps::default()
+            && self.variants.values().all(|gaps| *gaps == Gaps::default());
+    }
+
+    /// Keeps the gaps of the variants after `variant_idx` with them when it
+    /// is deleted.
+    pub fn variant_deleted(&mut self, variant_idx: usize) {
+        self.variants = std::mem::take(&mut self.variants)
+            .into_iter()
+            .filter(|(idx, _)| *idx != variant_idx)
+            .map(|(idx, gaps)| {
+                if idx > variant_idx {
+                    (idx - 1, gaps)
+                } else {
+                    (idx, gaps)
+                }
+            })
+            .collect();
+    }
+
+    pub fn variants_swapped(&mut self, idx1: usize, idx2: usize) {
+        let gaps1 = self.variants.remove(&idx1);
+
+        let gaps2 = self.variants.remove(&idx2);
+
+        if let Some(gaps) = gaps1 {
+            self.variants.insert(idx2, gaps);
+        }
+
+        if let Some(gaps) = gaps2 {
+            self.variants.insert(idx1, gaps);
+        }
+    }
+
+    /// Parses gaps written by [`GapSettings::to_json`], for example
+    /// `{"inner": 8, "outer": 16, "variants": {"1": {"inner": 0}}}`. Values
+    /// that are left out are 0, and negative gaps are rejected.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let mut settings = GapSettings {
+            layout: Gaps::from_json(value)?,
+            variants: BTreeMap::new(),
+        };
+
+        if let Some(variants) = value.get("variants") {
+            for (idx, gaps) in variants.as_object()? {
+                settings
+                    .variants
+                    .insert(idx.parse().ok()?, Gaps::from_json(gaps)?);
+            }
+        }
+
+        return Some(settings);
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut value = self.layout.to_json();
+
+        if !self.variants.is_empty() {
+            let variants: Map<String, Value> = self
+                .variants
+                .iter()
+                .map(|(idx, gaps)| (idx.to_string(), gaps.to_json()))
+                .collect();
+
+            value["variants"] = Value::Object(variants);
+        }
+
+        return value;
+    }
+}
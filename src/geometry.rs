@@ -0,0 +1,354 @@
+use himewm_layout::{Direction, Zone};
+
+pub const MIN_ZONE_SIZE: i32 = 16;
+
+/// A border shared by adjacent zones. A `Direction::Horizontal` edge sits at
+/// an x coordinate, matching the axis `SplitDirection::Horizontal` splits
+/// along.
+#[derive(Clone)]
+pub struct ZoneEdge {
+    pub direction: Direction,
+    pub position: i32,
+    pub zone_idxs: Vec<usize>,
+}
+
+/// One of the four sides of a zone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneSide {
+    Left,
+    Top,
+    Right,
+    Bottom,
+}
+
+impl ZoneSide {
+    pub const ALL: [ZoneSide; 4] = [
+        ZoneSide::Left,
+        ZoneSide::Top,
+        ZoneSide::Right,
+        ZoneSide::Bottom,
+    ];
+
+    /// The direction of the edges this side can lie on, following the
+    /// convention of `ZoneEdge`.
+    pub fn direction(self) -> Direction {
+        return match self {
+            ZoneSide::Left | ZoneSide::Right => Direction::Horizontal,
+            ZoneSide::Top | ZoneSide::Bottom => Direction::Vertical,
+        };
+    }
+
+    pub fn position(self, zone: &Zone) -> i32 {
+        return match self {
+            ZoneSide::Left => zone.left,
+            ZoneSide::Top => zone.top,
+            ZoneSide::Right => zone.right,
+            ZoneSide::Bottom => zone.bottom,
+        };
+    }
+}
+
+pub fn scale_zone(
+    zone: &Zone,
+    variant_width: f64,
+    variant_height: f64,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+) -> (i32, i32, i32, i32) {
+    return scale_rect(
+        (zone.left, zone.top, zone.right, zone.bottom),
+        variant_width,
+        variant_height,
+        x,
+        y,
+        w,
+        h,
+    );
+}
+
+/// Scales a (left, top, right, bottom) rectangle on the monitor the same way
+/// as `scale_zone`, for the parts of zones windows are placed in.
+pub fn scale_rect(
+    (left, top, right, bottom): (i32, i32, i32, i32),
+    variant_width: f64,
+    variant_height: f64,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+) -> (i32, i32, i32, i32) {
+    return (
+        ((left as f64 * w as f64) / variant_width).round() as i32 + x,
+        ((top as f64 * h as f64) / variant_height).round() as i32 + y,
+        (((right - left) as f64 * w as f64) / variant_width).round() as i32,
+        (((bottom - top) as f64 * h as f64) / variant_height).round() as i32,
+    );
+}
+
+/// The largest rectangle in the shape of a `monitor_w` by `monitor_h`
+/// monitor that fits in (`x`, `y`, `w`, `h`), centred in it.
+pub fn fit_aspect_ratio(
+    monitor_w: i32,
+    monitor_h: i32,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+) -> (i32, i32, i32, i32) {
+    if monitor_w <= 0 || monitor_h <= 0 {
+        return (x, y, w, h);
+    }
+
+    let scale = f64::min(w as f64 / monitor_w as f64, h as f64 / monitor_h as f64);
+
+    let fitted_w = (monitor_w as f64 * scale).round() as i32;
+
+    let fitted_h = (monitor_h as f64 * scale).round() as i32;
+
+    return (
+        x + (w - fitted_w) / 2,
+        y + (h - fitted_h) / 2,
+        fitted_w,
+        fitted_h,
+    );
+}
+
+pub fn zone_at(zones: &[Zone], x: i32, y: i32) -> Option<usize> {
+    return zones
+        .iter()
+        .position(|zone| zone.left <= x && x < zone.right && zone.top <= y && y < zone.bottom);
+}
+
+/// Picks the split that cuts `zone` parallel to whichever of its edges is
+/// closest to (`x`, `y`), relative to the size of the zone.
+pub fn nearer_split_direction(zone: &Zone, x: i32, y: i32) -> Direction {
+    let dx = std::cmp::min(x - zone.left, zone.right - x) as f64 / zone.w() as f64;
+
+    let dy = std::cmp::min(y - zone.top, zone.bottom - y) as f64 / zone.h() as f64;
+
+    if dx <= dy {
+        return Direction::Horizontal;
+    } else {
+        return Direction::Vertical;
+    }
+}
+
+pub fn same_zones(zones: &[Zone], other: &[Zone]) -> bool {
+    return zones.len() == other.len()
+        && zones.iter().zip(other).all(|(zone, other)| {
+            zone.left == other.left
+                && zone.top == other.top
+                && zone.right == other.right
+                && zone.bottom == other.bottom
+        });
+}
+
+fn start(zone: &Zone, direction: Direction) -> i32 {
+    return match direction {
+        Direction::Horizontal => zone.left,
+        Direction::Vertical => zone.top,
+    };
+}
+
+fn end(zone: &Zone, direction: Direction) -> i32 {
+    return match direction {
+        Direction::Horizontal => zone.right,
+        Direction::Vertical => zone.bottom,
+    };
+}
+
+fn span(zone: &Zone, direction: Direction) -> (i32, i32) {
+    return match direction {
+        Direction::Horizontal => (zone.top, zone.bottom),
+        Direction::Vertical => (zone.left, zone.right),
+    };
+}
+
+/// Finds the edge at `position` that passes through `along`, including every
+/// zone that borders it without interruption. Returns `None` if no zone lies
+/// on one of its sides, as is the case for the borders of the monitor.
+pub fn find_edge(
+    zones: &[Zone],
+    direction: Direction,
+    position: i32,
+    along: i32,
+) -> Option<ZoneEdge> {
+    let touching: Vec<usize> = (0..zones.len())
+        .filter(|&i| {
+            start(&zones[i], direction) == position || end(&zones[i], direction) == position
+        })
+        .collect();
+
+    let (mut span_start, mut span_end) = touching
+        .iter()
+        .map(|&i| span(&zones[i], direction))
+        .find(|&(s, e)| s <= along && along <= e)?;
+
+    let mut zone_idxs = Vec::new();
+
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for &i in &touching {
+            let (s, e) = span(&zones[i], direction);
+
+            if zone_idxs.contains(&i) || s >= span_end || e <= span_start {
+                continue;
+            }
+
+            span_start = std::cmp::min(span_start, s);
+
+            span_end = std::cmp::max(span_end, e);
+
+            zone_idxs.push(i);
+
+            changed = true;
+        }
+    }
+
+    let has_before = zone_idxs
+        .iter()
+        .any(|&i| end(&zones[i], direction) == position);
+
+    let has_after = zone_idxs
+        .iter()
+        .any(|&i| start(&zones[i], direction) == position);
+
+    if !has_before || !has_after {
+        return None;
+    }
+
+    return Some(ZoneEdge {
+        direction,
+        position,
+        zone_idxs,
+    });
+}
+
+/// Finds the edge `side` of the zone at `zone_idx` lies on, or `None` if it
+/// lies on the border of the monitor.
+pub fn side_edge(zones: &[Zone], zone_idx: usize, side: ZoneSide) -> Option<ZoneEdge> {
+    let zone = zones.get(zone_idx)?;
+
+    let direction = side.direction();
+
+    let (span_start, span_end) = span(zone, direction);
+
+    return find_edge(
+        zones,
+        direction,
+        side.position(zone),
+        (span_start + span_end) / 2,
+    );
+}
+
+/// Finds the edge closest to the point (`x`, `y`) within `tolerance`.
+pub fn edge_near(zones: &[Zone], x: i32, y: i32, tolerance: i32) -> Option<ZoneEdge> {
+    let mut closest: Option<(i32, ZoneEdge)> = None;
+
+    for zone in zones {
+        let candidates = [
+            (Direction::Horizontal, zone.left, x, y),
+            (Direction::Horizontal, zone.right, x, y),
+            (Direction::Vertical, zone.top, y, x),
+            (Direction::Vertical, zone.bottom, y, x),
+        ];
+
+        for (direction, position, across, along) in candidates {
+            let distance = (across - position).abs();
+
+            let (s, e) = span(zone, direction);
+
+            if distance > tolerance || along < s || along > e {
+                continue;
+            }
+
+            if let Some((closest_distance, _)) = &closest {
+                if *closest_distance <= distance {
+                    continue;
+                }
+            }
+
+            if let Some(edge) = find_edge(zones, direction, position, along) {
+                closest = Some((distance, edge));
+            }
+        }
+    }
+
+    return closest.map(|(_, edge)| edge);
+}
+
+/// The range `edge` can be moved within without shrinking any of its zones
+/// below `MIN_ZONE_SIZE`.
+pub fn edge_bounds(zones: &[Zone], edge: &ZoneEdge) -> (i32, i32) {
+    let mut min = i32::MIN;
+
+    let mut max = i32::MAX;
+
+    for &i in &edge.zone_idxs {
+        let zone = &zones[i];
+
+        if end(zone, edge.direction) == edge.position {
+            min = std::cmp::max(min, start(zone, edge.direction) + MIN_ZONE_SIZE);
+        }
+
+        if start(zone, edge.direction) == edge.position {
+            max = std::cmp::min(max, end(zone, edge.direction) - MIN_ZONE_SIZE);
+        }
+    }
+
+    return (min, max);
+}
+
+/// Snaps `position` to the nearest edge of another zone or to a common
+/// fraction of `extent` if one lies within `tolerance`.
+pub fn snap_edge(
+    zones: &[Zone],
+    edge: &ZoneEdge,
+    position: i32,
+    extent: i32,
+    tolerance: i32,
+) -> i32 {
+    let fractions = [0.25, 1.0 / 3.0, 0.5, 2.0 / 3.0, 0.75]
+        .into_iter()
+        .map(|fraction| (extent as f64 * fraction).round() as i32);
+
+    let zone_edges = zones
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !edge.zone_idxs.contains(i))
+        .flat_map(|(_, zone)| [start(zone, edge.direction), end(zone, edge.direction)]);
+
+    return fractions
+        .chain(zone_edges)
+        .filter(|candidate| (candidate - position).abs() <= tolerance)
+        .min_by_key(|candidate| (candidate - position).abs())
+        .unwrap_or(position);
+}
+
+pub fn move_edge(zones: &mut [Zone], edge: &ZoneEdge, position: i32) {
+    for &i in &edge.zone_idxs {
+        let zone = &mut zones[i];
+
+        match edge.direction {
+            Direction::Horizontal => {
+                if zone.left == edge.position {
+                    zone.left = position;
+                } else if zone.right == edge.position {
+                    zone.right = position;
+                }
+            }
+            Direction::Vertical => {
+                if zone.top == edge.position {
+                    zone.top = position;
+                } else if zone.bottom == edge.position {
+                    zone.bottom = position;
+                }
+            }
+        }
+    }
+}
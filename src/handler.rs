@@ -1,811 +1,959 @@
-use crate::{LayoutEditorGUI, Message, SwapDirection};
+use crate::{
+    editor::Action, repeating, split_position, LayoutEditorGUI, Message, RepeatingSplitSpec,
+    SwapDirection,
+};
 use fltk::{prelude::InputExt, *};
 use himewm_layout::*;
 use prelude::{GroupExt, WidgetBase, WidgetExt};
 
 pub fn handle_events(layout_editor: &mut LayoutEditorGUI) {
+    if let None = layout_editor.editor_widgets {
+        return;
+    }
+
+    if let Some(msg) = layout_editor.receiver.recv() {
+        let modifies_layout = msg.modifies_layout();
+
+        if msg.blocked_when_readonly() && layout_editor.readonly {
+            return;
+        }
+
+        handle_message(layout_editor, msg);
+
+        if let Some(editor_widgets) = &mut layout_editor.editor_widgets {
+            if modifies_layout {
+                editor_widgets.update_diagnostics();
+            }
+
+            // Anything the strips show only changes in response to a message
+            editor_widgets.update_thumbnail_strips(layout_editor.comparison);
+        }
+    }
+}
+
+fn handle_message(layout_editor: &mut LayoutEditorGUI, msg: Message) {
     let editor_widgets = match &mut layout_editor.editor_widgets {
         Some(val) => val,
 
         None => return,
     };
 
-    if let Some(msg) = layout_editor.receiver.recv() {
-        match msg {
-            Message::SelectedVariantChanged(idx) => {
-                editor_widgets.reset_zone_selection();
+    match msg {
+        Message::SelectedVariantChanged(idx) => {
+            editor_widgets.reset_zone_selection();
 
-                if let Some(_) = &editor_widgets.buffers {
-                    editor_widgets
-                        .end_behaviour_actions
-                        .cancel_preview_button
-                        .deactivate();
-
-                    editor_widgets.actions.widgets.activate();
-
-                    editor_widgets.remove_extend_preview();
-
-                    editor_widgets.end_behaviour_actions.preview_count = 0;
-                }
+            if idx != editor_widgets.editor.selected_variant_idx() {
+                editor_widgets.cancel_extend_preview();
+            }
 
-                let old_variant_idx = editor_widgets.editor.selected_variant_idx;
+            let old_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                let old_variant_state_idx = editor_widgets.editor.selected_variant_state_idx;
+            let old_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                editor_widgets.editor.selected_variant_idx = idx;
+            if let Err(_) = editor_widgets.editor.apply(Action::SelectVariant(idx)) {
+                return;
+            }
 
-                editor_widgets.editor.selected_variant_state_idx = 0;
+            editor_widgets.update_highlighted_variant(old_variant_idx, idx);
 
-                editor_widgets.update_highlighted_variant(old_variant_idx, idx);
+            editor_widgets.update_shown_variant_state_selection(old_variant_idx, idx);
 
-                editor_widgets.update_shown_variant_state_selection(old_variant_idx, idx);
+            editor_widgets.update_highlighted_variant_state_button(
+                (old_variant_idx, old_variant_state_idx),
+                (idx, 0),
+            );
 
-                editor_widgets.update_highlighted_variant_state_button(
-                    (old_variant_idx, old_variant_state_idx),
-                    (idx, 0),
-                );
+            editor_widgets
+                .update_shown_variant_state((old_variant_idx, old_variant_state_idx), (idx, 0));
 
+            if idx == editor_widgets.editor.layout().default_variant_idx() {
                 editor_widgets
-                    .update_shown_variant_state((old_variant_idx, old_variant_state_idx), (idx, 0));
-
-                if idx == editor_widgets.editor.layout.default_variant_idx() {
-                    editor_widgets
-                        .variant_actions
-                        .set_as_default_button
-                        .deactivate();
-                } else if !editor_widgets
                     .variant_actions
                     .set_as_default_button
-                    .active()
-                {
-                    editor_widgets
-                        .variant_actions
-                        .set_as_default_button
-                        .activate();
-                }
-
-                editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
+                    .deactivate();
+            } else if !editor_widgets
+                .variant_actions
+                .set_as_default_button
+                .active()
+            {
+                editor_widgets
+                    .variant_actions
+                    .set_as_default_button
+                    .activate();
             }
 
-            Message::SelectedVariantStateChanged(idx) => {
-                editor_widgets.reset_zone_selection();
+            editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
 
-                let variant_idx = editor_widgets.editor.selected_variant_idx;
+            editor_widgets.update_end_behaviour_actions(&layout_editor.sender);
 
-                let old_idx = editor_widgets.editor.selected_variant_state_idx;
+            editor_widgets
+                .gap_actions
+                .update(editor_widgets.editor.gaps(), idx);
+        }
 
-                editor_widgets.editor.selected_variant_state_idx = idx;
+        Message::SelectedVariantStateChanged(idx) => {
+            editor_widgets.reset_zone_selection();
 
-                editor_widgets.update_highlighted_variant_state_button(
-                    (variant_idx, old_idx),
-                    (variant_idx, idx),
-                );
+            let variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                editor_widgets
-                    .update_shown_variant_state((variant_idx, old_idx), (variant_idx, idx));
+            let old_idx = editor_widgets.editor.selected_variant_state_idx();
+
+            if let Err(_) = editor_widgets.editor.apply(Action::SelectVariantState(idx)) {
+                return;
             }
 
-            Message::SelectedZoneChanged(idx) => {
-                let selected_variant_idx = editor_widgets.editor.selected_variant_idx;
+            editor_widgets.update_highlighted_variant_state_button(
+                (variant_idx, old_idx),
+                (variant_idx, idx),
+            );
 
-                let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx;
+            editor_widgets.update_shown_variant_state((variant_idx, old_idx), (variant_idx, idx));
+        }
 
-                if app::is_event_shift() {
-                    if editor_widgets.editor.selected_zone_idx1 == None
-                        || editor_widgets.editor.selected_zone_idx1 == Some(idx)
-                    {
-                        return;
-                    }
+        Message::SelectedZoneChanged(idx) => {
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                    if let Some(old_idx) = editor_widgets.editor.selected_zone_idx2 {
-                        editor_widgets.dehighlight_zone(
-                            selected_variant_idx,
-                            selected_variant_state_idx,
-                            old_idx,
-                        );
+            let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                        if old_idx == idx {
-                            editor_widgets.editor.selected_zone_idx2 = None;
+            let old_selection = [
+                editor_widgets.editor.selected_zone_idx1(),
+                editor_widgets.editor.selected_zone_idx2(),
+            ];
 
-                            editor_widgets.actions.split_button.set_label("Split");
+            let action = if app::is_event_shift() {
+                Action::AddZoneToSelection(idx)
+            } else {
+                Action::SelectZone(idx)
+            };
 
-                            editor_widgets.actions.split_button.activate();
+            if let Err(_) = editor_widgets.editor.apply(action) {
+                return;
+            }
 
-                            editor_widgets.update_split_bounds();
+            for zone_idx in old_selection.into_iter().flatten() {
+                editor_widgets.dehighlight_zone(
+                    selected_variant_idx,
+                    selected_variant_state_idx,
+                    zone_idx,
+                );
+            }
 
-                            editor_widgets.actions.merge_button.deactivate();
+            let new_selection = [
+                editor_widgets.editor.selected_zone_idx1(),
+                editor_widgets.editor.selected_zone_idx2(),
+            ];
 
-                            editor_widgets.actions.swap_button.deactivate();
+            for zone_idx in new_selection.into_iter().flatten() {
+                editor_widgets.highlight_selected_zone(zone_idx);
+            }
 
-                            return;
-                        }
-                    }
+            editor_widgets.update_zone_actions();
+        }
 
-                    editor_widgets.editor.selected_zone_idx2 = Some(idx);
+        Message::NewVariantState => {
+            if let Err(_) = editor_widgets.editor.apply(Action::NewVariantState) {
+                return;
+            }
 
-                    editor_widgets.highlight_selected_zone(idx);
+            editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
 
-                    editor_widgets.actions.swap_button.activate();
+            editor_widgets.new_variant_state(&layout_editor.sender);
+        }
 
-                    let variant =
-                        &editor_widgets.editor.layout.get_variants()[selected_variant_idx];
+        Message::CloneVariantState => {
+            if let Err(_) = editor_widgets.editor.apply(Action::CloneVariantState) {
+                return;
+            }
 
-                    if variant.can_merge_zones(
-                        selected_variant_state_idx,
-                        editor_widgets.editor.selected_zone_idx1.unwrap(),
-                        idx,
-                    ) {
-                        editor_widgets
-                            .actions
-                            .split_button
-                            .set_label("Merge and split");
+            editor_widgets.new_variant_state(&layout_editor.sender);
+        }
 
-                        editor_widgets.actions.split_button.activate();
+        Message::DeleteVariantState => {
+            let idx = editor_widgets.editor.selected_variant_state_idx();
 
-                        editor_widgets.update_split_bounds();
+            if let Err(_) = editor_widgets.editor.apply(Action::DeleteVariantState) {
+                return;
+            }
 
-                        editor_widgets.actions.merge_button.activate();
-                    } else {
-                        editor_widgets.disable_split();
+            editor_widgets.delete_variant_state(idx, &layout_editor.sender);
 
-                        editor_widgets.actions.merge_button.deactivate();
-                    }
-                } else {
-                    editor_widgets.actions.split_button.set_label("Split");
+            layout_editor
+                .sender
+                .send(Message::SelectedVariantStateChanged(
+                    editor_widgets.editor.selected_variant_state_idx(),
+                ));
+        }
 
-                    editor_widgets.actions.merge_button.deactivate();
+        Message::SwapVariantState(swap_direction) => {
+            let idx = editor_widgets.editor.selected_variant_state_idx();
 
-                    editor_widgets.actions.swap_button.deactivate();
+            if let Err(_) = editor_widgets
+                .editor
+                .apply(Action::SwapVariantState(swap_direction))
+            {
+                return;
+            }
 
-                    let mut new_selection = true;
+            // The selection moves along with the swapped variant state
+            let swap_with = editor_widgets.editor.selected_variant_state_idx();
 
-                    if let Some(old_idx) = editor_widgets.editor.selected_zone_idx1 {
-                        editor_widgets.dehighlight_zone(
-                            selected_variant_idx,
-                            selected_variant_state_idx,
-                            old_idx,
-                        );
+            editor_widgets.swap_variant_states(idx, swap_with, &layout_editor.sender);
+        }
 
-                        if old_idx == idx {
-                            editor_widgets.editor.selected_zone_idx1 = None;
+        Message::SwapSplitDirection => {
+            editor_widgets.actions.selected_direction =
+                editor_widgets.actions.selected_direction.other();
 
-                            new_selection = false;
-                        }
-                    }
+            match editor_widgets.actions.selected_direction {
+                Direction::Horizontal => {
+                    editor_widgets.actions.split_axis_text.set_label("x: ");
+                }
+                Direction::Vertical => {
+                    editor_widgets.actions.split_axis_text.set_label("y: ");
+                }
+            }
 
-                    if let Some(old_idx) = editor_widgets.editor.selected_zone_idx2 {
-                        editor_widgets.editor.selected_zone_idx2 = None;
+            if let Some(_) = editor_widgets.actions.split_bound_max {
+                editor_widgets.update_split_bounds();
+            }
+        }
 
-                        if old_idx == idx {
-                            editor_widgets.editor.selected_zone_idx1 = Some(idx);
+        Message::Split => {
+            let split_bound_max = match editor_widgets.actions.split_bound_max {
+                Some(val) => val,
+                None => return,
+            };
 
-                            editor_widgets.update_split_bounds();
+            let mut split_at_value = editor_widgets.actions.split_at_input.value();
 
-                            return;
-                        }
+            if split_at_value.trim().is_empty() {
+                split_at_value = "50%".to_string();
+            }
+
+            let split_at =
+                match split_position::parse_split_position(&split_at_value, split_bound_max) {
+                    Some(val) => val,
+                    None => {
+                        editor_widgets.reset_zone_selection();
 
-                        editor_widgets.dehighlight_zone(
-                            selected_variant_idx,
-                            selected_variant_state_idx,
-                            old_idx,
-                        );
+                        return;
                     }
+                };
 
-                    if new_selection {
-                        editor_widgets.editor.selected_zone_idx1 = Some(idx);
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                        editor_widgets.highlight_selected_zone(idx);
+            let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                        editor_widgets.actions.split_button.activate();
+            if let Err(_) = editor_widgets.editor.apply(Action::Split(
+                editor_widgets.actions.selected_direction,
+                split_at,
+            )) {
+                editor_widgets.reset_zone_selection();
 
-                        editor_widgets.update_split_bounds();
-                    } else {
-                        editor_widgets.disable_split();
-                    }
-                }
+                return;
             }
 
-            Message::NewVariantState => {
-                let w = editor_widgets.editor.layout.get_monitor_rect().w();
+            editor_widgets.actions.split_at_input.set_value("");
 
-                let h = editor_widgets.editor.layout.get_monitor_rect().h();
+            editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
 
-                let variant = &mut editor_widgets.editor.layout.get_variants_mut()
-                    [editor_widgets.editor.selected_variant_idx];
+            editor_widgets.update_variant_state_display(
+                selected_variant_idx,
+                selected_variant_state_idx,
+                &layout_editor.sender,
+            );
+        }
 
-                variant.new_zone_vec(w, h);
+        Message::SplitEvenly => {
+            let parts: i32 = match editor_widgets.actions.split_parts_input.value().parse() {
+                Ok(val) => val,
+                Err(_) => return,
+            };
 
-                variant.set_end_zone_idx(0);
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
+            let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                editor_widgets.new_variant_state(&layout_editor.sender);
+            if let Err(_) = editor_widgets.editor.apply(Action::SplitEvenly(
+                editor_widgets.actions.selected_direction,
+                parts,
+            )) {
+                return;
             }
 
-            Message::CloneVariantState => {
-                let variant = &mut editor_widgets.editor.layout.get_variants_mut()
-                    [editor_widgets.editor.selected_variant_idx];
+            editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
+
+            editor_widgets.update_variant_state_display(
+                selected_variant_idx,
+                selected_variant_state_idx,
+                &layout_editor.sender,
+            );
+        }
 
-                let idx = editor_widgets.editor.selected_variant_state_idx;
+        Message::Swap => {
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                variant.clone_zone_vec(idx);
+            let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                editor_widgets.new_variant_state(&layout_editor.sender);
+            if let Err(_) = editor_widgets.editor.apply(Action::Swap) {
+                return;
             }
 
-            Message::DeleteVariantState => {
-                let variant = &mut editor_widgets.editor.layout.get_variants_mut()
-                    [editor_widgets.editor.selected_variant_idx];
+            editor_widgets.update_variant_state_display(
+                selected_variant_idx,
+                selected_variant_state_idx,
+                &layout_editor.sender,
+            );
+        }
 
-                let idx = editor_widgets.editor.selected_variant_state_idx;
+        Message::Merge => {
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                variant.delete_zones(idx);
+            let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                editor_widgets.delete_variant_state(&layout_editor.sender, None);
+            if let Err(_) = editor_widgets.editor.apply(Action::Merge) {
+                return;
             }
 
-            Message::SwapVariantState(swap_direction) => {
-                let variant = &mut editor_widgets.editor.layout.get_variants_mut()
-                    [editor_widgets.editor.selected_variant_idx];
+            editor_widgets.update_variant_state_display(
+                selected_variant_idx,
+                selected_variant_state_idx,
+                &layout_editor.sender,
+            );
+        }
 
-                let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx;
+        Message::NewVariant => {
+            editor_widgets.cancel_extend_preview();
 
-                let swap_with = match swap_direction {
-                    SwapDirection::Previous if selected_variant_state_idx != 0 => {
-                        selected_variant_state_idx - 1
-                    }
-                    SwapDirection::Next
-                        if editor_widgets.editor.selected_variant_state_idx
-                            != variant.manual_zones_until() - 1 =>
-                    {
-                        selected_variant_state_idx + 1
-                    }
-                    _ => return,
-                };
+            if let Err(_) = editor_widgets.editor.apply(Action::NewVariant) {
+                return;
+            }
 
-                variant.swap_zone_vectors(selected_variant_state_idx, swap_with);
+            let idx = editor_widgets.editor.layout().variants_len() - 1;
 
-                editor_widgets.swap_variant_states(swap_with, &layout_editor.sender);
-            }
+            editor_widgets.add_variant_widgets(&layout_editor.sender);
 
-            Message::SwapSplitDirection => {
-                editor_widgets.actions.selected_direction =
-                    editor_widgets.actions.selected_direction.other();
+            layout_editor
+                .sender
+                .send(Message::SelectedVariantChanged(idx));
 
-                match editor_widgets.actions.selected_direction {
-                    Direction::Horizontal => {
-                        editor_widgets.actions.split_axis_text.set_label("x: ");
-                    }
-                    Direction::Vertical => {
-                        editor_widgets.actions.split_axis_text.set_label("y: ");
-                    }
-                }
+            if !editor_widgets.variant_actions.delete_button.active() {
+                editor_widgets.variant_actions.delete_button.activate();
+            }
+        }
 
-                if let Some(_) = editor_widgets.actions.split_bound_max {
-                    editor_widgets.update_split_bounds();
-                }
+        Message::CloneVariant => {
+            editor_widgets.cancel_extend_preview();
+
+            if let Err(_) = editor_widgets.editor.apply(Action::CloneVariant) {
+                return;
             }
 
-            Message::Split => {
-                let split_at: i32 = match editor_widgets.actions.split_at_input.value().parse() {
-                    Ok(val) if val > 0 && val < editor_widgets.actions.split_bound_max.unwrap() => {
-                        val
-                    }
-                    _ => {
-                        editor_widgets.reset_zone_selection();
+            let idx = editor_widgets.editor.layout().variants_len() - 1;
 
-                        return;
-                    }
-                };
+            editor_widgets.add_variant_widgets(&layout_editor.sender);
 
-                editor_widgets.actions.split_at_input.set_value("");
+            layout_editor
+                .sender
+                .send(Message::SelectedVariantChanged(idx));
 
-                let mut zone_idx = editor_widgets.editor.selected_zone_idx1.unwrap();
+            if !editor_widgets.variant_actions.delete_button.active() {
+                editor_widgets.variant_actions.delete_button.activate();
+            }
+        }
 
-                let selected_variant_idx = editor_widgets.editor.selected_variant_idx;
+        Message::DeleteVariant => {
+            editor_widgets.cancel_extend_preview();
 
-                let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx;
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                let variant =
-                    &mut editor_widgets.editor.layout.get_variants_mut()[selected_variant_idx];
+            let old_default_variant_idx = editor_widgets.editor.layout().default_variant_idx();
 
-                let other_zone_idx = if let Some(idx) = editor_widgets.editor.selected_zone_idx2 {
-                    variant.merge_zones(selected_variant_state_idx, zone_idx, idx);
+            if let Err(_) = editor_widgets.editor.apply(Action::DeleteVariant) {
+                return;
+            }
 
-                    zone_idx = std::cmp::min(zone_idx, idx);
+            let variants_pack =
+                group::Pack::from_dyn_widget(&editor_widgets.variant_list.child(0).unwrap())
+                    .unwrap();
 
-                    Some(std::cmp::max(zone_idx, idx))
-                } else {
-                    None
-                };
+            let variant_state_selection = &editor_widgets.variant_state_selection;
 
-                let zone = &variant.get_zones()[selected_variant_state_idx][zone_idx];
+            let variant_state_display = &editor_widgets.variant_state_display;
 
-                let direction = match editor_widgets.actions.selected_direction {
-                    Direction::Horizontal => SplitDirection::Horizontal(zone.left + split_at),
-                    Direction::Vertical => SplitDirection::Vertical(zone.top + split_at),
-                };
+            WidgetBase::delete(variants_pack.child(selected_variant_idx as i32).unwrap());
 
-                variant.split(selected_variant_state_idx, zone_idx, direction);
+            WidgetBase::delete(
+                variant_state_selection
+                    .child(selected_variant_idx as i32)
+                    .unwrap(),
+            );
 
-                if let Some(idx) = other_zone_idx {
-                    let zone = variant.get_zones_mut()[selected_variant_state_idx]
-                        .pop()
-                        .unwrap();
+            WidgetBase::delete(
+                variant_state_display
+                    .child(selected_variant_idx as i32)
+                    .unwrap(),
+            );
 
-                    variant.get_zones_mut()[selected_variant_state_idx].insert(idx, zone);
-                }
+            layout_editor.sender.send(Message::SelectedVariantChanged(
+                editor_widgets.editor.selected_variant_idx(),
+            ));
 
-                editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
+            for i in selected_variant_idx as i32..variants_pack.children() {
+                let b =
+                    &mut button::Button::from_dyn_widget(&variants_pack.child(i).unwrap()).unwrap();
 
-                editor_widgets.update_variant_state_display(
-                    selected_variant_idx,
-                    selected_variant_state_idx,
-                    &layout_editor.sender,
+                b.set_label(i.to_string().as_str());
+
+                b.emit(
+                    layout_editor.sender.clone(),
+                    Message::SelectedVariantChanged(i as usize),
                 );
             }
 
-            Message::Swap => {
-                let selected_variant_idx = editor_widgets.editor.selected_variant_idx;
+            editor_widgets.update_default_variant_label(
+                old_default_variant_idx,
+                editor_widgets.editor.layout().default_variant_idx(),
+            );
 
-                let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx;
+            if editor_widgets.editor.layout().variants_len() == 1 {
+                editor_widgets.variant_actions.delete_button.deactivate();
+            }
+        }
 
-                let selected_zone_idx1 = editor_widgets.editor.selected_zone_idx1.unwrap();
+        Message::SwapVariant(swap_direction) => {
+            editor_widgets.cancel_extend_preview();
 
-                let selected_zone_idx2 = editor_widgets.editor.selected_zone_idx2.unwrap();
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                let variant =
-                    &mut editor_widgets.editor.layout.get_variants_mut()[selected_variant_idx];
+            if let Err(_) = editor_widgets
+                .editor
+                .apply(Action::SwapVariant(swap_direction))
+            {
+                return;
+            }
 
-                variant.swap_zones(
-                    selected_variant_state_idx,
-                    selected_zone_idx1,
-                    selected_zone_idx2,
-                );
+            // The selection moves along with the swapped variant
+            let new_idx = editor_widgets.editor.selected_variant_idx();
 
-                editor_widgets.update_variant_state_display(
-                    selected_variant_idx,
-                    selected_variant_state_idx,
-                    &layout_editor.sender,
-                );
+            let variants_pack =
+                &mut group::Pack::from_dyn_widget(&editor_widgets.variant_list.child(0).unwrap())
+                    .unwrap();
+
+            let variant_state_selection = &mut editor_widgets.variant_state_selection;
+
+            let variant_state_display = &mut editor_widgets.variant_state_display;
+
+            let first_idx = std::cmp::min(selected_variant_idx, new_idx);
+
+            let second_idx = std::cmp::max(selected_variant_idx, new_idx);
+
+            let first_variant_button = &mut button::Button::from_dyn_widget(
+                &variants_pack.child(first_idx as i32).unwrap(),
+            )
+            .unwrap();
+
+            let second_variant_button = &mut button::Button::from_dyn_widget(
+                &variants_pack.child(second_idx as i32).unwrap(),
+            )
+            .unwrap();
+
+            if second_idx == editor_widgets.editor.layout().default_variant_idx() {
+                first_variant_button.set_label(format!("{second_idx} (default)").as_str());
+            } else {
+                first_variant_button.set_label(second_idx.to_string().as_str());
             }
 
-            Message::Merge => {
-                let selected_variant_idx = editor_widgets.editor.selected_variant_idx;
+            if first_idx == editor_widgets.editor.layout().default_variant_idx() {
+                second_variant_button.set_label(format!("{first_idx} (default)").as_str());
+            } else {
+                second_variant_button.set_label(first_idx.to_string().as_str());
+            }
 
-                let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx;
+            first_variant_button.emit(
+                layout_editor.sender.clone(),
+                Message::SelectedVariantChanged(second_idx),
+            );
 
-                let selected_zone_idx1 = editor_widgets.editor.selected_zone_idx1.unwrap();
+            second_variant_button.emit(
+                layout_editor.sender.clone(),
+                Message::SelectedVariantChanged(first_idx),
+            );
 
-                let selected_zone_idx2 = editor_widgets.editor.selected_zone_idx2.unwrap();
+            let first_state_selection_pack =
+                variant_state_selection.child(first_idx as i32).unwrap();
 
-                let variant =
-                    &mut editor_widgets.editor.layout.get_variants_mut()[selected_variant_idx];
+            let second_state_selection_pack =
+                variant_state_selection.child(second_idx as i32).unwrap();
 
-                variant.merge_zones(
-                    selected_variant_state_idx,
-                    selected_zone_idx1,
-                    selected_zone_idx2,
-                );
+            let first_variant_state_display_group =
+                variant_state_display.child(first_idx as i32).unwrap();
 
-                editor_widgets.editor.selected_zone_idx1 = None;
+            let second_variant_state_display_group =
+                variant_state_display.child(second_idx as i32).unwrap();
 
-                editor_widgets.editor.selected_zone_idx2 = None;
+            variants_pack.remove_by_index(second_idx as i32);
 
-                editor_widgets.update_variant_state_display(
-                    selected_variant_idx,
-                    selected_variant_state_idx,
-                    &layout_editor.sender,
-                );
-            }
+            variants_pack.remove_by_index(first_idx as i32);
 
-            Message::NewVariant => {
-                let layout = &editor_widgets.editor.layout;
+            variant_state_selection.remove_by_index(second_idx as i32);
 
-                let w = layout.get_monitor_rect().w();
+            variant_state_selection.remove_by_index(first_idx as i32);
 
-                let h = layout.get_monitor_rect().h();
+            variant_state_display.remove_by_index(second_idx as i32);
 
-                let new_variant = Variant::new(w, h);
+            variant_state_display.remove_by_index(first_idx as i32);
 
-                editor_widgets.add_new_variant(new_variant, &layout_editor.sender);
+            variants_pack.insert(second_variant_button, first_idx as i32);
 
-                layout_editor.sender.send(Message::SelectedVariantChanged(
-                    editor_widgets.editor.layout.variants_len() - 1,
-                ));
+            variants_pack.insert(first_variant_button, second_idx as i32);
 
-                if !editor_widgets.variant_actions.delete_button.active() {
-                    editor_widgets.variant_actions.delete_button.activate();
-                }
-            }
+            variant_state_selection.insert(&second_state_selection_pack, first_idx as i32);
 
-            Message::CloneVariant => {
-                let layout = &mut editor_widgets.editor.layout;
+            variant_state_selection.insert(&first_state_selection_pack, second_idx as i32);
 
-                let new_variant =
-                    layout.get_variants()[editor_widgets.editor.selected_variant_idx].clone();
+            variant_state_display.insert(&second_variant_state_display_group, first_idx as i32);
 
-                editor_widgets.add_new_variant(new_variant, &layout_editor.sender);
+            variant_state_display.insert(&first_variant_state_display_group, second_idx as i32);
 
-                layout_editor.sender.send(Message::SelectedVariantChanged(
-                    editor_widgets.editor.layout.variants_len() - 1,
-                ));
+            layout_editor
+                .sender
+                .send(Message::SelectedVariantChanged(new_idx));
+        }
 
-                if !editor_widgets.variant_actions.delete_button.active() {
-                    editor_widgets.variant_actions.delete_button.activate();
-                }
-            }
+        Message::SetVariantAsDefault => {
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-            Message::DeleteVariant => {
-                let selected_variant_idx = editor_widgets.editor.selected_variant_idx;
+            let old_default_variant_idx = editor_widgets.editor.layout().default_variant_idx();
 
-                let old_default_variant_idx = editor_widgets.editor.layout.default_variant_idx();
+            if let Err(_) = editor_widgets.editor.apply(Action::SetVariantAsDefault) {
+                return;
+            }
 
-                editor_widgets
-                    .editor
-                    .layout
-                    .delete_variant(selected_variant_idx);
+            editor_widgets
+                .update_default_variant_label(old_default_variant_idx, selected_variant_idx);
 
-                let variants_pack =
-                    group::Pack::from_dyn_widget(&editor_widgets.variant_list.child(0).unwrap())
-                        .unwrap();
+            editor_widgets
+                .variant_actions
+                .set_as_default_button
+                .deactivate();
+        }
 
-                let variant_state_selection = &editor_widgets.variant_state_selection;
+        Message::CancelPreview => editor_widgets.cancel_extend_preview(),
 
-                let variant_state_display = &editor_widgets.variant_state_display;
+        Message::PreviewExtend => editor_widgets.preview_extend(),
 
-                WidgetBase::delete(variants_pack.child(selected_variant_idx as i32).unwrap());
+        Message::EndZoneIdxChanged(idx) => {
+            if let Err(_) = editor_widgets.editor.apply(Action::SetEndZoneIdx(idx)) {
+                return;
+            }
+        }
 
-                WidgetBase::delete(
-                    variant_state_selection
-                        .child(selected_variant_idx as i32)
-                        .unwrap(),
-                );
+        Message::SwapEndTilingBehaviour => {
+            match editor_widgets
+                .editor
+                .selected_variant()
+                .get_end_tiling_behaviour()
+                .to_owned()
+            {
+                EndTilingBehaviour::Directional {
+                    direction,
+                    from_zones,
+                    zone_idx,
+                } => {
+                    editor_widgets
+                        .end_behaviour_actions
+                        .directional
+                        .widgets
+                        .hide();
 
-                WidgetBase::delete(
-                    variant_state_display
-                        .child(selected_variant_idx as i32)
-                        .unwrap(),
-                );
+                    editor_widgets
+                        .end_behaviour_actions
+                        .repeating
+                        .widgets
+                        .show();
+
+                    editor_widgets
+                        .end_behaviour_actions
+                        .directional
+                        .end_tiling_behaviour = EndTilingBehaviour::Directional {
+                        direction,
+                        from_zones,
+                        zone_idx,
+                    };
 
-                if selected_variant_idx == editor_widgets.editor.layout.variants_len() {
-                    editor_widgets.editor.selected_variant_idx = selected_variant_idx - 1;
+                    let end_tiling_behaviour = editor_widgets
+                        .end_behaviour_actions
+                        .repeating
+                        .end_tiling_behaviour
+                        .to_owned();
 
-                    layout_editor
-                        .sender
-                        .send(Message::SelectedVariantChanged(selected_variant_idx - 1));
-                } else {
-                    layout_editor
-                        .sender
-                        .send(Message::SelectedVariantChanged(selected_variant_idx));
+                    if let Err(_) = editor_widgets
+                        .editor
+                        .apply(Action::SetEndTilingBehaviour(end_tiling_behaviour))
+                    {
+                        return;
+                    }
                 }
+                EndTilingBehaviour::Repeating { splits, zone_idx } => {
+                    editor_widgets
+                        .end_behaviour_actions
+                        .repeating
+                        .widgets
+                        .hide();
 
-                for i in selected_variant_idx as i32..variants_pack.children() {
-                    let b = &mut button::Button::from_dyn_widget(&variants_pack.child(i).unwrap())
-                        .unwrap();
+                    editor_widgets
+                        .end_behaviour_actions
+                        .directional
+                        .widgets
+                        .show();
 
-                    b.set_label(i.to_string().as_str());
+                    editor_widgets
+                        .end_behaviour_actions
+                        .repeating
+                        .end_tiling_behaviour = EndTilingBehaviour::Repeating { splits, zone_idx };
+
+                    let end_tiling_behaviour = editor_widgets
+                        .end_behaviour_actions
+                        .directional
+                        .end_tiling_behaviour
+                        .to_owned();
 
-                    b.emit(
-                        layout_editor.sender.clone(),
-                        Message::SelectedVariantChanged(i as usize),
-                    );
+                    if let Err(_) = editor_widgets
+                        .editor
+                        .apply(Action::SetEndTilingBehaviour(end_tiling_behaviour))
+                    {
+                        return;
+                    }
                 }
+            }
 
-                editor_widgets.update_default_variant_label(
-                    old_default_variant_idx,
-                    editor_widgets.editor.layout.default_variant_idx(),
-                );
+            editor_widgets.update_end_behaviour_actions(&layout_editor.sender);
+        }
 
-                if editor_widgets.editor.layout.variants_len() == 1 {
-                    editor_widgets.variant_actions.delete_button.deactivate();
-                }
+        Message::SwapEndTilingDirection => {
+            let new_direction = match editor_widgets
+                .editor
+                .selected_variant()
+                .get_end_tiling_direction()
+            {
+                Some(direction) => direction.other(),
+                None => return,
+            };
+
+            if let Err(_) = editor_widgets
+                .editor
+                .apply(Action::SetEndTilingDirection(new_direction))
+            {
+                return;
             }
 
-            Message::SwapVariant(swap_direction) => {
-                let selected_variant_idx = editor_widgets.editor.selected_variant_idx;
+            // The direction can be switched from the menu as well as the
+            // radio buttons
+            editor_widgets.update_end_behaviour_actions(&layout_editor.sender);
+        }
 
-                let new_idx;
+        Message::MoveZoneEdge(edge, position) => {
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                let variants_pack = &mut group::Pack::from_dyn_widget(
-                    &editor_widgets.variant_list.child(0).unwrap(),
-                )
-                .unwrap();
+            let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                let variant_state_selection = &mut editor_widgets.variant_state_selection;
+            if let Err(_) = editor_widgets
+                .editor
+                .apply(Action::MoveZoneEdge(edge, position))
+            {
+                return;
+            }
 
-                let variant_state_display = &mut editor_widgets.variant_state_display;
+            editor_widgets.update_variant_state_display(
+                selected_variant_idx,
+                selected_variant_state_idx,
+                &layout_editor.sender,
+            );
+        }
 
-                let first_idx;
+        Message::ZoneSideEdited(side) => {
+            let zone_idx = match editor_widgets.editor.selected_zone_idx1() {
+                Some(val) => val,
+                None => return,
+            };
 
-                let second_idx;
+            let row = side as usize;
 
-                match swap_direction {
-                    SwapDirection::Previous => {
-                        if selected_variant_idx == 0 {
-                            return;
-                        } else {
-                            first_idx = selected_variant_idx - 1;
+            let value = editor_widgets.zone_inspector.side_rows[row].0.value();
 
-                            second_idx = selected_variant_idx;
+            let extent = match side.direction() {
+                Direction::Horizontal => editor_widgets.editor.layout().get_monitor_rect().w(),
+                Direction::Vertical => editor_widgets.editor.layout().get_monitor_rect().h(),
+            };
 
-                            new_idx = selected_variant_idx - 1;
-                        }
-                    }
-                    SwapDirection::Next => {
-                        if selected_variant_idx == editor_widgets.editor.layout.variants_len() - 1 {
-                            return;
-                        } else {
-                            first_idx = selected_variant_idx;
+            let edited = match split_position::parse_split_position(&value, extent) {
+                Some(position) => editor_widgets
+                    .editor
+                    .apply(Action::SetZoneSide(zone_idx, side, position))
+                    .is_ok(),
+                None => false,
+            };
 
-                            second_idx = selected_variant_idx + 1;
+            editor_widgets.show_edited_zone(zone_idx, edited, &layout_editor.sender);
+        }
 
-                            new_idx = selected_variant_idx + 1;
-                        }
-                    }
-                };
+        Message::ZoneSizeEdited(direction) => {
+            let zone_idx = match editor_widgets.editor.selected_zone_idx1() {
+                Some(val) => val,
+                None => return,
+            };
 
-                editor_widgets
+            let (row, extent) = match direction {
+                Direction::Horizontal => (0, editor_widgets.editor.layout().get_monitor_rect().w()),
+                Direction::Vertical => (1, editor_widgets.editor.layout().get_monitor_rect().h()),
+            };
+
+            let value = editor_widgets.zone_inspector.size_rows[row].0.value();
+
+            let edited = match split_position::parse_split_position(&value, extent) {
+                Some(size) => editor_widgets
                     .editor
-                    .layout
-                    .swap_variants(first_idx, second_idx);
+                    .apply(Action::SetZoneSize(zone_idx, direction, size))
+                    .is_ok(),
+                None => false,
+            };
 
-                let first_variant_button = &mut button::Button::from_dyn_widget(
-                    &variants_pack.child(first_idx as i32).unwrap(),
-                )
-                .unwrap();
+            editor_widgets.show_edited_zone(zone_idx, edited, &layout_editor.sender);
+        }
 
-                let second_variant_button = &mut button::Button::from_dyn_widget(
-                    &variants_pack.child(second_idx as i32).unwrap(),
-                )
-                .unwrap();
+        Message::SplitZoneAt(zone_idx, direction, position) => {
+            editor_widgets.reset_zone_selection();
 
-                if second_idx == editor_widgets.editor.layout.default_variant_idx() {
-                    first_variant_button.set_label(format!("{second_idx} (default)").as_str());
-                } else {
-                    first_variant_button.set_label(second_idx.to_string().as_str());
-                }
+            let selected_variant_idx = editor_widgets.editor.selected_variant_idx();
 
-                if first_idx == editor_widgets.editor.layout.default_variant_idx() {
-                    second_variant_button.set_label(format!("{first_idx} (default)").as_str());
-                } else {
-                    second_variant_button.set_label(first_idx.to_string().as_str());
-                }
+            let selected_variant_state_idx = editor_widgets.editor.selected_variant_state_idx();
 
-                first_variant_button.emit(
-                    layout_editor.sender.clone(),
-                    Message::SelectedVariantChanged(second_idx),
-                );
+            if let Err(_) = editor_widgets
+                .editor
+                .apply(Action::SplitZoneAt(zone_idx, direction, position))
+            {
+                return;
+            }
 
-                second_variant_button.emit(
-                    layout_editor.sender.clone(),
-                    Message::SelectedVariantChanged(first_idx),
-                );
+            editor_widgets.update_end_zone_idx_choice(&layout_editor.sender);
+
+            editor_widgets.update_variant_state_display(
+                selected_variant_idx,
+                selected_variant_state_idx,
+                &layout_editor.sender,
+            );
+        }
+
+        Message::AddRepeatingSplit => {
+            // The menu and the command palette can send this while the
+            // variant is directional, which is not for them to switch
+            if let EndTilingBehaviour::Directional { .. } = editor_widgets
+                .editor
+                .selected_variant()
+                .get_end_tiling_behaviour()
+            {
+                return;
+            }
+
+            let variant = &editor_widgets.editor.layout().get_variants()
+                [editor_widgets.editor.selected_variant_idx()];
+
+            let mut splits = repeating::repeating_splits(variant);
+
+            splits.push(RepeatingSplitSpec::following(splits.last()));
+
+            layout_editor.window.begin();
+
+            editor_widgets.set_repeating_splits(&splits, true, &layout_editor.sender);
+
+            layout_editor.window.end();
+        }
+
+        Message::DeleteRepeatingSplit(idx) => {
+            let variant = &editor_widgets.editor.layout().get_variants()
+                [editor_widgets.editor.selected_variant_idx()];
+
+            let mut splits = repeating::repeating_splits(variant);
+
+            splits.remove(idx);
+
+            layout_editor.window.begin();
 
-                let first_state_selection_pack =
-                    variant_state_selection.child(first_idx as i32).unwrap();
+            editor_widgets.set_repeating_splits(&splits, true, &layout_editor.sender);
 
-                let second_state_selection_pack =
-                    variant_state_selection.child(second_idx as i32).unwrap();
+            layout_editor.window.end();
+        }
+
+        Message::SwapRepeatingSplit(idx, swap_direction) => {
+            let variant = &editor_widgets.editor.layout().get_variants()
+                [editor_widgets.editor.selected_variant_idx()];
+
+            let mut splits = repeating::repeating_splits(variant);
+
+            let swap_with = match swap_direction {
+                SwapDirection::Previous if idx != 0 => idx - 1,
+                SwapDirection::Next if idx != splits.len() - 1 => idx + 1,
+                _ => return,
+            };
 
-                let first_variant_state_display_group =
-                    variant_state_display.child(first_idx as i32).unwrap();
+            splits.swap(idx, swap_with);
 
-                let second_variant_state_display_group =
-                    variant_state_display.child(second_idx as i32).unwrap();
+            layout_editor.window.begin();
 
-                variants_pack.remove_by_index(second_idx as i32);
+            editor_widgets.set_repeating_splits(&splits, true, &layout_editor.sender);
 
-                variants_pack.remove_by_index(first_idx as i32);
+            layout_editor.window.end();
+        }
 
-                variant_state_selection.remove_by_index(second_idx as i32);
+        Message::RepeatingSplitChanged(idx) => {
+            let variant = &editor_widgets.editor.layout().get_variants()
+                [editor_widgets.editor.selected_variant_idx()];
 
-                variant_state_selection.remove_by_index(first_idx as i32);
+            let mut splits = repeating::repeating_splits(variant);
 
-                variant_state_display.remove_by_index(second_idx as i32);
+            splits[idx] = editor_widgets.end_behaviour_actions.repeating.rows[idx].value();
 
-                variant_state_display.remove_by_index(first_idx as i32);
+            layout_editor.window.begin();
 
-                variants_pack.insert(second_variant_button, first_idx as i32);
+            editor_widgets.set_repeating_splits(&splits, false, &layout_editor.sender);
 
-                variants_pack.insert(first_variant_button, second_idx as i32);
+            layout_editor.window.end();
+        }
 
-                variant_state_selection.insert(&second_state_selection_pack, first_idx as i32);
+        Message::FromZonesChanged(variant_state_idx) => {
+            layout_editor.window.begin();
 
-                variant_state_selection.insert(&first_state_selection_pack, second_idx as i32);
+            editor_widgets.set_from_zones(variant_state_idx, &layout_editor.sender);
 
-                variant_state_display.insert(&second_variant_state_display_group, first_idx as i32);
+            layout_editor.window.end();
+        }
 
-                variant_state_display.insert(&first_variant_state_display_group, second_idx as i32);
+        Message::DiagnosticSelected(idx) => {
+            let diagnostic = match editor_widgets.diagnostics.diagnostics.get(idx) {
+                Some(val) => val.to_owned(),
+                None => return,
+            };
 
-                editor_widgets.editor.selected_variant_idx = new_idx;
+            layout_editor
+                .sender
+                .send(Message::SelectedVariantChanged(diagnostic.variant_idx));
 
+            if let Some(idx) = diagnostic.variant_state_idx {
                 layout_editor
                     .sender
-                    .send(Message::SelectedVariantChanged(new_idx));
+                    .send(Message::SelectedVariantStateChanged(idx));
             }
 
-            Message::SetVariantAsDefault => {
-                let selected_variant_idx = editor_widgets.editor.selected_variant_idx;
+            if let Some(idx) = diagnostic.zone_idx {
+                layout_editor.sender.send(Message::SelectedZoneChanged(idx));
+            }
+        }
 
-                let old_default_variant_idx = editor_widgets.editor.layout.default_variant_idx();
+        Message::NewLayout => layout_editor.new_layout(),
 
-                editor_widgets
-                    .editor
-                    .layout
-                    .set_default_variant_idx(selected_variant_idx);
+        Message::NewLayoutFromTemplate => layout_editor.new_layout_from_template(),
 
-                editor_widgets
-                    .update_default_variant_label(old_default_variant_idx, selected_variant_idx);
+        Message::OpenLayout => layout_editor.open_layout_with_dialog(),
 
-                editor_widgets
-                    .variant_actions
-                    .set_as_default_button
-                    .deactivate();
-            }
+        Message::SaveLayout => layout_editor.save_layout(),
 
-            Message::CancelPreview => {
-                editor_widgets.remove_extend_preview();
+        Message::SaveLayoutAs => layout_editor.save_layout_as(),
 
-                editor_widgets.end_behaviour_actions.preview_count = 0;
+        Message::Quit => layout_editor.quit(),
 
-                editor_widgets
-                    .end_behaviour_actions
-                    .cancel_preview_button
-                    .deactivate();
+        Message::Undo => layout_editor.undo(),
 
-                editor_widgets.actions.widgets.activate();
+        Message::Redo => layout_editor.redo(),
 
-                layout_editor.sender.send(Message::SelectedVariantChanged(
-                    editor_widgets.editor.selected_variant_idx,
-                ));
-            }
+        Message::GapsChanged => layout_editor.set_gaps(),
 
-            Message::PreviewExtend => {
-                editor_widgets
-                    .end_behaviour_actions
-                    .cancel_preview_button
-                    .activate();
+        Message::ChangeMonitorSize => layout_editor.change_monitor_size(),
 
-                editor_widgets.actions.widgets.deactivate();
+        Message::PreviewResolutions => layout_editor.preview_resolutions(),
 
-                layout_editor.window.begin();
+        Message::PlayWindowCounts => layout_editor.play_window_counts(),
 
-                editor_widgets.preview_extend(
-                    editor_widgets.editor.selected_variant_idx,
-                    true,
-                    &layout_editor.sender,
-                );
+        Message::ToggleComparison(comparison) => layout_editor.toggle_comparison(comparison),
 
-                layout_editor.window.end();
+        Message::ExportImage => layout_editor.export_image(),
+
+        Message::ExportLayout(format) => layout_editor.export_layout(format),
+
+        Message::ImportLayout(format) => layout_editor.import_layout(format),
+
+        Message::SetSplitDirection(direction) => {
+            let horizontal = matches!(direction, Direction::Horizontal);
+
+            if horizontal
+                == matches!(
+                    editor_widgets.actions.selected_direction,
+                    Direction::Horizontal
+                )
+            {
+                return;
             }
 
-            Message::EndZoneIdxChanged(idx) => {
-                if let Some(_) = &editor_widgets.buffers {
-                    editor_widgets.remove_extend_preview();
+            editor_widgets.actions.toggle_direction_buttons(direction);
 
-                    editor_widgets.editor.layout.get_variants_mut()
-                        [editor_widgets.editor.selected_variant_idx]
-                        .set_end_zone_idx(idx);
+            layout_editor.sender.send(Message::SwapSplitDirection);
+        }
 
-                    layout_editor.window.begin();
+        Message::SelectNextZone => {
+            let zone_count = editor_widgets.editor.selected_zones().len();
 
-                    for _i in 0..editor_widgets.end_behaviour_actions.preview_count {
-                        editor_widgets.preview_extend(
-                            editor_widgets.editor.selected_variant_idx,
-                            false,
-                            &layout_editor.sender,
-                        );
-                    }
+            let last_selected = editor_widgets
+                .editor
+                .selected_zone_idx2()
+                .or(editor_widgets.editor.selected_zone_idx1());
 
-                    layout_editor.window.end();
-                } else {
-                    editor_widgets.editor.layout.get_variants_mut()
-                        [editor_widgets.editor.selected_variant_idx]
-                        .set_end_zone_idx(idx);
-                }
-            }
+            let next = match last_selected {
+                Some(idx) => (idx + 1) % zone_count,
+                None => 0,
+            };
 
-            Message::SwapEndTilingBehaviour => {
-                let variant = &mut editor_widgets.editor.layout.get_variants_mut()
-                    [editor_widgets.editor.selected_variant_idx];
+            layout_editor
+                .sender
+                .send(Message::SelectedZoneChanged(next));
+        }
 
-                match variant.get_end_tiling_behaviour().to_owned() {
-                    EndTilingBehaviour::Directional {
-                        direction,
-                        from_zones,
-                        zone_idx,
-                    } => {
-                        editor_widgets
-                            .end_behaviour_actions
-                            .directional
-                            .widgets
-                            .hide();
-
-                        editor_widgets
-                            .end_behaviour_actions
-                            .repeating
-                            .widgets
-                            .show();
-
-                        editor_widgets
-                            .end_behaviour_actions
-                            .directional
-                            .end_tiling_behaviour = EndTilingBehaviour::Directional {
-                            direction,
-                            from_zones,
-                            zone_idx,
-                        };
-
-                        variant.set_end_tiling_behaviour(
-                            editor_widgets
-                                .end_behaviour_actions
-                                .repeating
-                                .end_tiling_behaviour
-                                .to_owned(),
-                        );
-                    }
-                    EndTilingBehaviour::Repeating { splits, zone_idx } => {
-                        editor_widgets
-                            .end_behaviour_actions
-                            .repeating
-                            .widgets
-                            .hide();
-
-                        editor_widgets
-                            .end_behaviour_actions
-                            .directional
-                            .widgets
-                            .show();
-
-                        editor_widgets
-                            .end_behaviour_actions
-                            .repeating
-                            .end_tiling_behaviour =
-                            EndTilingBehaviour::Repeating { splits, zone_idx };
-
-                        variant.set_end_tiling_behaviour(
-                            editor_widgets
-                                .end_behaviour_actions
-                                .directional
-                                .end_tiling_behaviour
-                                .to_owned(),
-                        );
-                    }
-                }
-            }
+        Message::SelectAdjacentVariantState(direction) => {
+            let idx = editor_widgets.editor.selected_variant_state_idx();
 
-            Message::SwapEndTilingDirection => {
-                let new_direction = editor_widgets.editor.layout.get_variants_mut()
-                    [editor_widgets.editor.selected_variant_idx]
-                    .get_end_tiling_direction()
-                    .unwrap()
-                    .other();
+            let count = editor_widgets
+                .editor
+                .selected_variant()
+                .manual_zones_until();
 
-                if let Some(_) = &editor_widgets.buffers {
-                    editor_widgets.remove_extend_preview();
+            let adjacent = match direction {
+                SwapDirection::Previous if idx > 0 => idx - 1,
+                SwapDirection::Next if idx + 1 < count => idx + 1,
+                _ => return,
+            };
 
-                    editor_widgets.editor.layout.get_variants_mut()
-                        [editor_widgets.editor.selected_variant_idx]
-                        .set_end_tiling_direction(new_direction);
+            layout_editor
+                .sender
+                .send(Message::SelectedVariantStateChanged(adjacent));
+        }
 
-                    layout_editor.window.begin();
+        Message::SelectAdjacentVariant(direction) => {
+            let idx = editor_widgets.editor.selected_variant_idx();
 
-                    for _i in 0..editor_widgets.end_behaviour_actions.preview_count {
-                        editor_widgets.preview_extend(
-                            editor_widgets.editor.selected_variant_idx,
-                            false,
-                            &layout_editor.sender,
-                        );
-                    }
+            let count = editor_widgets.editor.layout().variants_len();
 
-                    layout_editor.window.end();
-                } else {
-                    editor_widgets.editor.layout.get_variants_mut()
-                        [editor_widgets.editor.selected_variant_idx]
-                        .set_end_tiling_direction(new_direction);
-                }
-            }
+            let adjacent = match direction {
+                SwapDirection::Previous if idx > 0 => idx - 1,
+                SwapDirection::Next if idx + 1 < count => idx + 1,
+                _ => return,
+            };
+
+            layout_editor
+                .sender
+                .send(Message::SelectedVariantChanged(adjacent));
         }
+
+        Message::ToggleHelp => layout_editor.toggle_help(),
+
+        Message::ShowCommandPalette => layout_editor.show_command_palette(),
     }
 }
@@ -0,0 +1,64 @@
+use crate::gaps::GapSettings;
+use himewm_layout::Layout;
+use std::collections::VecDeque;
+
+pub const HISTORY_LIMIT: usize = 100;
+
+#[derive(Clone)]
+pub struct Snapshot {
+    pub layout: Layout,
+    pub gaps: GapSettings,
+    pub selected_variant_idx: usize,
+    pub selected_variant_state_idx: usize,
+    pub revision: usize,
+}
+
+pub struct History {
+    undo_stack: VecDeque<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    limit: usize,
+}
+
+impl History {
+    pub fn new(limit: usize) -> Self {
+        return History {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            limit,
+        };
+    }
+
+    pub fn record(&mut self, snapshot: Snapshot) {
+        self.redo_stack.clear();
+
+        self.undo_stack.push_back(snapshot);
+
+        if self.undo_stack.len() > self.limit {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.undo_stack.pop_back()?;
+
+        self.redo_stack.push(current);
+
+        return Some(snapshot);
+    }
+
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.redo_stack.pop()?;
+
+        self.undo_stack.push_back(current);
+
+        return Some(snapshot);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        return !self.undo_stack.is_empty();
+    }
+
+    pub fn can_redo(&self) -> bool {
+        return !self.redo_stack.is_empty();
+    }
+}
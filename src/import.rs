@@ -0,0 +1,258 @@
+pub mod fancyzones;
+pub mod i3;
+pub mod tmux;
+
+use crate::tiling_tree::{self, Container, Rect};
+use himewm_layout::*;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ImportError {
+    Json(serde_json::Error),
+    Syntax(String),
+    NoWindows,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Json(err) => write!(f, "invalid JSON: {err}"),
+            ImportError::Syntax(err) => write!(f, "{err}"),
+            ImportError::NoWindows => write!(f, "the layout does not contain any windows"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(err: serde_json::Error) -> Self {
+        return ImportError::Json(err);
+    }
+}
+
+/// Something in an imported layout that has no equivalent in a himewm
+/// layout, along with what was done instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Unrepresented {
+    /// A tabbed or stacked container, which became a single zone.
+    StackedContainer {
+        windows: usize,
+    },
+    FloatingWindows(usize),
+    OtherWorkspaces(usize),
+    /// Spacing between zones, which himewm leaves to the window manager.
+    Spacing(i32),
+    /// Zones that could not be reached by splitting, which were dropped.
+    UnsplittableZones(usize),
+    /// Parts of the monitor no zone covered, which were added to
+    /// neighbouring zones.
+    UncoveredArea,
+    /// A split that would have left a zone without any area.
+    SplitTooSmall,
+}
+
+impl fmt::Display for Unrepresented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unrepresented::StackedContainer { windows } => write!(
+                f,
+                "a tabbed or stacked container of {windows} windows became a single zone"
+            ),
+            Unrepresented::FloatingWindows(count) => {
+                write!(f, "{count} floating windows were ignored")
+            }
+            Unrepresented::OtherWorkspaces(count) => {
+                write!(
+                    f,
+                    "only the first workspace was imported, {count} were ignored"
+                )
+            }
+            Unrepresented::Spacing(spacing) => {
+                write!(f, "the {spacing} pixel spacing between zones was ignored")
+            }
+            Unrepresented::UnsplittableZones(count) => write!(
+                f,
+                "{count} zones could not be made by splitting and were dropped"
+            ),
+            Unrepresented::UncoveredArea => {
+                write!(
+                    f,
+                    "areas not covered by any zone were added to neighbouring zones"
+                )
+            }
+            Unrepresented::SplitTooSmall => {
+                write!(f, "a split too small to make a zone was dropped")
+            }
+        }
+    }
+}
+
+pub struct Import {
+    pub layout: Layout,
+    pub unrepresented: Vec<Unrepresented>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Format {
+    I3,
+    Tmux,
+    FancyZones,
+}
+
+impl Format {
+    /// Imports `source`, using `monitor_w` by `monitor_h` as the monitor size
+    /// unless `source` gives one.
+    pub fn parse(
+        self,
+        source: &str,
+        monitor_w: i32,
+        monitor_h: i32,
+    ) -> Result<Import, ImportError> {
+        return match self {
+            Format::I3 => i3::parse(source, monitor_w, monitor_h),
+            Format::Tmux => tmux::parse(source, monitor_w, monitor_h),
+            Format::FancyZones => fancyzones::parse(source, monitor_w, monitor_h),
+        };
+    }
+}
+
+/// Builds a tiling tree out of free-standing rectangles, reporting any that
+/// had to be dropped and any area they leave uncovered.
+fn container_from_rects(
+    region: Rect,
+    rects: &[Rect],
+    unrepresented: &mut Vec<Unrepresented>,
+) -> Container {
+    let covered: f64 = rects.iter().map(Rect::area).sum();
+
+    if covered < region.area() - 1.0 {
+        unrepresented.push(Unrepresented::UncoveredArea);
+    }
+
+    let mut dropped = 0;
+
+    let container = tiling_tree::cut_rects(region, rects.to_vec(), &mut dropped);
+
+    if dropped > 0 {
+        unrepresented.push(Unrepresented::UnsplittableZones(dropped));
+    }
+
+    return container;
+}
+
+/// Adds one variant state per split to `variant`, each one window more than
+/// the last, ending with `container` tiled across the monitor. The variant is
+/// expected to start with a single variant state covering the monitor.
+fn build_variant(
+    variant: &mut Variant,
+    container: &Container,
+    unrepresented: &mut Vec<Unrepresented>,
+) {
+    split_container(variant, container, 0, unrepresented);
+}
+
+fn split_container(
+    variant: &mut Variant,
+    container: &Container,
+    zone_idx: usize,
+    unrepresented: &mut Vec<Unrepresented>,
+) {
+    let (direction, children) = match container {
+        Container::Window => return,
+        Container::Split {
+            direction,
+            children,
+        } => (direction, children),
+    };
+
+    let total: f64 = children.iter().map(|(share, _)| share).sum();
+
+    let zone = &variant.get_zones()[variant.get_zones().len() - 1][zone_idx];
+
+    let (start, end) = match direction {
+        Direction::Horizontal => (zone.left, zone.right),
+        Direction::Vertical => (zone.top, zone.bottom),
+    };
+
+    let mut child_zone_idxs = vec![zone_idx];
+
+    let mut split_children = vec![&children[0].1];
+
+    let mut share_so_far = children[0].0;
+
+    for (share, child) in &children[1..] {
+        let position = start + ((end - start) as f64 * share_so_far / total).round() as i32;
+
+        share_so_far += share;
+
+        let previous = variant.get_zones().len() - 1;
+
+        let current_zone = &variant.get_zones()[previous][*child_zone_idxs.last().unwrap()];
+
+        let (current_start, current_end) = match direction {
+            Direction::Horizontal => (current_zone.left, current_zone.right),
+            Direction::Vertical => (current_zone.top, current_zone.bottom),
+        };
+
+        if position <= current_start || position >= current_end {
+            unrepresented.push(Unrepresented::SplitTooSmall);
+
+            continue;
+        }
+
+        variant.clone_zone_vec(previous);
+
+        let split_direction = match direction {
+            Direction::Horizontal => SplitDirection::Horizontal(position),
+            Direction::Vertical => SplitDirection::Vertical(position),
+        };
+
+        variant.split(
+            previous + 1,
+            *child_zone_idxs.last().unwrap(),
+            split_direction,
+        );
+
+        child_zone_idxs.push(variant.get_zones()[previous + 1].len() - 1);
+
+        split_children.push(child);
+    }
+
+    for (child, idx) in split_children.into_iter().zip(child_zone_idxs) {
+        split_container(variant, child, idx, unrepresented);
+    }
+}
+
+/// Makes a layout with one variant per container.
+fn layout_from_containers(
+    containers: &[Container],
+    monitor_w: i32,
+    monitor_h: i32,
+    mut unrepresented: Vec<Unrepresented>,
+) -> Result<Import, ImportError> {
+    if containers.is_empty() {
+        return Err(ImportError::NoWindows);
+    }
+
+    let mut layout = Layout::new(monitor_w, monitor_h);
+
+    for (i, container) in containers.iter().enumerate() {
+        if i > 0 {
+            layout
+                .get_variants_mut()
+                .push(Variant::new(monitor_w, monitor_h));
+        }
+
+        build_variant(
+            &mut layout.get_variants_mut()[i],
+            container,
+            &mut unrepresented,
+        );
+    }
+
+    return Ok(Import {
+        layout,
+        unrepresented,
+    });
+}
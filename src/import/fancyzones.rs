@@ -0,0 +1,210 @@
+use super::{container_from_rects, layout_from_containers, Import, ImportError, Unrepresented};
+use crate::tiling_tree::{Container, Rect};
+use serde_json::Value;
+
+/// Grid rows and columns are given in hundredths of a percent.
+const GRID_TOTAL: f64 = 10000.0;
+
+/// Imports the custom layouts of a PowerToys FancyZones
+/// `custom-layouts.json`, one variant per layout. A single layout object or
+/// an array of them is accepted as well. The monitor size is taken from the
+/// first canvas layout, or is `monitor_w` by `monitor_h` if there is none.
+pub fn parse(json: &str, monitor_w: i32, monitor_h: i32) -> Result<Import, ImportError> {
+    let value: Value = serde_json::from_str(json)?;
+
+    let layouts = match &value {
+        Value::Array(layouts) => layouts.iter().collect(),
+        Value::Object(object) => match object.get("custom-layouts") {
+            Some(Value::Array(layouts)) => layouts.iter().collect(),
+            _ => vec![&value],
+        },
+        _ => Vec::new(),
+    };
+
+    let mut unrepresented = Vec::new();
+
+    let mut containers = Vec::new();
+
+    let mut monitor_size = None;
+
+    for layout in layouts {
+        let info = &layout["info"];
+
+        let container = match layout["type"].as_str() {
+            Some("canvas") => {
+                let ref_w = number(info, "ref-width")?;
+
+                let ref_h = number(info, "ref-height")?;
+
+                if let None = monitor_size {
+                    monitor_size = Some((ref_w as i32, ref_h as i32));
+                }
+
+                canvas(info, ref_w, ref_h, &mut unrepresented)?
+            }
+            Some("grid") => grid(info, &mut unrepresented)?,
+            Some(other) => {
+                return Err(ImportError::Syntax(format!(
+                    "unknown FancyZones layout type '{other}'"
+                )))
+            }
+            None => {
+                return Err(ImportError::Syntax(
+                    "a FancyZones layout is missing its type".to_string(),
+                ))
+            }
+        };
+
+        containers.push(container);
+    }
+
+    let (monitor_w, monitor_h) = monitor_size.unwrap_or((monitor_w, monitor_h));
+
+    return layout_from_containers(&containers, monitor_w, monitor_h, unrepresented);
+}
+
+fn number(value: &Value, key: &str) -> Result<f64, ImportError> {
+    return value[key]
+        .as_f64()
+        .ok_or_else(|| ImportError::Syntax(format!("a FancyZones layout is missing '{key}'")));
+}
+
+fn numbers(value: &Value, key: &str) -> Result<Vec<f64>, ImportError> {
+    let missing = || ImportError::Syntax(format!("a FancyZones layout is missing '{key}'"));
+
+    return value[key]
+        .as_array()
+        .ok_or_else(missing)?
+        .iter()
+        .map(|number| number.as_f64().ok_or_else(missing))
+        .collect();
+}
+
+fn canvas(
+    info: &Value,
+    ref_w: f64,
+    ref_h: f64,
+    unrepresented: &mut Vec<Unrepresented>,
+) -> Result<Container, ImportError> {
+    let zones = info["zones"].as_array().ok_or(ImportError::NoWindows)?;
+
+    let rects = zones
+        .iter()
+        .map(|zone| {
+            let left = number(zone, "X")?;
+
+            let top = number(zone, "Y")?;
+
+            return Ok(Rect {
+                left,
+                top,
+                right: left + number(zone, "width")?,
+                bottom: top + number(zone, "height")?,
+            });
+        })
+        .collect::<Result<Vec<Rect>, ImportError>>()?;
+
+    if rects.is_empty() {
+        return Err(ImportError::NoWindows);
+    }
+
+    let region = Rect {
+        left: 0.0,
+        top: 0.0,
+        right: ref_w,
+        bottom: ref_h,
+    };
+
+    return Ok(container_from_rects(region, &rects, unrepresented));
+}
+
+/// Grid zones are made of the cells that share a zone number, which must
+/// together form a rectangle.
+fn grid(info: &Value, unrepresented: &mut Vec<Unrepresented>) -> Result<Container, ImportError> {
+    let row_bounds = bounds(&numbers(info, "rows-percentage")?);
+
+    let column_bounds = bounds(&numbers(info, "columns-percentage")?);
+
+    let cell_child_map = info["cell-child-map"].as_array().ok_or_else(|| {
+        ImportError::Syntax("a FancyZones layout is missing 'cell-child-map'".to_string())
+    })?;
+
+    let mut rects: Vec<Option<Rect>> = Vec::new();
+
+    for (row, cells) in cell_child_map.iter().enumerate() {
+        for (column, zone) in cells.as_array().into_iter().flatten().enumerate() {
+            let zone = match zone.as_u64() {
+                Some(val) => val as usize,
+                None => continue,
+            };
+
+            let (top, bottom) = match (row_bounds.get(row), row_bounds.get(row + 1)) {
+                (Some(top), Some(bottom)) => (*top, *bottom),
+                _ => continue,
+            };
+
+            let (left, right) = match (column_bounds.get(column), column_bounds.get(column + 1)) {
+                (Some(left), Some(right)) => (*left, *right),
+                _ => continue,
+            };
+
+            if zone >= rects.len() {
+                rects.resize(zone + 1, None);
+            }
+
+            rects[zone] = Some(match rects[zone] {
+                Some(rect) => Rect {
+                    left: f64::min(rect.left, left),
+                    top: f64::min(rect.top, top),
+                    right: f64::max(rect.right, right),
+                    bottom: f64::max(rect.bottom, bottom),
+                },
+                None => Rect {
+                    left,
+                    top,
+                    right,
+                    bottom,
+                },
+            });
+        }
+    }
+
+    let rects: Vec<Rect> = rects.into_iter().flatten().collect();
+
+    if rects.is_empty() {
+        return Err(ImportError::NoWindows);
+    }
+
+    let spacing = info["spacing"].as_i64().unwrap_or(0);
+
+    if info["show-spacing"].as_bool().unwrap_or(false) && spacing > 0 {
+        unrepresented.push(Unrepresented::Spacing(spacing as i32));
+    }
+
+    let region = Rect {
+        left: 0.0,
+        top: 0.0,
+        right: GRID_TOTAL,
+        bottom: GRID_TOTAL,
+    };
+
+    return Ok(container_from_rects(region, &rects, unrepresented));
+}
+
+/// Turns sizes into the positions between them, scaled so that they end at
+/// `GRID_TOTAL` even if the sizes do not quite add up to it.
+fn bounds(sizes: &[f64]) -> Vec<f64> {
+    let total: f64 = sizes.iter().sum();
+
+    let mut bounds = vec![0.0];
+
+    let mut position = 0.0;
+
+    for size in sizes {
+        position += size;
+
+        bounds.push(position * GRID_TOTAL / total);
+    }
+
+    return bounds;
+}
@@ -0,0 +1,244 @@
+use super::{layout_from_containers, Import, ImportError, Unrepresented};
+use crate::tiling_tree::Container;
+use himewm_layout::Direction;
+use serde_json::Value;
+
+/// Imports a layout saved by `i3-save-tree`, or a tree printed by
+/// `swaymsg -t get_tree` or `i3-msg -t get_tree`. Saved layouts may hold
+/// several top-level containers, which are placed side by side the way a
+/// workspace would place them. Trees keep the first non-empty workspace
+/// only, and take the monitor size from it.
+pub fn parse(json: &str, monitor_w: i32, monitor_h: i32) -> Result<Import, ImportError> {
+    let json = strip_comments(json);
+
+    let nodes = serde_json::Deserializer::from_str(&json)
+        .into_iter::<Value>()
+        .collect::<Result<Vec<Value>, serde_json::Error>>()?;
+
+    let mut unrepresented = Vec::new();
+
+    let mut monitor_w = monitor_w;
+
+    let mut monitor_h = monitor_h;
+
+    let is_tree = nodes.len() == 1 && matches!(node_type(&nodes[0]), "root" | "output");
+
+    let container = if is_tree {
+        let mut workspaces = Vec::new();
+
+        find_workspaces(&nodes[0], &mut workspaces);
+
+        let workspace = match workspaces.first() {
+            Some(val) => *val,
+            None => return Err(ImportError::NoWindows),
+        };
+
+        if workspaces.len() > 1 {
+            unrepresented.push(Unrepresented::OtherWorkspaces(workspaces.len() - 1));
+        }
+
+        let rect = &workspace["rect"];
+
+        if let (Some(w), Some(h)) = (rect["width"].as_i64(), rect["height"].as_i64()) {
+            if w > 0 && h > 0 {
+                monitor_w = w as i32;
+
+                monitor_h = h as i32;
+            }
+        }
+
+        container_from_node(workspace, &mut unrepresented)
+    } else {
+        let (floating, tiled): (Vec<&Value>, Vec<&Value>) = nodes
+            .iter()
+            .partition(|node| node_type(node) == "floating_con");
+
+        if !floating.is_empty() {
+            unrepresented.push(Unrepresented::FloatingWindows(
+                floating.into_iter().map(window_count).sum(),
+            ));
+        }
+
+        container_from_children(Direction::Horizontal, &tiled, &mut unrepresented)
+    };
+
+    let containers: Vec<Container> = container.into_iter().collect();
+
+    return layout_from_containers(&containers, monitor_w, monitor_h, unrepresented);
+}
+
+fn node_type(node: &Value) -> &str {
+    return node["type"].as_str().unwrap_or("con");
+}
+
+fn child_nodes<'a>(node: &'a Value, key: &str) -> Vec<&'a Value> {
+    return match node[key].as_array() {
+        Some(nodes) => nodes.iter().collect(),
+        None => Vec::new(),
+    };
+}
+
+fn find_workspaces<'a>(node: &'a Value, workspaces: &mut Vec<&'a Value>) {
+    if node_type(node) == "workspace" {
+        let scratchpad = node["name"]
+            .as_str()
+            .is_some_and(|name| name.starts_with("__i3"));
+
+        if !scratchpad && !child_nodes(node, "nodes").is_empty() {
+            workspaces.push(node);
+        }
+
+        return;
+    }
+
+    for child in child_nodes(node, "nodes") {
+        find_workspaces(child, workspaces);
+    }
+}
+
+fn container_from_node(node: &Value, unrepresented: &mut Vec<Unrepresented>) -> Option<Container> {
+    let floating: usize = child_nodes(node, "floating_nodes")
+        .into_iter()
+        .map(window_count)
+        .sum();
+
+    if floating > 0 {
+        unrepresented.push(Unrepresented::FloatingWindows(floating));
+    }
+
+    let children = child_nodes(node, "nodes");
+
+    if children.is_empty() {
+        return match node_type(node) {
+            "root" | "output" | "workspace" => None,
+            _ => Some(Container::Window),
+        };
+    }
+
+    let direction = match node["layout"].as_str() {
+        Some("splitv") => Direction::Vertical,
+        Some("tabbed") | Some("stacked") => {
+            unrepresented.push(Unrepresented::StackedContainer {
+                windows: window_count(node),
+            });
+
+            return Some(Container::Window);
+        }
+        _ => Direction::Horizontal,
+    };
+
+    return container_from_children(direction, &children, unrepresented);
+}
+
+/// Containers with a single child are replaced by the child, since they do
+/// not split anything.
+fn container_from_children(
+    direction: Direction,
+    nodes: &[&Value],
+    unrepresented: &mut Vec<Unrepresented>,
+) -> Option<Container> {
+    let equal_share = 1.0 / nodes.len() as f64;
+
+    let mut children: Vec<(f64, Container)> = nodes
+        .iter()
+        .filter_map(|node| {
+            let share = node["percent"].as_f64().unwrap_or(equal_share);
+
+            container_from_node(node, unrepresented).map(|container| (share, container))
+        })
+        .collect();
+
+    return match children.len() {
+        0 => None,
+        1 => Some(children.pop().unwrap().1),
+        _ => Some(Container::Split {
+            direction,
+            children,
+        }),
+    };
+}
+
+fn window_count(node: &Value) -> usize {
+    let children = child_nodes(node, "nodes");
+
+    if children.is_empty() {
+        return 1;
+    }
+
+    return children.into_iter().map(window_count).sum();
+}
+
+/// Removes the `//` comments `i3-save-tree` writes, along with commas left
+/// dangling by commenting out the last entry of an object.
+fn strip_comments(json: &str) -> String {
+    let mut stripped = String::with_capacity(json.len());
+
+    let mut chars = json.chars().peekable();
+
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        stripped.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+
+                stripped.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+
+                    chars.next();
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    let mut without_dangling_commas = String::with_capacity(stripped.len());
+
+    let mut in_string = false;
+
+    let mut escaped = false;
+
+    for (i, c) in stripped.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = stripped[i + 1..].trim_start().chars().next();
+
+            if let Some('}') | Some(']') = next {
+                continue;
+            }
+        }
+
+        without_dangling_commas.push(c);
+    }
+
+    return without_dangling_commas;
+}
@@ -0,0 +1,207 @@
+use super::{layout_from_containers, Import, ImportError};
+use crate::tiling_tree::Container;
+use himewm_layout::Direction;
+
+/// A pane or a group of panes, measured in character cells.
+struct Cell {
+    w: i32,
+    h: i32,
+    x: i32,
+    y: i32,
+    children: Option<(Direction, Vec<Cell>)>,
+}
+
+/// Imports a tmux layout string, as printed by
+/// `tmux display-message -p '#{window_layout}'` and accepted by
+/// `select-layout`. The checksum at the start is optional. Since tmux
+/// measures panes in characters, only their proportions carry over to the
+/// monitor.
+pub fn parse(layout: &str, monitor_w: i32, monitor_h: i32) -> Result<Import, ImportError> {
+    let layout = layout.trim();
+
+    let body = match layout.split_once(',') {
+        Some((checksum, body))
+            if checksum.len() == 4 && checksum.chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            body
+        }
+        _ => layout,
+    };
+
+    let mut parser = Parser {
+        input: body.as_bytes(),
+        pos: 0,
+    };
+
+    let cell = parser.cell()?;
+
+    if parser.pos != parser.input.len() {
+        return Err(parser.unexpected());
+    }
+
+    return layout_from_containers(
+        &[container_from_cell(&cell)],
+        monitor_w,
+        monitor_h,
+        Vec::new(),
+    );
+}
+
+/// Each pane's share includes the separator after it, so that the shares of
+/// a group add up to the whole group.
+fn container_from_cell(cell: &Cell) -> Container {
+    let (direction, children) = match &cell.children {
+        Some(val) => val,
+        None => return Container::Window,
+    };
+
+    let (start, size) = match direction {
+        Direction::Horizontal => (cell.x, cell.w),
+        Direction::Vertical => (cell.y, cell.h),
+    };
+
+    let starts: Vec<i32> = children
+        .iter()
+        .map(|child| match direction {
+            Direction::Horizontal => child.x,
+            Direction::Vertical => child.y,
+        })
+        .collect();
+
+    let children = children
+        .iter()
+        .enumerate()
+        .map(|(i, child)| {
+            let end = match starts.get(i + 1) {
+                Some(next) => *next,
+                None => start + size,
+            };
+
+            (
+                (end - starts[i]) as f64 / size as f64,
+                container_from_cell(child),
+            )
+        })
+        .collect();
+
+    return Container::Split {
+        direction: direction.to_owned(),
+        children,
+    };
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn unexpected(&self) -> ImportError {
+        return match self.input.get(self.pos) {
+            Some(c) => ImportError::Syntax(format!(
+                "unexpected '{}' at position {} of the tmux layout",
+                *c as char,
+                self.pos + 1
+            )),
+            None => ImportError::Syntax("the tmux layout ends too early".to_string()),
+        };
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ImportError> {
+        if self.input.get(self.pos) != Some(&c) {
+            return Err(self.unexpected());
+        }
+
+        self.pos += 1;
+
+        return Ok(());
+    }
+
+    fn number(&mut self) -> Result<i32, ImportError> {
+        let start = self.pos;
+
+        while let Some(b'0'..=b'9') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+
+        return std::str::from_utf8(&self.input[start..self.pos])
+            .unwrap()
+            .parse()
+            .map_err(|_| self.unexpected());
+    }
+
+    /// `<w>x<h>,<x>,<y>` followed by `,<pane id>`, `{<cells>}` for panes
+    /// side by side, or `[<cells>]` for panes stacked on top of each other.
+    fn cell(&mut self) -> Result<Cell, ImportError> {
+        let w = self.number()?;
+
+        self.expect(b'x')?;
+
+        let h = self.number()?;
+
+        self.expect(b',')?;
+
+        let x = self.number()?;
+
+        self.expect(b',')?;
+
+        let y = self.number()?;
+
+        let children = match self.input.get(self.pos) {
+            Some(b'{') => Some((Direction::Horizontal, self.cells(b'}')?)),
+            Some(b'[') => Some((Direction::Vertical, self.cells(b']')?)),
+            Some(b',') if self.input.get(self.pos + 1).is_some_and(u8::is_ascii_digit) => {
+                // A pane id, unless the comma separates this cell from the
+                // next one
+                let next = self.pos;
+
+                self.pos += 1;
+
+                self.number()?;
+
+                if let Some(b'x') = self.input.get(self.pos) {
+                    self.pos = next;
+                }
+
+                None
+            }
+            _ => None,
+        };
+
+        if w <= 0 || h <= 0 {
+            return Err(ImportError::Syntax(format!(
+                "a pane of the tmux layout is {w}x{h}"
+            )));
+        }
+
+        return Ok(Cell {
+            w,
+            h,
+            x,
+            y,
+            children,
+        });
+    }
+
+    fn cells(&mut self, close: u8) -> Result<Vec<Cell>, ImportError> {
+        self.pos += 1;
+
+        let mut cells = vec![self.cell()?];
+
+        loop {
+            match self.input.get(self.pos) {
+                Some(b',') => {
+                    self.pos += 1;
+
+                    cells.push(self.cell()?);
+                }
+                Some(c) if *c == close => {
+                    self.pos += 1;
+
+                    return Ok(cells);
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use fltk::enums::Key;
+use serde_json::Value;
+use std::{fmt, io, path::PathBuf};
+
+/// The first function key, `Key::from_i32(FUNCTION_KEY + n)` being Fn.
+const FUNCTION_KEY: i32 = 0xffbd;
+
+fn named_keys() -> [(&'static str, Key); 15] {
+    return [
+        ("Tab", Key::Tab),
+        ("Enter", Key::Enter),
+        ("Escape", Key::Escape),
+        ("Space", Key::from_char(' ')),
+        ("Backspace", Key::BackSpace),
+        ("Delete", Key::Delete),
+        ("Insert", Key::Insert),
+        ("Home", Key::Home),
+        ("End", Key::End),
+        ("PageUp", Key::PageUp),
+        ("PageDown", Key::PageDown),
+        ("Left", Key::Left),
+        ("Right", Key::Right),
+        ("Up", Key::Up),
+        ("Down", Key::Down),
+    ];
+}
+
+#[derive(Debug)]
+pub enum KeybindingsError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    NotAnObject,
+    UnknownCommand(String),
+    InvalidKey { command: String, key: String },
+}
+
+impl fmt::Display for KeybindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeybindingsError::Io(err) => write!(f, "{err}"),
+            KeybindingsError::Json(err) => write!(f, "invalid JSON: {err}"),
+            KeybindingsError::NotAnObject => {
+                write!(f, "key bindings must be an object of commands")
+            }
+            KeybindingsError::UnknownCommand(command) => {
+                write!(f, "unknown command '{command}'")
+            }
+            KeybindingsError::InvalidKey { command, key } => {
+                write!(f, "'{key}' bound to '{command}' is not a key")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeybindingsError {}
+
+impl From<io::Error> for KeybindingsError {
+    fn from(err: io::Error) -> Self {
+        return KeybindingsError::Io(err);
+    }
+}
+
+impl From<serde_json::Error> for KeybindingsError {
+    fn from(err: serde_json::Error) -> Self {
+        return KeybindingsError::Json(err);
+    }
+}
+
+/// Something the editor can do from the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Selects a zone of the shown variant state, counting from 0.
+    SelectZone(usize),
+    SelectNextZone,
+    SplitHorizontally,
+    SplitVertically,
+    Split,
+    Merge,
+    Swap,
+    PreviousVariantState,
+    NextVariantState,
+    PreviousVariant,
+    NextVariant,
+    ShowHelp,
+}
+
+impl Command {
+    pub const ALL: [Command; 20] = [
+        Command::SelectZone(0),
+        Command::SelectZone(1),
+        Command::SelectZone(2),
+        Command::SelectZone(3),
+        Command::SelectZone(4),
+        Command::SelectZone(5),
+        Command::SelectZone(6),
+        Command::SelectZone(7),
+        Command::SelectZone(8),
+        Command::SelectNextZone,
+        Command::SplitHorizontally,
+        Command::SplitVertically,
+        Command::Split,
+        Command::Merge,
+        Command::Swap,
+        Command::PreviousVariantState,
+        Command::NextVariantState,
+        Command::PreviousVariant,
+        Command::NextVariant,
+        Command::ShowHelp,
+    ];
+
+    /// The name of the command in the key bindings file.
+    pub fn name(self) -> String {
+        return match self {
+            Command::SelectZone(idx) => format!("select-zone-{}", idx + 1),
+            Command::SelectNextZone => "select-next-zone".to_string(),
+            Command::SplitHorizontally => "split-horizontally".to_string(),
+            Command::SplitVertically => "split-vertically".to_string(),
+            Command::Split => "split".to_string(),
+            Command::Merge => "merge".to_string(),
+            Command::Swap => "swap".to_string(),
+            Command::PreviousVariantState => "previous-variant-state".to_string(),
+            Command::NextVariantState => "next-variant-state".to_string(),
+            Command::PreviousVariant => "previous-variant".to_string(),
+            Command::NextVariant => "next-variant".to_string(),
+            Command::ShowHelp => "show-help".to_string(),
+        };
+    }
+
+    pub fn description(self) -> String {
+        return match self {
+            Command::SelectZone(idx) => format!("Select zone {}", idx + 1),
+            Command::SelectNextZone => "Select the next zone".to_string(),
+            Command::SplitHorizontally => "Split side by side".to_string(),
+            Command::SplitVertically => "Split on top of each other".to_string(),
+            Command::Split => "Split the selected zone".to_string(),
+            Command::Merge => "Merge the selected zones".to_string(),
+            Command::Swap => "Swap the selected zones".to_string(),
+            Command::PreviousVariantState => "Previous variant state".to_string(),
+            Command::NextVariantState => "Next variant state".to_string(),
+            Command::PreviousVariant => "Previous variant".to_string(),
+            Command::NextVariant => "Next variant".to_string(),
+            Command::ShowHelp => "Show or hide this help".to_string(),
+        };
+    }
+
+    /// Whether holding Shift adds the zone the command selects as the second
+    /// selected zone, instead of needing a binding of its own.
+    pub fn adds_with_shift(self) -> bool {
+        return matches!(self, Command::SelectZone(_) | Command::SelectNextZone);
+    }
+
+    fn default_keys(self) -> Vec<KeyCombo> {
+        let keys = match self {
+            Command::SelectZone(idx) => {
+                vec![Key::from_char(
+                    char::from_digit(idx as u32 + 1, 10).unwrap(),
+                )]
+            }
+            Command::SelectNextZone => vec![Key::Tab],
+            Command::SplitHorizontally => vec![Key::from_char('h')],
+            Command::SplitVertically => vec![Key::from_char('v')],
+            Command::Split => vec![Key::from_char('s')],
+            Command::Merge => vec![Key::from_char('m')],
+            Command::Swap => vec![Key::from_char('x')],
+            Command::PreviousVariantState => vec![Key::Left],
+            Command::NextVariantState => vec![Key::Right],
+            Command::PreviousVariant => vec![Key::Up, Key::PageUp],
+            Command::NextVariant => vec![Key::Down, Key::PageDown],
+            Command::ShowHelp => vec![Key::from_i32(FUNCTION_KEY + 1)],
+        };
+
+        return keys.into_iter().map(KeyCombo::new).collect();
+    }
+}
+
+/// A key along with the modifiers held down with it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyCombo {
+    pub key: Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyCombo {
+    pub fn new(key: Key) -> Self {
+        return KeyCombo {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        };
+    }
+
+    /// Parses combinations such as `S`, `Ctrl+Shift+Tab` or `F1`. Letters
+    /// are case-insensitive, Shift has to be given explicitly.
+    pub fn parse(combo: &str) -> Option<Self> {
+        let combo = combo.trim();
+
+        // "Ctrl++" binds the plus key
+        let (modifiers, key_name) = match combo.strip_suffix('+') {
+            Some(rest) if rest.is_empty() || rest.ends_with('+') => {
+                (rest.strip_suffix('+').unwrap_or(rest), "+")
+            }
+            _ => combo.rsplit_once('+').unwrap_or(("", combo)),
+        };
+
+        let mut key_combo = KeyCombo::new(parse_key(key_name.trim())?);
+
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => key_combo.ctrl = true,
+                "shift" => key_combo.shift = true,
+                "alt" => key_combo.alt = true,
+                _ => return None,
+            }
+        }
+
+        return Some(key_combo);
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+
+        if let Some((name, _)) = named_keys().into_iter().find(|(_, key)| *key == self.key) {
+            return write!(f, "{name}");
+        }
+
+        let code = self.key.bits();
+
+        if (FUNCTION_KEY + 1..=FUNCTION_KEY + 12).contains(&code) {
+            return write!(f, "F{}", code - FUNCTION_KEY);
+        }
+
+        return match char::from_u32(code as u32) {
+            Some(c) if c.is_ascii_graphic() => write!(f, "{}", c.to_ascii_uppercase()),
+            _ => write!(f, "#{code:x}"),
+        };
+    }
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+    if let Some((_, key)) = named_keys()
+        .into_iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+    {
+        return Some(key);
+    }
+
+    if let Some(number) = name
+        .strip_prefix('F')
+        .or_else(|| name.strip_prefix('f'))
+        .and_then(|number| number.parse::<i32>().ok())
+    {
+        if (1..=12).contains(&number) {
+            return Some(Key::from_i32(FUNCTION_KEY + number));
+        }
+    }
+
+    let mut chars = name.chars();
+
+    return match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_graphic() => Some(Key::from_char(c.to_ascii_lowercase())),
+        _ => None,
+    };
+}
+
+/// Which keys run which commands. Every command has its default keys unless
+/// the key bindings file says otherwise.
+#[derive(Clone, Debug)]
+pub struct Keybindings {
+    bindings: Vec<(Command, Vec<KeyCombo>)>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        return Keybindings {
+            bindings: Command::ALL
+                .into_iter()
+                .map(|command| (command, command.default_keys()))
+                .collect(),
+        };
+    }
+}
+
+impl Keybindings {
+    /// `keybindings.json` in the editor's configuration directory.
+    pub fn config_path() -> Option<PathBuf> {
+        let project_dirs = directories::ProjectDirs::from("", "", "himewm_layout_editor")?;
+
+        return Some(project_dirs.config_dir().join("keybindings.json"));
+    }
+
+    /// Reads the key bindings file, falling back to the defaults if there is
+    /// none.
+    pub fn load() -> Result<Self, KeybindingsError> {
+        let path = match Self::config_path() {
+            Some(val) => val,
+            None => return Ok(Keybindings::default()),
+        };
+
+        return match std::fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Keybindings::default()),
+            Err(err) => Err(err.into()),
+        };
+    }
+
+    /// Parses an object of command names to keys, for example
+    /// `{"split": ["S", "Ctrl+Enter"], "swap": "W"}`. Commands that are left
+    /// out keep their default keys, and `[]` unbinds a command.
+    pub fn from_json(json: &str) -> Result<Self, KeybindingsError> {
+        let value: Value = serde_json::from_str(json)?;
+
+        let object = match value.as_object() {
+            Some(val) => val,
+            None => return Err(KeybindingsError::NotAnObject),
+        };
+
+        let mut keybindings = Keybindings::default();
+
+        for (name, keys) in object {
+            let binding = match keybindings
+                .bindings
+                .iter_mut()
+                .find(|(command, _)| command.name() == *name)
+            {
+                Some(val) => val,
+                None => return Err(KeybindingsError::UnknownCommand(name.to_owned())),
+            };
+
+            let keys: Vec<&Value> = match keys {
+                Value::Array(keys) => keys.iter().collect(),
+                key => vec![key],
+            };
+
+            binding.1 = keys
+                .into_iter()
+                .map(|key| {
+                    key.as_str().and_then(KeyCombo::parse).ok_or_else(|| {
+                        KeybindingsError::InvalidKey {
+                            command: name.to_owned(),
+                            key: key.to_string(),
+                        }
+                    })
+                })
+                .collect::<Result<Vec<KeyCombo>, KeybindingsError>>()?;
+        }
+
+        return Ok(keybindings);
+    }
+
+    pub fn bindings(&self) -> &[(Command, Vec<KeyCombo>)] {
+        return &self.bindings;
+    }
+
+    pub fn command_for(&self, pressed: KeyCombo) -> Option<Command> {
+        let exact = self
+            .bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&pressed))
+            .map(|(command, _)| *command);
+
+        if exact.is_some() || !pressed.shift {
+            return exact;
+        }
+
+        let without_shift = KeyCombo {
+            shift: false,
+            ..pressed
+        };
+
+        return self
+            .bindings
+            .iter()
+            .find(|(command, keys)| command.adds_with_shift() && keys.contains(&without_shift))
+            .map(|(command, _)| *command);
+    }
+}
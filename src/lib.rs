@@ -1,5 +1,26 @@
+pub mod comparison;
+pub mod editor;
+pub mod export;
+pub mod file;
+pub mod gaps;
+pub mod geometry;
 mod handler;
-
+mod history;
+pub mod import;
+pub mod keybindings;
+pub mod palette;
+pub mod panels;
+pub mod playback;
+pub mod repeating;
+pub mod rescale;
+pub mod split_position;
+pub mod templates;
+pub mod text;
+mod tiling_tree;
+pub mod validation;
+
+use comparison::Comparison;
+use editor::{Action, LayoutEditor, SwapDirection};
 use enums::{Align, Color, FrameType};
 use fltk::{
     enums::Shortcut,
@@ -9,16 +30,51 @@ use fltk::{
     *,
 };
 use fltk_theme::*;
+use gaps::{GapSettings, Gaps};
 use group::{PackType, ScrollType};
 use handler::handle_events;
 use himewm_layout::*;
+use keybindings::{Command, KeyCombo, Keybindings};
+use panels::PanelSizes;
 use prelude::{GroupExt, WidgetBase, WidgetExt};
+use repeating::RepeatingSplitSpec;
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
-#[derive(Clone)]
-enum SwapDirection {
-    Previous,
-    Next,
-}
+const MENU_BAR_HEIGHT: i32 = 24;
+
+const LAYOUT_FILE_FILTER: &str = "Layout files\t*.json";
+
+const IMAGE_FILE_FILTER: &str = "Images\t*.{svg,png}";
+
+const JSON_FILE_FILTER: &str = "JSON files\t*.json";
+
+const CSV_FILE_FILTER: &str = "CSV files\t*.csv";
+
+const EDGE_GRAB_DISTANCE: i32 = 4;
+
+const SNAP_DISTANCE: i32 = 8;
+
+const SPLITTER_WIDTH: i32 = 6;
+
+const STATES_STRIP_HEIGHT: i32 = 108;
+
+const VARIANT_ACTIONS_HEIGHT: i32 = 144;
+
+const ZONE_INSPECTOR_HEIGHT: i32 = 216;
+
+const ACTIONS_HEIGHT: i32 = 192;
+
+const GAP_ACTIONS_HEIGHT: i32 = 120;
+
+const END_BEHAVIOUR_HEIGHT: i32 = 256;
+
+const DIAGNOSTICS_HEIGHT: i32 = 128;
+
+const THUMBNAIL_STRIP_HEIGHT: i32 = 192;
 
 #[derive(Clone)]
 enum Message {
@@ -43,27 +99,105 @@ enum Message {
     EndZoneIdxChanged(usize),
     SwapEndTilingBehaviour,
     SwapEndTilingDirection,
+    NewLayout,
+    NewLayoutFromTemplate,
+    OpenLayout,
+    SaveLayout,
+    SaveLayoutAs,
+    Quit,
+    Undo,
+    Redo,
+    MoveZoneEdge(geometry::ZoneEdge, i32),
+    ZoneSideEdited(geometry::ZoneSide),
+    ZoneSizeEdited(Direction),
+    GapsChanged,
+    SplitZoneAt(usize, Direction, i32),
+    SplitEvenly,
+    AddRepeatingSplit,
+    DeleteRepeatingSplit(usize),
+    SwapRepeatingSplit(usize, SwapDirection),
+    RepeatingSplitChanged(usize),
+    FromZonesChanged(Option<usize>),
+    DiagnosticSelected(usize),
+    ChangeMonitorSize,
+    PreviewResolutions,
+    ExportImage,
+    ExportLayout(export::Format),
+    ImportLayout(import::Format),
+    SetSplitDirection(Direction),
+    SelectNextZone,
+    SelectAdjacentVariantState(SwapDirection),
+    SelectAdjacentVariant(SwapDirection),
+    ToggleHelp,
+    ShowCommandPalette,
+    ToggleComparison(Comparison),
+    PlayWindowCounts,
 }
 
-struct LayoutEditor {
-    layout: Layout,
-    selected_variant_idx: usize,
-    selected_variant_state_idx: usize,
-    selected_zone_idx1: Option<usize>,
-    selected_zone_idx2: Option<usize>,
-}
+impl Message {
+    fn from_command(command: Command) -> Self {
+        return match command {
+            Command::SelectZone(idx) => Message::SelectedZoneChanged(idx),
+            Command::SelectNextZone => Message::SelectNextZone,
+            Command::SplitHorizontally => Message::SetSplitDirection(Direction::Horizontal),
+            Command::SplitVertically => Message::SetSplitDirection(Direction::Vertical),
+            Command::Split => Message::Split,
+            Command::Merge => Message::Merge,
+            Command::Swap => Message::Swap,
+            Command::PreviousVariantState => {
+                Message::SelectAdjacentVariantState(SwapDirection::Previous)
+            }
+            Command::NextVariantState => Message::SelectAdjacentVariantState(SwapDirection::Next),
+            Command::PreviousVariant => Message::SelectAdjacentVariant(SwapDirection::Previous),
+            Command::NextVariant => Message::SelectAdjacentVariant(SwapDirection::Next),
+            Command::ShowHelp => Message::ToggleHelp,
+        };
+    }
 
-impl LayoutEditor {
-    fn new(layout: Layout) -> Self {
-        let default_variant_idx = layout.default_variant_idx();
+    fn modifies_layout(&self) -> bool {
+        return matches!(
+            self,
+            Message::NewVariantState
+                | Message::CloneVariantState
+                | Message::DeleteVariantState
+                | Message::SwapVariantState(_)
+                | Message::Split
+                | Message::Swap
+                | Message::Merge
+                | Message::NewVariant
+                | Message::CloneVariant
+                | Message::DeleteVariant
+                | Message::SwapVariant(_)
+                | Message::SetVariantAsDefault
+                | Message::EndZoneIdxChanged(_)
+                | Message::SwapEndTilingBehaviour
+                | Message::SwapEndTilingDirection
+                | Message::MoveZoneEdge(_, _)
+                | Message::ZoneSideEdited(_)
+                | Message::ZoneSizeEdited(_)
+                | Message::GapsChanged
+                | Message::SplitZoneAt(_, _, _)
+                | Message::SplitEvenly
+                | Message::AddRepeatingSplit
+                | Message::DeleteRepeatingSplit(_)
+                | Message::SwapRepeatingSplit(_, _)
+                | Message::RepeatingSplitChanged(_)
+                | Message::FromZonesChanged(_)
+        );
+    }
 
-        return LayoutEditor {
-            layout,
-            selected_variant_idx: default_variant_idx,
-            selected_variant_state_idx: 0,
-            selected_zone_idx1: None,
-            selected_zone_idx2: None,
-        };
+    /// Whether the message is ignored while the layout is open read-only:
+    /// edits, undoing and redoing them, and creating layouts that start out
+    /// modified.
+    fn blocked_when_readonly(&self) -> bool {
+        return self.modifies_layout()
+            || matches!(
+                self,
+                Message::Undo
+                    | Message::Redo
+                    | Message::ImportLayout(_)
+                    | Message::NewLayoutFromTemplate
+            );
     }
 }
 
@@ -71,10 +205,14 @@ struct Actions {
     widgets: group::Flex,
     split_bound_max: Option<i32>,
     selected_direction: Direction,
+    horizontal_radio_button: button::RadioRoundButton,
+    vertical_radio_button: button::RadioRoundButton,
     split_button: button::Button,
     split_axis_text: frame::Frame,
-    split_at_input: input::IntInput,
+    split_at_input: input::Input,
     split_bounds_text: frame::Frame,
+    split_parts_input: input::IntInput,
+    split_evenly_button: button::Button,
     swap_button: button::Button,
     merge_button: button::Button,
 }
@@ -85,12 +223,6 @@ impl Actions {
 
         let mut widgets = group::Flex::default_fill().row();
 
-        let widgets_w = widgets.w() / 4;
-
-        let widgets_h = widgets.h() / 4;
-
-        WidgetExt::set_size(&mut widgets, widgets_w, widgets_h);
-
         let mut split_actions_column = group::Flex::default().column();
 
         let mut horizontal_radio_button =
@@ -116,7 +248,11 @@ impl Actions {
 
         split_at_selection.fixed(&split_axis_text, 16);
 
-        let split_at_input = input::IntInput::default();
+        let mut split_at_input = input::Input::default();
+
+        split_at_input.set_tooltip(
+            "Pixels (640), percentage (50%), fraction (1/3) or ratio (2:1). Splits in half if empty",
+        );
 
         split_at_selection.end();
 
@@ -130,6 +266,24 @@ impl Actions {
 
         split_actions_column.fixed(&split_button, 32);
 
+        let mut split_evenly_row = group::Flex::default().row();
+
+        let mut split_parts_input = input::IntInput::default();
+
+        split_parts_input.set_value("2");
+
+        split_evenly_row.fixed(&split_parts_input, 48);
+
+        let mut split_evenly_button = button::Button::default().with_label("Split evenly");
+
+        split_evenly_button.emit(sender.clone(), Message::SplitEvenly);
+
+        split_evenly_button.deactivate();
+
+        split_evenly_row.end();
+
+        split_actions_column.fixed(&split_evenly_row, 32);
+
         split_actions_column.end();
 
         let columns_spacer = frame::Frame::default();
@@ -161,15 +315,28 @@ impl Actions {
         return Actions {
             widgets,
             selected_direction,
+            horizontal_radio_button,
+            vertical_radio_button,
             split_bound_max: None,
             split_button,
             split_axis_text,
             split_at_input,
             split_bounds_text,
+            split_parts_input,
+            split_evenly_button,
             merge_button,
             swap_button,
         };
     }
+
+    /// Shows `direction` as the chosen split direction, without choosing it.
+    fn toggle_direction_buttons(&mut self, direction: Direction) {
+        let horizontal = matches!(direction, Direction::Horizontal);
+
+        self.horizontal_radio_button.toggle(horizontal);
+
+        self.vertical_radio_button.toggle(!horizontal);
+    }
 }
 
 struct VariantActions {
@@ -182,10 +349,6 @@ impl VariantActions {
     fn initialize(sender: &app::Sender<Message>) -> Self {
         let mut widgets = group::Flex::default_fill().column();
 
-        let w = widgets.w() / 8;
-
-        WidgetExt::set_size(&mut widgets, w, 144);
-
         let create_row = group::Flex::default()
             .with_size(0, 32)
             .with_type(FlexType::Row);
@@ -243,6 +406,11 @@ impl VariantActions {
 
 struct Directional {
     widgets: group::Flex,
+    horizontal_radio_button: button::RadioRoundButton,
+    vertical_radio_button: button::RadioRoundButton,
+    from_zones_choice: menu::Choice,
+    from_zones_preview: frame::Frame,
+    from_zones_preview_zones: Rc<RefCell<Option<(Vec<Zone>, Gaps, f64, f64)>>>,
     end_tiling_behaviour: EndTilingBehaviour,
 }
 
@@ -267,7 +435,7 @@ impl Directional {
             } => EndTilingBehaviour::default_directional(),
         };
 
-        let widgets = group::Flex::default_fill().column();
+        let mut widgets = group::Flex::default_fill().column();
 
         let _direction_label = frame::Frame::default().with_label("Direction");
 
@@ -296,34 +464,342 @@ impl Directional {
             }
         }
 
-        let _compression_frame = frame::Frame::default();
+        let mut from_zones_row = group::Flex::default().row();
+
+        let from_zones_text = frame::Frame::default()
+            .with_align(Align::Left.union(Align::Inside))
+            .with_label("Compress from: ");
+
+        from_zones_row.fixed(&from_zones_text, 112);
+
+        let mut from_zones_choice = menu::Choice::default();
+
+        from_zones_choice.set_tooltip("The variant state directional compression starts from");
+
+        from_zones_row.end();
+
+        widgets.fixed(&from_zones_row, 32);
+
+        let from_zones_preview_zones: Rc<RefCell<Option<(Vec<Zone>, Gaps, f64, f64)>>> =
+            Rc::new(RefCell::new(None));
+
+        let mut from_zones_preview = frame::Frame::default();
+
+        from_zones_preview.set_tooltip("The compressed state produced when extending");
+
+        from_zones_preview.draw({
+            let from_zones_preview_zones = from_zones_preview_zones.clone();
+
+            move |f| {
+                draw::draw_rect_fill(f.x(), f.y(), f.w(), f.h(), Color::Background2);
+
+                let (zones, gaps, variant_width, variant_height) =
+                    match &*from_zones_preview_zones.borrow() {
+                        Some(val) => val.to_owned(),
+                        None => {
+                            draw::set_draw_color(Color::Foreground);
+
+                            draw::set_font(enums::Font::Helvetica, 12);
+
+                            draw::draw_text2(
+                                "No compression",
+                                f.x(),
+                                f.y(),
+                                f.w(),
+                                f.h(),
+                                Align::Center,
+                            );
+
+                            return;
+                        }
+                    };
+
+                let scale = f64::min(f.w() as f64 / variant_width, f.h() as f64 / variant_height);
+
+                let w = (variant_width * scale).round() as i32;
+
+                let h = (variant_height * scale).round() as i32;
+
+                let x = f.x() + (f.w() - w) / 2;
+
+                let y = f.y() + (f.h() - h) / 2;
+
+                draw_zones(&zones, gaps, variant_width, variant_height, x, y, w, h);
+            }
+        });
 
         widgets.end();
 
         return Directional {
             widgets,
+            horizontal_radio_button,
+            vertical_radio_button,
+            from_zones_choice,
+            from_zones_preview,
+            from_zones_preview_zones,
             end_tiling_behaviour,
         };
     }
+
+    fn update_from_zones_controls(
+        &mut self,
+        layout: &Layout,
+        gaps: &GapSettings,
+        variant_idx: usize,
+        sender: &app::Sender<Message>,
+    ) {
+        let variant = &layout.get_variants()[variant_idx];
+
+        self.from_zones_choice.clear();
+
+        self.from_zones_choice.add_emit(
+            "Automatic",
+            Shortcut::None,
+            MenuFlag::Normal,
+            sender.clone(),
+            Message::FromZonesChanged(None),
+        );
+
+        for idx in 0..variant.manual_zones_until() {
+            self.from_zones_choice.add_emit(
+                (idx + 1).to_string().as_str(),
+                Shortcut::None,
+                MenuFlag::Normal,
+                sender.clone(),
+                Message::FromZonesChanged(Some(idx)),
+            );
+        }
+
+        let selected = match variant.get_end_tiling_behaviour() {
+            EndTilingBehaviour::Directional {
+                direction: _,
+                from_zones: Some(from_zones),
+                zone_idx: _,
+            } => match variant
+                .get_zones()
+                .iter()
+                .take(variant.manual_zones_until())
+                .position(|zones| geometry::same_zones(zones, from_zones))
+            {
+                Some(idx) => idx as i32 + 1,
+                None => -1,
+            },
+            _ => 0,
+        };
+
+        self.from_zones_choice.set_value(selected);
+
+        let mut updated_variant = variant.clone();
+
+        updated_variant.update_from_zones();
+
+        let from_zones = match updated_variant.get_end_tiling_behaviour() {
+            EndTilingBehaviour::Directional {
+                direction: _,
+                from_zones: Some(from_zones),
+                zone_idx: _,
+            } if updated_variant.using_from_zones() => Some((
+                from_zones.to_owned(),
+                gaps.for_variant(variant_idx),
+                layout.get_monitor_rect().w() as f64,
+                layout.get_monitor_rect().h() as f64,
+            )),
+            _ => None,
+        };
+
+        *self.from_zones_preview_zones.borrow_mut() = from_zones;
+
+        self.from_zones_preview.redraw();
+    }
+}
+
+struct RepeatingSplitRow {
+    direction_choice: menu::Choice,
+    split_ratio_spinner: misc::Spinner,
+    zone_idx_spinner: misc::Spinner,
+    swap_check_button: button::CheckButton,
+}
+
+impl RepeatingSplitRow {
+    fn initialize(idx: usize, split: &RepeatingSplitSpec, sender: &app::Sender<Message>) -> Self {
+        let mut row = group::Flex::default().with_size(0, 28).row();
+
+        row.set_pad(4);
+
+        let mut direction_choice = menu::Choice::default();
+
+        direction_choice.add_choice("Horizontal|Vertical");
+
+        direction_choice.set_value(match split.direction {
+            Direction::Horizontal => 0,
+            Direction::Vertical => 1,
+        });
+
+        direction_choice.emit(sender.clone(), Message::RepeatingSplitChanged(idx));
+
+        let mut split_ratio_spinner = misc::Spinner::default();
+
+        split_ratio_spinner.set_type(misc::SpinnerType::Float);
+
+        split_ratio_spinner.set_range(0.05, 0.95);
+
+        split_ratio_spinner.set_step(0.05);
+
+        split_ratio_spinner.set_value(split.split_ratio);
+
+        split_ratio_spinner.set_tooltip("Split ratio");
+
+        split_ratio_spinner.emit(sender.clone(), Message::RepeatingSplitChanged(idx));
+
+        row.fixed(&split_ratio_spinner, 64);
+
+        let mut zone_idx_spinner = misc::Spinner::default();
+
+        zone_idx_spinner.set_type(misc::SpinnerType::Int);
+
+        zone_idx_spinner.set_range(1.0, 99.0);
+
+        zone_idx_spinner.set_step(1.0);
+
+        zone_idx_spinner.set_value((split.zone_idx + 1) as f64);
+
+        zone_idx_spinner.set_tooltip("Zone to split");
+
+        zone_idx_spinner.emit(sender.clone(), Message::RepeatingSplitChanged(idx));
+
+        row.fixed(&zone_idx_spinner, 56);
+
+        let mut swap_check_button = button::CheckButton::default().with_label("Swap");
+
+        swap_check_button.set_checked(split.swap);
+
+        swap_check_button.emit(sender.clone(), Message::RepeatingSplitChanged(idx));
+
+        row.fixed(&swap_check_button, 64);
+
+        let mut up_button = button::Button::default().with_label("@8>");
+
+        up_button.emit(
+            sender.clone(),
+            Message::SwapRepeatingSplit(idx, SwapDirection::Previous),
+        );
+
+        row.fixed(&up_button, 24);
+
+        let mut down_button = button::Button::default().with_label("@2>");
+
+        down_button.emit(
+            sender.clone(),
+            Message::SwapRepeatingSplit(idx, SwapDirection::Next),
+        );
+
+        row.fixed(&down_button, 24);
+
+        let mut delete_button = button::Button::default().with_label("@1+");
+
+        delete_button.emit(sender.clone(), Message::DeleteRepeatingSplit(idx));
+
+        row.fixed(&delete_button, 24);
+
+        row.end();
+
+        return RepeatingSplitRow {
+            direction_choice,
+            split_ratio_spinner,
+            zone_idx_spinner,
+            swap_check_button,
+        };
+    }
+
+    fn value(&self) -> RepeatingSplitSpec {
+        let direction = match self.direction_choice.value() {
+            0 => Direction::Horizontal,
+            _ => Direction::Vertical,
+        };
+
+        return RepeatingSplitSpec {
+            direction,
+            split_ratio: self.split_ratio_spinner.value(),
+            zone_idx: self.zone_idx_spinner.value() as usize - 1,
+            swap: self.swap_check_button.is_checked(),
+        };
+    }
 }
 
 struct Repeating {
     widgets: group::Flex,
+    split_rows: group::Pack,
+    rows: Vec<RepeatingSplitRow>,
     end_tiling_behaviour: EndTilingBehaviour,
 }
 
 impl Repeating {
-    fn initialize() -> Self {
+    fn initialize(sender: &app::Sender<Message>) -> Self {
+        let mut widgets = group::Flex::default_fill().column();
+
+        let splits_label = frame::Frame::default()
+            .with_align(Align::Left.union(Align::Inside))
+            .with_label("Splits");
+
+        widgets.fixed(&splits_label, 24);
+
+        let mut scroll = group::Scroll::default().with_type(ScrollType::Vertical);
+
+        scroll.set_color(Color::Background2);
+
+        scroll.resize_callback(|s, _, _, w, _| {
+            if let Some(p) = &mut s.child(0) {
+                p.set_size(w, p.h());
+            }
+        });
+
+        let mut split_rows = group::Pack::default_fill().with_type(PackType::Vertical);
+
+        split_rows.set_spacing(4);
+
+        split_rows.end();
+
+        scroll.end();
+
+        let mut add_button = button::Button::default().with_label("Add split");
+
+        add_button.emit(sender.clone(), Message::AddRepeatingSplit);
+
+        widgets.fixed(&add_button, 32);
+
+        widgets.end();
+
         return Repeating {
-            widgets: group::Flex::default(),
+            widgets,
+            split_rows,
+            rows: Vec::new(),
             end_tiling_behaviour: EndTilingBehaviour::default_repeating(),
         };
     }
+
+    fn update_rows(&mut self, splits: &[RepeatingSplitSpec], sender: &app::Sender<Message>) {
+        self.split_rows.clear();
+
+        self.rows.clear();
+
+        self.split_rows.begin();
+
+        for (i, split) in splits.iter().enumerate() {
+            self.rows
+                .push(RepeatingSplitRow::initialize(i, split, sender));
+        }
+
+        self.split_rows.end();
+
+        self.widgets.redraw();
+    }
 }
 
 struct EndBehaviourActions {
-    preview_count: u32,
+    preview_count: usize,
     widgets: group::Flex,
+    directional_radio_button: button::RadioRoundButton,
+    repeating_radio_button: button::RadioRoundButton,
     zone_idx_choice: menu::Choice,
     cancel_preview_button: button::Button,
     directional: Directional,
@@ -334,12 +810,6 @@ impl EndBehaviourActions {
     fn initialize(layout: &Layout, sender: &app::Sender<Message>) -> Self {
         let mut widgets = group::Flex::default_fill().row();
 
-        let w = widgets.w() / 3;
-
-        let h = widgets.h() / 4;
-
-        WidgetExt::set_size(&mut widgets, w, h);
-
         let mut behaviour_column = group::Flex::default().column();
 
         let _offset_frame = frame::Frame::default();
@@ -396,11 +866,17 @@ impl EndBehaviourActions {
 
         widgets.add(&directional.widgets);
 
-        let repeating = Repeating::initialize();
+        let mut repeating = Repeating::initialize(sender);
+
+        widgets.add(&repeating.widgets);
+
+        repeating.widgets.hide();
 
         return EndBehaviourActions {
             preview_count: 0,
             widgets,
+            directional_radio_button,
+            repeating_radio_button,
             zone_idx_choice,
             cancel_preview_button,
             directional,
@@ -409,1098 +885,4297 @@ impl EndBehaviourActions {
     }
 }
 
-#[derive(Clone)]
-struct Buffers {
-    layout: Layout,
-    variant_state_selection: group::Scroll,
-    variant_state_pack: group::Pack,
-    variant_state_display: group::Group,
-}
-
-impl Buffers {
-    fn new(
-        layout: Layout,
-        variant_state_selection: group::Scroll,
-        variant_state_pack: group::Pack,
-        variant_state_display: group::Group,
-    ) -> Self {
-        Buffers {
-            layout,
-            variant_state_selection,
-            variant_state_pack,
-            variant_state_display,
-        }
-    }
+/// Shows the geometry of the first selected zone in pixels and as a share of
+/// the monitor, and lets its sides and size be typed in.
+struct ZoneInspector {
+    widgets: group::Flex,
+    title: frame::Frame,
+    side_rows: Vec<(input::Input, frame::Frame)>,
+    size_rows: Vec<(input::Input, frame::Frame)>,
 }
 
-struct EditorWidgets {
-    editor: LayoutEditor,
-    buffers: Option<Buffers>,
-    variant_list: group::Scroll,
-    variant_state_selection: group::Scroll,
-    variant_state_pack: group::Pack,
-    variant_state_display: group::Group,
-    actions: Actions,
-    variant_actions: VariantActions,
-    end_behaviour_actions: EndBehaviourActions,
-}
+impl ZoneInspector {
+    fn initialize(sender: &app::Sender<Message>) -> Self {
+        let mut widgets = group::Flex::default_fill().column();
 
-impl EditorWidgets {
-    fn initialize(layout: Layout, sender: &app::Sender<Message>) -> Self {
-        let variant_list = Self::create_variant_list(&layout, sender);
+        let title = frame::Frame::default()
+            .with_align(Align::Left.union(Align::Inside))
+            .with_label("No zone selected");
+
+        widgets.fixed(&title, 20);
+
+        let side_rows = geometry::ZoneSide::ALL
+            .into_iter()
+            .map(|side| {
+                let label = match side {
+                    geometry::ZoneSide::Left => "Left",
+                    geometry::ZoneSide::Top => "Top",
+                    geometry::ZoneSide::Right => "Right",
+                    geometry::ZoneSide::Bottom => "Bottom",
+                };
+
+                return zone_inspector_row(
+                    &mut widgets,
+                    label,
+                    Message::ZoneSideEdited(side),
+                    sender,
+                );
+            })
+            .collect();
+
+        let size_rows = [
+            ("Width", Direction::Horizontal),
+            ("Height", Direction::Vertical),
+        ]
+        .into_iter()
+        .map(|(label, direction)| {
+            return zone_inspector_row(
+                &mut widgets,
+                label,
+                Message::ZoneSizeEdited(direction),
+                sender,
+            );
+        })
+        .collect();
 
-        let variant_state_selection = Self::create_variant_state_selection(&layout, sender);
+        widgets.end();
 
-        let variant_state_buttons =
-            Self::create_variant_state_buttons(sender).below_of(&variant_state_selection, 4);
+        return ZoneInspector {
+            widgets,
+            title,
+            side_rows,
+            size_rows,
+        };
+    }
 
-        let variant_state_pack_h = variant_state_selection.h() + variant_state_buttons.h();
+    /// Shows the zone at `zone_idx` of `zones`, or clears the inspector if it
+    /// is `None`. Sides on the border of the monitor cannot be moved, so
+    /// their inputs are deactivated.
+    fn update(&mut self, zones: &[Zone], zone_idx: Option<usize>, monitor_size: (i32, i32)) {
+        let zone = match zone_idx.and_then(|idx| zones.get(idx)) {
+            Some(val) => val,
+            None => {
+                self.title.set_label("No zone selected");
 
-        let mut variant_state_pack =
-            group::Pack::default().with_size(variant_state_selection.w(), variant_state_pack_h);
+                for (input, percentage) in self.side_rows.iter_mut().chain(&mut self.size_rows) {
+                    input.set_value("");
 
-        variant_state_pack.end();
+                    input.deactivate();
 
-        variant_state_pack.set_spacing(4);
+                    percentage.set_label("");
+                }
 
-        variant_state_pack.resize_callback(move |p, _, _, _, h| {
-            if h != variant_state_pack_h {
-                p.widget_resize(p.x(), p.y(), p.w(), variant_state_pack_h);
+                return;
             }
-        });
+        };
 
-        variant_state_pack.add(&variant_state_selection);
+        let zone_idx = zone_idx.unwrap();
 
-        variant_state_pack.add(&variant_state_buttons);
+        self.title
+            .set_label(format!("Zone {}", zone_idx + 1).as_str());
 
-        let variant_state_display = Self::create_variant_state_display(&layout, sender);
+        let percentage_of = |value: i32, direction: Direction| {
+            let extent = match direction {
+                Direction::Horizontal => monitor_size.0,
+                Direction::Vertical => monitor_size.1,
+            };
 
-        let actions = Actions::initialize(sender);
+            return format!("{:.1}%", value as f64 * 100.0 / extent as f64);
+        };
 
-        let variant_actions = VariantActions::initialize(sender);
+        for (side, (input, percentage)) in
+            geometry::ZoneSide::ALL.into_iter().zip(&mut self.side_rows)
+        {
+            let position = side.position(zone);
 
-        let end_behaviour_actions = EndBehaviourActions::initialize(&layout, sender);
+            input.set_value(position.to_string().as_str());
 
-        let editor = LayoutEditor::new(layout);
+            percentage.set_label(percentage_of(position, side.direction()).as_str());
 
-        let buffers = None;
+            match geometry::side_edge(zones, zone_idx, side) {
+                Some(_) => input.activate(),
+                None => input.deactivate(),
+            }
+        }
 
-        let mut ret = EditorWidgets {
-            editor,
-            buffers,
-            variant_list,
-            variant_state_selection,
-            variant_state_pack,
-            variant_state_display,
-            actions,
-            variant_actions,
-            end_behaviour_actions,
+        let spans_monitor = |direction: Direction| {
+            return match direction {
+                Direction::Horizontal => zone.w() == monitor_size.0,
+                Direction::Vertical => zone.h() == monitor_size.1,
+            };
         };
 
-        ret.update_highlighted_variant(
-            ret.editor.selected_variant_idx,
-            ret.editor.selected_variant_idx,
-        );
+        for ((size, direction), (input, percentage)) in [
+            (zone.w(), Direction::Horizontal),
+            (zone.h(), Direction::Vertical),
+        ]
+        .into_iter()
+        .zip(&mut self.size_rows)
+        {
+            input.set_value(size.to_string().as_str());
 
-        ret.update_shown_variant_state_selection(
-            ret.editor.selected_variant_idx,
-            ret.editor.selected_variant_idx,
-        );
+            percentage.set_label(percentage_of(size, direction).as_str());
 
-        ret.update_highlighted_variant_state_button(
-            (
-                ret.editor.selected_variant_idx,
-                ret.editor.selected_variant_state_idx,
-            ),
-            (
-                ret.editor.selected_variant_idx,
-                ret.editor.selected_variant_state_idx,
-            ),
-        );
+            if spans_monitor(direction) {
+                input.deactivate();
+            } else {
+                input.activate();
+            }
+        }
+    }
+}
 
-        ret.update_shown_variant_state(
-            (
-                ret.editor.selected_variant_idx,
-                ret.editor.selected_variant_state_idx,
-            ),
-            (
-                ret.editor.selected_variant_idx,
-                ret.editor.selected_variant_state_idx,
-            ),
-        );
+/// The inner gap and outer margin of the layout, or of the selected variant
+/// if it has gaps of its own.
+struct GapActions {
+    widgets: group::Flex,
+    inner_spinner: misc::Spinner,
+    outer_spinner: misc::Spinner,
+    own_gaps_check_button: button::CheckButton,
+}
 
-        ret.update_end_zone_idx_choice(sender);
+impl GapActions {
+    fn initialize(sender: &app::Sender<Message>) -> Self {
+        let mut widgets = group::Flex::default_fill().column();
 
-        ret.end_behaviour_actions.zone_idx_choice.set_value(
-            ret.editor.layout.get_variants()[ret.editor.selected_variant_idx].get_end_zone_idx()
-                as i32,
-        );
+        let title = frame::Frame::default()
+            .with_align(Align::Left.union(Align::Inside))
+            .with_label("Gaps");
 
-        if ret.editor.layout.variants_len() == 1 {
-            ret.variant_actions.delete_button.deactivate();
-        }
+        widgets.fixed(&title, 20);
 
-        if ret.editor.selected_variant_idx == ret.editor.layout.default_variant_idx() {
-            ret.variant_actions.set_as_default_button.deactivate();
+        let mut inner_spinner = labelled_spinner(&mut widgets, "Inner gap");
+
+        inner_spinner.set_tooltip("Space between neighbouring windows, in pixels");
+
+        let mut outer_spinner = labelled_spinner(&mut widgets, "Outer margin");
+
+        outer_spinner
+            .set_tooltip("Space between windows and the borders of the monitor, in pixels");
+
+        for spinner in [&mut inner_spinner, &mut outer_spinner] {
+            spinner.set_range(0.0, 256.0);
+
+            spinner.emit(sender.clone(), Message::GapsChanged);
         }
 
-        return ret;
+        let mut own_gaps_check_button =
+            button::CheckButton::default().with_label("Only this variant");
+
+        own_gaps_check_button.set_tooltip("Use these gaps for the selected variant only");
+
+        own_gaps_check_button.emit(sender.clone(), Message::GapsChanged);
+
+        widgets.fixed(&own_gaps_check_button, 24);
+
+        widgets.end();
+
+        return GapActions {
+            widgets,
+            inner_spinner,
+            outer_spinner,
+            own_gaps_check_button,
+        };
     }
 
-    fn display_group_from_variant_state(
-        variant_width: f64,
-        variant_height: f64,
-        variant: &Variant,
-        idx: usize,
-        sender: &app::Sender<Message>,
-    ) -> group::Group {
-        let group = group::Group::default_fill();
+    fn update(&mut self, gaps: &GapSettings, variant_idx: usize) {
+        let shown = gaps.for_variant(variant_idx);
 
-        let w = group.w();
+        self.inner_spinner.set_value(shown.inner as f64);
 
-        let h = group.h();
+        self.outer_spinner.set_value(shown.outer as f64);
 
-        let x_offset = group.x();
+        self.own_gaps_check_button
+            .set_checked(gaps.variants.contains_key(&variant_idx));
+    }
 
-        let y_offset = group.y();
+    fn value(&self) -> Gaps {
+        return Gaps {
+            inner: self.inner_spinner.value() as i32,
+            outer: self.outer_spinner.value() as i32,
+        };
+    }
+}
 
-        let zones = &variant.get_zones()[idx];
+/// A variant state drawn in a thumbnail strip.
+struct StripThumbnail {
+    label: String,
+    zones: Vec<Zone>,
+    gaps: Gaps,
+    selected: bool,
+    /// The variant and variant state clicking the thumbnail selects, if it
+    /// can be selected at all.
+    selects: Option<(usize, usize)>,
+}
 
-        for (i, zone) in zones.iter().enumerate() {
-            let mut b = button::Button::new(
-                ((zone.left as f64 * w as f64) / variant_width).round() as i32 + x_offset,
-                ((zone.top as f64 * h as f64) / variant_height).round() as i32 + y_offset,
-                ((zone.w() as f64 * w as f64) / variant_width).round() as i32,
-                ((zone.h() as f64 * h as f64) / variant_height).round() as i32,
-                Some((i + 1).to_string().as_str()),
-            );
+impl StripThumbnail {
+    fn same_as(&self, other: &StripThumbnail) -> bool {
+        return self.label == other.label
+            && self.gaps == other.gaps
+            && self.selected == other.selected
+            && self.selects == other.selects
+            && geometry::same_zones(&self.zones, &other.zones);
+    }
+}
 
-            // TODO: this frame type doesn't look too great - probably
-            // figure out how to make it look better
-            b.set_frame(FrameType::EmbossedBox);
+/// Shows several variant states at once as thumbnails below the canvas.
+/// Clicking a thumbnail selects its variant state, if it has one.
+struct ThumbnailStrip {
+    widgets: group::Flex,
+    title: frame::Frame,
+    thumbnails: frame::Frame,
+    shown: Rc<RefCell<Vec<StripThumbnail>>>,
+}
 
-            b.set_label_size(36);
+impl ThumbnailStrip {
+    fn initialize(monitor_size: (i32, i32), sender: &app::Sender<Message>) -> Self {
+        let mut widgets = group::Flex::default_fill().column();
 
-            b.set_label_color(Color::Black);
+        let title = frame::Frame::default().with_align(Align::Left.union(Align::Inside));
 
-            b.set_color(colors::html::Gainsboro);
+        widgets.fixed(&title, 20);
 
-            b.set_selection_color(colors::html::DodgerBlue);
+        let mut thumbnails = frame::Frame::default();
 
-            b.emit(sender.clone(), Message::SelectedZoneChanged(i));
-        }
+        thumbnails.set_frame(FrameType::FlatBox);
 
-        group.end();
+        thumbnails.set_color(Color::Background2);
 
-        return group;
-    }
+        widgets.end();
 
-    fn create_variant_list(layout: &Layout, sender: &app::Sender<Message>) -> group::Scroll {
-        let mut scroll = group::Scroll::default_fill().with_type(ScrollType::Vertical);
+        widgets.hide();
 
-        scroll.set_size(scroll.w() / 8, scroll.h() / 2);
+        let shown: Rc<RefCell<Vec<StripThumbnail>>> = Rc::new(RefCell::new(Vec::new()));
 
-        scroll.set_color(Color::Background2);
+        thumbnails.draw({
+            let shown = shown.clone();
 
-        scroll.resize_callback(|s, _, _, w, _| {
-            if let Some(p) = &mut s.child(0) {
-                p.set_size(w, p.h());
+            move |f| Self::draw(f, &shown.borrow(), monitor_size)
+        });
+
+        thumbnails.handle({
+            let shown = shown.clone();
+
+            let sender = sender.clone();
+
+            move |f, ev| {
+                if ev != enums::Event::Push {
+                    return false;
+                }
+
+                let shown = shown.borrow();
+
+                let clicked = Self::grid(f, shown.len(), monitor_size)
+                    .into_iter()
+                    .position(|(x, y, w, h)| {
+                        let (mouse_x, mouse_y) = (app::event_x(), app::event_y());
+
+                        return x <= mouse_x && mouse_x < x + w && y <= mouse_y && mouse_y < y + h;
+                    });
+
+                let (variant_idx, variant_state_idx) =
+                    match clicked.and_then(|idx| shown[idx].selects) {
+                        Some(val) => val,
+                        None => return false,
+                    };
+
+                sender.send(Message::SelectedVariantChanged(variant_idx));
+
+                sender.send(Message::SelectedVariantStateChanged(variant_state_idx));
+
+                return true;
             }
         });
 
-        let pack = group::Pack::default_fill().with_type(PackType::Vertical);
+        return ThumbnailStrip {
+            widgets,
+            title,
+            thumbnails,
+            shown,
+        };
+    }
 
-        for i in 0..layout.variants_len() {
-            let mut b = button::Button::default().with_size(0, 20);
+    fn grid(f: &frame::Frame, count: usize, monitor_size: (i32, i32)) -> Vec<(i32, i32, i32, i32)> {
+        return comparison::thumbnail_grid(
+            count,
+            monitor_size.0,
+            monitor_size.1,
+            (f.x() + 4, f.y() + 4, f.w() - 8, f.h() - 8),
+            8,
+            16,
+        );
+    }
 
-            b.set_label_size(16);
+    fn draw(f: &frame::Frame, shown: &[StripThumbnail], monitor_size: (i32, i32)) {
+        draw::draw_rect_fill(f.x(), f.y(), f.w(), f.h(), Color::Background2);
+
+        let grid = Self::grid(f, shown.len(), monitor_size);
+
+        for (thumbnail, (x, y, w, h)) in shown.iter().zip(grid) {
+            draw_zones(
+                &thumbnail.zones,
+                thumbnail.gaps,
+                monitor_size.0 as f64,
+                monitor_size.1 as f64,
+                x,
+                y,
+                w,
+                h,
+            );
 
-            b.set_color(colors::html::DodgerBlue);
+            if thumbnail.selected {
+                draw::set_draw_color(colors::html::DodgerBlue);
 
-            b.set_frame(FrameType::NoBox);
+                draw::draw_rect(x - 2, y - 2, w + 4, h + 4);
 
-            if i == layout.default_variant_idx() {
-                b.set_label(format!("{i} (default)").as_str());
-            } else {
-                b.set_label(i.to_string().as_str());
+                draw::draw_rect(x - 3, y - 3, w + 6, h + 6);
             }
 
-            b.emit(sender.clone(), Message::SelectedVariantChanged(i));
+            draw::set_draw_color(Color::Foreground);
+
+            draw::set_font(enums::Font::Helvetica, 12);
+
+            draw::draw_text2(thumbnail.label.as_str(), x, y + h, w, 16, Align::Center);
         }
+    }
 
-        pack.end();
+    /// Shows or hides the strip. Returns whether that changed, in which case
+    /// the column it is in needs to be laid out again.
+    fn set_visible(&mut self, visible: bool) -> bool {
+        if self.widgets.visible() == visible {
+            return false;
+        }
 
-        scroll.end();
+        if visible {
+            self.widgets.show();
+        } else {
+            self.widgets.hide();
+        }
 
-        return scroll;
+        return true;
     }
 
-    fn create_variant_state_selection(
-        layout: &Layout,
-        sender: &app::Sender<Message>,
-    ) -> group::Scroll {
-        let mut scroll = group::Scroll::default_fill().with_type(ScrollType::Horizontal);
+    /// Shows `thumbnails` under `title`, redrawing the thumbnails only if
+    /// they changed since the last update.
+    fn update(&mut self, title: &str, thumbnails: Vec<StripThumbnail>) {
+        self.title.set_label(title);
 
-        scroll.set_size(scroll.w() / 2, 72);
+        self.title.redraw();
 
-        // Any styling of the scrollbar should probably happen here
+        let unchanged = {
+            let shown = self.shown.borrow();
 
-        scroll.set_color(Color::Background2);
+            shown.len() == thumbnails.len()
+                && shown
+                    .iter()
+                    .zip(&thumbnails)
+                    .all(|(shown, thumbnail)| shown.same_as(thumbnail))
+        };
 
-        for variant in layout.get_variants() {
-            Self::create_state_selection_pack(&scroll, variant, sender);
+        if unchanged {
+            return;
         }
 
-        scroll.end();
+        *self.shown.borrow_mut() = thumbnails;
 
-        return scroll;
+        self.thumbnails.redraw();
     }
+}
 
-    fn create_state_selection_pack(
-        scroll: &group::Scroll,
-        variant: &Variant,
-        sender: &app::Sender<Message>,
-    ) -> group::Pack {
-        let mut pack = group::Pack::default()
-            .with_size(scroll.w() - 8, 64)
-            .with_type(PackType::Horizontal)
-            .center_of_parent();
+struct Diagnostics {
+    widgets: group::Flex,
+    browser: browser::HoldBrowser,
+    diagnostics: Vec<validation::Diagnostic>,
+}
 
-        pack.set_spacing(4);
+impl Diagnostics {
+    fn initialize(sender: &app::Sender<Message>) -> Self {
+        let mut widgets = group::Flex::default_fill().column();
 
-        for j in 0..variant.manual_zones_until() {
-            let mut b = button::Button::default()
-                .with_size(64, 0)
-                .with_label((j + 1).to_string().as_str());
+        let title = frame::Frame::default()
+            .with_align(Align::Left.union(Align::Inside))
+            .with_label("Problems");
 
-            b.set_color(Color::Background2);
+        widgets.fixed(&title, 20);
 
-            b.set_selection_color(Color::Background);
+        let mut browser = browser::HoldBrowser::default();
 
-            b.emit(sender.clone(), Message::SelectedVariantStateChanged(j));
-        }
+        browser.set_callback({
+            let sender = sender.clone();
 
-        pack.end();
+            move |b| {
+                if b.value() > 0 {
+                    sender.send(Message::DiagnosticSelected(b.value() as usize - 1));
+                }
+            }
+        });
 
-        pack.hide();
+        widgets.end();
 
-        return pack;
+        return Diagnostics {
+            widgets,
+            browser,
+            diagnostics: Vec::new(),
+        };
     }
 
-    fn create_variant_state_buttons(sender: &app::Sender<Message>) -> group::Flex {
-        let mut flex = group::Flex::default_fill().row();
+    fn update(&mut self, layout: &Layout) {
+        self.diagnostics = validation::validate(layout);
 
-        let new_width = flex.w() / 2;
+        self.browser.clear();
 
-        WidgetExt::set_size(&mut flex, new_width, 32);
+        for diagnostic in &self.diagnostics {
+            let severity = match diagnostic.severity() {
+                validation::Severity::Error => "Error",
+                validation::Severity::Warning => "Warning",
+            };
 
-        flex.set_pad(4);
+            self.browser
+                .add(format!("{severity}: {diagnostic}").as_str());
+        }
 
-        let mut new_button = button::Button::default().with_label("New");
+        if self.diagnostics.is_empty() {
+            self.browser.add("No problems found");
+        }
+    }
+}
 
-        let mut clone_button = button::Button::default().with_label("Clone");
+/// The containers the editor widgets are laid out in: the variants sidebar on
+/// the left, the variant states strip above the canvas in the centre, and the
+/// inspector on the right. The sidebar and the inspector keep their width
+/// when the window is resized, and can be resized by dragging the splitters
+/// next to them.
+struct Panels {
+    root: group::Flex,
+    sidebar: group::Flex,
+    centre: group::Flex,
+    states_strip: group::Group,
+    canvas: group::Group,
+    inspector: group::Flex,
+}
 
-        let mut delete_button = button::Button::default().with_label("Delete");
+impl Panels {
+    fn initialize(
+        bounds: (i32, i32, i32, i32),
+        monitor_size: (i32, i32),
+        panel_sizes: &Rc<RefCell<PanelSizes>>,
+    ) -> Self {
+        let (x, y, w, h) = bounds;
 
-        let _frame = frame::Frame::default();
+        let sizes = panel_sizes.borrow().fitted(w - 2 * SPLITTER_WIDTH);
 
-        let mut left_button = button::Button::default().with_label("@<");
+        let mut root = group::Flex::new(x, y, w, h, None).row();
 
-        let mut right_button = button::Button::default().with_label("@>");
+        root.set_margin(4);
 
-        flex.fixed(&new_button, 64);
+        root.set_pad(0);
 
-        flex.fixed(&clone_button, 80);
+        let mut sidebar = group::Flex::default().column();
 
-        flex.fixed(&delete_button, 64);
+        sidebar.set_pad(4);
 
-        flex.fixed(&left_button, 32);
+        sidebar.end();
 
-        flex.fixed(&right_button, 32);
+        root.fixed(&sidebar, sizes.sidebar_w);
 
-        flex.end();
+        let mut sidebar_splitter = frame::Frame::default();
 
-        new_button.emit(sender.clone(), Message::NewVariantState);
+        root.fixed(&sidebar_splitter, SPLITTER_WIDTH);
 
-        clone_button.emit(sender.clone(), Message::CloneVariantState);
+        let mut centre = group::Flex::default().column();
 
-        delete_button.emit(sender.clone(), Message::DeleteVariantState);
+        centre.set_pad(4);
 
-        left_button.emit(
-            sender.clone(),
-            Message::SwapVariantState(SwapDirection::Previous),
-        );
+        let mut states_strip = group::Group::default();
 
-        right_button.emit(
-            sender.clone(),
-            Message::SwapVariantState(SwapDirection::Next),
-        );
+        states_strip.end();
 
-        return flex;
-    }
+        centre.fixed(&states_strip, STATES_STRIP_HEIGHT);
 
-    fn create_variant_state_display(
-        layout: &Layout,
-        sender: &app::Sender<Message>,
-    ) -> group::Group {
-        let mut group = group::Group::default_fill();
+        let mut canvas = group::Group::default();
 
-        group.set_size(group.w() / 2, group.h() / 2);
+        canvas.end();
 
-        for variant in layout.get_variants() {
-            let mut display = group::Group::default_fill();
+        centre.end();
 
-            for i in 0..variant.manual_zones_until() {
-                let mut g = Self::display_group_from_variant_state(
-                    layout.get_monitor_rect().w() as f64,
-                    layout.get_monitor_rect().h() as f64,
-                    variant,
-                    i,
-                    sender,
-                );
+        let mut inspector_splitter = frame::Frame::default();
 
-                g.hide();
-            }
+        root.fixed(&inspector_splitter, SPLITTER_WIDTH);
 
-            display.end();
+        let mut inspector = group::Flex::default().column();
 
-            display.hide();
-        }
+        inspector.set_pad(4);
 
-        group.end();
+        inspector.end();
 
-        return group;
-    }
+        root.fixed(&inspector, sizes.inspector_w);
 
-    fn update_highlighted_variant(&mut self, old_idx: usize, new_idx: usize) {
-        if let Some(p) = &self.variant_list.child(0) {
-            let pack = group::Pack::from_dyn_widget(p).unwrap();
+        root.end();
 
-            if let Some(old_button) = &mut pack.child(old_idx as i32) {
-                old_button.set_frame(FrameType::NoBox);
+        root.recalc();
+
+        states_strip.resize_callback(|g, x, y, w, h| {
+            for i in 0..g.children() {
+                if let Some(mut child) = g.child(i) {
+                    child.resize(x, y, w, h);
+                }
             }
+        });
 
-            if let Some(new_button) = &mut pack.child(new_idx as i32) {
-                new_button.set_frame(FrameType::UpBox);
+        // Zones are drawn in the shape of the monitor, as large as the canvas
+        // allows
+        canvas.resize_callback(move |g, x, y, w, h| {
+            let (display_x, display_y, display_w, display_h) =
+                geometry::fit_aspect_ratio(monitor_size.0, monitor_size.1, x, y, w, h);
+
+            for i in 0..g.children() {
+                if let Some(mut child) = g.child(i) {
+                    child.resize(display_x, display_y, display_w, display_h);
+                }
             }
-        }
-    }
+        });
 
-    fn update_shown_variant_state_selection(&mut self, old_idx: usize, new_idx: usize) {
-        if let Some(old_pack) = &mut self.variant_state_selection.child(old_idx as i32) {
-            old_pack.hide();
-        }
+        Self::handle_splitter(
+            &mut sidebar_splitter,
+            &root,
+            &sidebar,
+            &inspector,
+            false,
+            panel_sizes,
+        );
 
-        if let Some(new_pack) = &mut self.variant_state_selection.child(new_idx as i32) {
-            new_pack.show();
-        }
+        Self::handle_splitter(
+            &mut inspector_splitter,
+            &root,
+            &inspector,
+            &sidebar,
+            true,
+            panel_sizes,
+        );
+
+        return Panels {
+            root,
+            sidebar,
+            centre,
+            states_strip,
+            canvas,
+            inspector,
+        };
     }
 
-    fn update_highlighted_variant_state_button(
-        &mut self,
-        old_idx: (usize, usize),
-        new_idx: (usize, usize),
+    /// Makes `splitter` resize `panel` when dragged, remembering its width in
+    /// `panel_sizes`. The inspector is on the right of its splitter, the
+    /// sidebar on the left.
+    fn handle_splitter(
+        splitter: &mut frame::Frame,
+        root: &group::Flex,
+        panel: &group::Flex,
+        other_panel: &group::Flex,
+        on_right: bool,
+        panel_sizes: &Rc<RefCell<PanelSizes>>,
     ) {
-        if let Some(old_pack) = &mut self.variant_state_selection.child(old_idx.0 as i32) {
+        splitter.set_tooltip("Drag to resize");
+
+        splitter.handle({
+            let mut root = root.clone();
+
+            let panel = panel.clone();
+
+            let other_panel = other_panel.clone();
+
+            let panel_sizes = panel_sizes.clone();
+
+            move |s, ev| match ev {
+                enums::Event::Enter => {
+                    if let Some(mut window) = s.window() {
+                        window.set_cursor(enums::Cursor::WE);
+                    }
+
+                    return true;
+                }
+
+                enums::Event::Leave => {
+                    if let Some(mut window) = s.window() {
+                        window.set_cursor(enums::Cursor::Default);
+                    }
+
+                    return true;
+                }
+
+                enums::Event::Push => true,
+
+                enums::Event::Drag => {
+                    let w = if on_right {
+                        root.x() + root.w() - app::event_x()
+                    } else {
+                        app::event_x() - root.x()
+                    };
+
+                    let max_w =
+                        root.w() - other_panel.w() - 2 * SPLITTER_WIDTH - panels::MIN_CANVAS_WIDTH;
+
+                    let w = w.clamp(
+                        panels::MIN_PANEL_WIDTH,
+                        std::cmp::max(max_w, panels::MIN_PANEL_WIDTH),
+                    );
+
+                    root.fixed(&panel, w);
+
+                    root.recalc();
+
+                    if on_right {
+                        panel_sizes.borrow_mut().inspector_w = w;
+                    } else {
+                        panel_sizes.borrow_mut().sidebar_w = w;
+                    }
+
+                    if let Some(mut window) = root.window() {
+                        window.redraw();
+                    }
+
+                    return true;
+                }
+
+                _ => false,
+            }
+        });
+    }
+}
+
+struct EditorWidgets {
+    editor: LayoutEditor,
+    panels: Panels,
+    variant_list: group::Scroll,
+    variant_state_selection: group::Scroll,
+    variant_state_pack: group::Pack,
+    variant_state_display: group::Group,
+    comparison_strip: ThumbnailStrip,
+    preview_strip: ThumbnailStrip,
+    /// The revision, selected variant and number of extensions the preview
+    /// strip was last filled for.
+    previewed: Option<(usize, usize, usize)>,
+    zone_inspector: ZoneInspector,
+    actions: Actions,
+    gap_actions: GapActions,
+    variant_actions: VariantActions,
+    end_behaviour_actions: EndBehaviourActions,
+    diagnostics: Diagnostics,
+}
+
+impl EditorWidgets {
+    /// Lays the editor out in `bounds` of the window, which should be the
+    /// current group.
+    fn initialize(
+        editor: LayoutEditor,
+        bounds: (i32, i32, i32, i32),
+        panel_sizes: &Rc<RefCell<PanelSizes>>,
+        sender: &app::Sender<Message>,
+    ) -> Self {
+        let layout = editor.layout();
+
+        let monitor_size = (layout.get_monitor_rect().w(), layout.get_monitor_rect().h());
+
+        let mut panels = Panels::initialize(bounds, monitor_size, panel_sizes);
+
+        // The panels are filled in only once they have been laid out, so
+        // that everything in them starts out at its real size
+
+        panels.sidebar.begin();
+
+        let variant_list = Self::create_variant_list(layout, sender);
+
+        let variant_actions = VariantActions::initialize(sender);
+
+        panels.sidebar.end();
+
+        panels
+            .sidebar
+            .fixed(&variant_actions.widgets, VARIANT_ACTIONS_HEIGHT);
+
+        panels.states_strip.begin();
+
+        let (variant_state_pack, variant_state_selection) =
+            Self::create_variant_state_pack(layout, sender);
+
+        panels.states_strip.end();
+
+        panels.canvas.begin();
+
+        let variant_state_display =
+            Self::create_variant_state_display(&panels.canvas, layout, editor.gaps(), sender);
+
+        panels.canvas.end();
+
+        panels.centre.begin();
+
+        let comparison_strip = ThumbnailStrip::initialize(monitor_size, sender);
+
+        let preview_strip = ThumbnailStrip::initialize(monitor_size, sender);
+
+        let mut diagnostics = Diagnostics::initialize(sender);
+
+        panels.centre.end();
+
+        panels
+            .centre
+            .fixed(&comparison_strip.widgets, THUMBNAIL_STRIP_HEIGHT);
+
+        panels
+            .centre
+            .fixed(&preview_strip.widgets, THUMBNAIL_STRIP_HEIGHT);
+
+        panels
+            .centre
+            .fixed(&diagnostics.widgets, DIAGNOSTICS_HEIGHT);
+
+        panels.inspector.begin();
+
+        let zone_inspector = ZoneInspector::initialize(sender);
+
+        let actions = Actions::initialize(sender);
+
+        let end_behaviour_actions = EndBehaviourActions::initialize(layout, sender);
+
+        let mut gap_actions = GapActions::initialize(sender);
+
+        let _inspector_spacer = frame::Frame::default();
+
+        panels.inspector.end();
+
+        panels
+            .inspector
+            .fixed(&zone_inspector.widgets, ZONE_INSPECTOR_HEIGHT);
+
+        panels.inspector.fixed(&actions.widgets, ACTIONS_HEIGHT);
+
+        panels
+            .inspector
+            .fixed(&end_behaviour_actions.widgets, END_BEHAVIOUR_HEIGHT);
+
+        panels
+            .inspector
+            .fixed(&gap_actions.widgets, GAP_ACTIONS_HEIGHT);
+
+        panels.root.recalc();
+
+        diagnostics.update(layout);
+
+        gap_actions.update(editor.gaps(), editor.selected_variant_idx());
+
+        let mut ret = EditorWidgets {
+            editor,
+            panels,
+            variant_list,
+            variant_state_selection,
+            variant_state_pack,
+            variant_state_display,
+            comparison_strip,
+            preview_strip,
+            previewed: None,
+            zone_inspector,
+            actions,
+            gap_actions,
+            variant_actions,
+            end_behaviour_actions,
+            diagnostics,
+        };
+
+        ret.update_highlighted_variant(
+            ret.editor.selected_variant_idx(),
+            ret.editor.selected_variant_idx(),
+        );
+
+        ret.update_shown_variant_state_selection(
+            ret.editor.selected_variant_idx(),
+            ret.editor.selected_variant_idx(),
+        );
+
+        ret.update_highlighted_variant_state_button(
+            (
+                ret.editor.selected_variant_idx(),
+                ret.editor.selected_variant_state_idx(),
+            ),
+            (
+                ret.editor.selected_variant_idx(),
+                ret.editor.selected_variant_state_idx(),
+            ),
+        );
+
+        ret.update_shown_variant_state(
+            (
+                ret.editor.selected_variant_idx(),
+                ret.editor.selected_variant_state_idx(),
+            ),
+            (
+                ret.editor.selected_variant_idx(),
+                ret.editor.selected_variant_state_idx(),
+            ),
+        );
+
+        ret.update_end_zone_idx_choice(sender);
+
+        ret.update_end_behaviour_actions(sender);
+
+        ret.end_behaviour_actions
+            .zone_idx_choice
+            .set_value(ret.editor.selected_variant().get_end_zone_idx() as i32);
+
+        if ret.editor.layout().variants_len() == 1 {
+            ret.variant_actions.delete_button.deactivate();
+        }
+
+        if ret.editor.selected_variant_idx() == ret.editor.layout().default_variant_idx() {
+            ret.variant_actions.set_as_default_button.deactivate();
+        }
+
+        return ret;
+    }
+
+    /// Shows the zones of a variant state as buttons, shrunk by `gaps` to the
+    /// parts of the zones windows would be placed in.
+    fn display_group_from_variant_state(
+        variant_width: f64,
+        variant_height: f64,
+        variant: &Variant,
+        idx: usize,
+        gaps: Gaps,
+        sender: &app::Sender<Message>,
+    ) -> group::Group {
+        let mut group = group::Group::default_fill();
+
+        group.set_frame(FrameType::FlatBox);
+
+        group.set_color(Color::Background2);
+
+        let w = group.w();
+
+        let h = group.h();
+
+        let x_offset = group.x();
+
+        let y_offset = group.y();
+
+        let zones = &variant.get_zones()[idx];
+
+        for (i, zone) in zones.iter().enumerate() {
+            let (x, y, zone_w, zone_h) = scale_zone_window(
+                zone,
+                gaps,
+                variant_width,
+                variant_height,
+                x_offset,
+                y_offset,
+                w,
+                h,
+            );
+
+            let mut b =
+                button::Button::new(x, y, zone_w, zone_h, Some((i + 1).to_string().as_str()));
+
+            // TODO: this frame type doesn't look too great - probably
+            // figure out how to make it look better
+            b.set_frame(FrameType::EmbossedBox);
+
+            b.set_label_size(36);
+
+            b.set_label_color(Color::Black);
+
+            b.set_color(colors::html::Gainsboro);
+
+            b.set_selection_color(colors::html::DodgerBlue);
+
+            b.emit(sender.clone(), Message::SelectedZoneChanged(i));
+        }
+
+        let mut tooltip = frame::Frame::default();
+
+        tooltip.set_frame(FrameType::FlatBox);
+
+        tooltip.set_color(Color::Background2);
+
+        tooltip.set_label_color(Color::Foreground);
+
+        tooltip.hide();
+
+        let mut split_guide = frame::Frame::default();
+
+        split_guide.set_frame(FrameType::FlatBox);
+
+        split_guide.set_color(colors::html::DodgerBlue);
+
+        split_guide.hide();
+
+        group.end();
+
+        let zones = zones.to_owned();
+
+        let sender = sender.clone();
+
+        let mut dragged_edge: Option<(geometry::ZoneEdge, i32)> = None;
+
+        let mut split_direction: Option<Direction> = None;
+
+        group.handle(move |g, ev| {
+            let scale_x = variant_width / g.w() as f64;
+
+            let scale_y = variant_height / g.h() as f64;
+
+            let mouse_x = ((app::event_x() - g.x()) as f64 * scale_x).round() as i32;
+
+            let mouse_y = ((app::event_y() - g.y()) as f64 * scale_y).round() as i32;
+
+            let grab_distance = (EDGE_GRAB_DISTANCE as f64 * scale_x.max(scale_y)).round() as i32;
+
+            match ev {
+                enums::Event::Move => {
+                    if app::is_event_ctrl() {
+                        return Self::update_split_guide(
+                            g,
+                            &mut split_guide,
+                            &zones,
+                            (mouse_x, mouse_y),
+                            split_direction,
+                        );
+                    }
+
+                    split_direction = None;
+
+                    split_guide.hide();
+
+                    let cursor = match geometry::edge_near(&zones, mouse_x, mouse_y, grab_distance)
+                    {
+                        Some(edge) => match edge.direction {
+                            Direction::Horizontal => enums::Cursor::WE,
+                            Direction::Vertical => enums::Cursor::NS,
+                        },
+                        None => enums::Cursor::Default,
+                    };
+
+                    if let Some(mut window) = g.window() {
+                        window.set_cursor(cursor);
+                    }
+
+                    g.redraw();
+
+                    return false;
+                }
+
+                enums::Event::KeyDown | enums::Event::Shortcut
+                    if app::is_event_ctrl() && split_guide.visible() =>
+                {
+                    split_direction = match app::event_key() {
+                        k if k == enums::Key::from_char('h') => Some(Direction::Horizontal),
+                        k if k == enums::Key::from_char('v') => Some(Direction::Vertical),
+                        _ => return false,
+                    };
+
+                    return Self::update_split_guide(
+                        g,
+                        &mut split_guide,
+                        &zones,
+                        (mouse_x, mouse_y),
+                        split_direction,
+                    );
+                }
+
+                enums::Event::KeyUp if !app::is_event_ctrl() && split_guide.visible() => {
+                    split_direction = None;
+
+                    split_guide.hide();
+
+                    g.redraw();
+
+                    return false;
+                }
+
+                enums::Event::Leave => {
+                    split_direction = None;
+
+                    split_guide.hide();
+
+                    if let Some(mut window) = g.window() {
+                        window.set_cursor(enums::Cursor::Default);
+                    }
+
+                    g.redraw();
+
+                    return false;
+                }
+
+                enums::Event::Push => {
+                    if app::event_mouse_button() != app::MouseButton::Left {
+                        return false;
+                    }
+
+                    if app::is_event_ctrl() {
+                        let zone_idx = match geometry::zone_at(&zones, mouse_x, mouse_y) {
+                            Some(idx) => idx,
+                            None => return false,
+                        };
+
+                        let direction = split_direction.unwrap_or(
+                            geometry::nearer_split_direction(&zones[zone_idx], mouse_x, mouse_y),
+                        );
+
+                        let position = match direction {
+                            Direction::Horizontal => mouse_x,
+                            Direction::Vertical => mouse_y,
+                        };
+
+                        split_guide.hide();
+
+                        sender.send(Message::SplitZoneAt(zone_idx, direction, position));
+
+                        return true;
+                    }
+
+                    match geometry::edge_near(&zones, mouse_x, mouse_y, grab_distance) {
+                        Some(edge) => {
+                            let position = edge.position;
+
+                            dragged_edge = Some((edge, position));
+
+                            return true;
+                        }
+                        None => return false,
+                    }
+                }
+
+                enums::Event::Drag => {
+                    let (edge, position) = match &mut dragged_edge {
+                        Some(val) => val,
+                        None => return false,
+                    };
+
+                    let (min, max) = geometry::edge_bounds(&zones, edge);
+
+                    if min > max {
+                        return true;
+                    }
+
+                    let (axis, mouse_position, extent, scale) = match edge.direction {
+                        Direction::Horizontal => ("x", mouse_x, variant_width, scale_x),
+                        Direction::Vertical => ("y", mouse_y, variant_height, scale_y),
+                    };
+
+                    let snapped_position = if app::is_event_alt() {
+                        mouse_position
+                    } else {
+                        geometry::snap_edge(
+                            &zones,
+                            edge,
+                            mouse_position,
+                            extent as i32,
+                            (SNAP_DISTANCE as f64 * scale).round() as i32,
+                        )
+                    };
+
+                    *position = snapped_position.clamp(min, max);
+
+                    let mut moved_zones = zones.to_owned();
+
+                    geometry::move_edge(&mut moved_zones, edge, *position);
+
+                    for &i in &edge.zone_idxs {
+                        let (x, y, w, h) = scale_zone_window(
+                            &moved_zones[i],
+                            gaps,
+                            variant_width,
+                            variant_height,
+                            g.x(),
+                            g.y(),
+                            g.w(),
+                            g.h(),
+                        );
+
+                        if let Some(mut b) = g.child(i as i32) {
+                            b.resize(x, y, w, h);
+                        }
+                    }
+
+                    tooltip.set_label(
+                        format!(
+                            "{axis}: {position} ({:.1}%)",
+                            *position as f64 * 100.0 / extent
+                        )
+                        .as_str(),
+                    );
+
+                    let (label_w, label_h) = tooltip.measure_label();
+
+                    tooltip.resize(
+                        app::event_x() + 12,
+                        app::event_y() + 12,
+                        label_w + 8,
+                        label_h + 4,
+                    );
+
+                    tooltip.show();
+
+                    g.redraw();
+
+                    return true;
+                }
+
+                enums::Event::Release => {
+                    let (edge, position) = match dragged_edge.take() {
+                        Some(val) => val,
+                        None => return false,
+                    };
+
+                    tooltip.hide();
+
+                    // The display is rebuilt from the layout once the message is
+                    // handled, so the zones are only moved back here
+                    for &i in &edge.zone_idxs {
+                        let (x, y, w, h) = scale_zone_window(
+                            &zones[i],
+                            gaps,
+                            variant_width,
+                            variant_height,
+                            g.x(),
+                            g.y(),
+                            g.w(),
+                            g.h(),
+                        );
+
+                        if let Some(mut b) = g.child(i as i32) {
+                            b.resize(x, y, w, h);
+                        }
+                    }
+
+                    g.redraw();
+
+                    if position != edge.position {
+                        sender.send(Message::MoveZoneEdge(edge, position));
+                    }
+
+                    return true;
+                }
+
+                _ => return false,
+            }
+        });
+
+        return group;
+    }
+
+    fn update_split_guide(
+        display: &mut group::Group,
+        split_guide: &mut frame::Frame,
+        zones: &[Zone],
+        mouse_position: (i32, i32),
+        split_direction: Option<Direction>,
+    ) -> bool {
+        let (mouse_x, mouse_y) = mouse_position;
+
+        let zone_idx = match geometry::zone_at(zones, mouse_x, mouse_y) {
+            Some(idx) => idx,
+            None => {
+                split_guide.hide();
+
+                return false;
+            }
+        };
+
+        let zone_button = match display.child(zone_idx as i32) {
+            Some(val) => val,
+            None => return false,
+        };
+
+        let direction = split_direction.unwrap_or(geometry::nearer_split_direction(
+            &zones[zone_idx],
+            mouse_x,
+            mouse_y,
+        ));
+
+        match direction {
+            Direction::Horizontal => {
+                split_guide.resize(app::event_x(), zone_button.y(), 2, zone_button.h());
+            }
+            Direction::Vertical => {
+                split_guide.resize(zone_button.x(), app::event_y(), zone_button.w(), 2);
+            }
+        }
+
+        split_guide.show();
+
+        display.redraw();
+
+        return true;
+    }
+
+    fn create_variant_list(layout: &Layout, sender: &app::Sender<Message>) -> group::Scroll {
+        let mut scroll = group::Scroll::default_fill().with_type(ScrollType::Vertical);
+
+        scroll.set_color(Color::Background2);
+
+        scroll.resize_callback(|s, _, _, w, _| {
+            if let Some(p) = &mut s.child(0) {
+                p.set_size(w, p.h());
+            }
+        });
+
+        let pack = group::Pack::default_fill().with_type(PackType::Vertical);
+
+        for i in 0..layout.variants_len() {
+            let mut b = button::Button::default().with_size(0, 20);
+
+            b.set_label_size(16);
+
+            b.set_color(colors::html::DodgerBlue);
+
+            b.set_frame(FrameType::NoBox);
+
+            if i == layout.default_variant_idx() {
+                b.set_label(format!("{i} (default)").as_str());
+            } else {
+                b.set_label(i.to_string().as_str());
+            }
+
+            b.emit(sender.clone(), Message::SelectedVariantChanged(i));
+        }
+
+        pack.end();
+
+        scroll.end();
+
+        return scroll;
+    }
+
+    fn create_variant_state_selection(
+        layout: &Layout,
+        sender: &app::Sender<Message>,
+    ) -> group::Scroll {
+        let mut scroll = group::Scroll::default_fill().with_type(ScrollType::Horizontal);
+
+        scroll.set_size(scroll.w(), 72);
+
+        // Any styling of the scrollbar should probably happen here
+
+        scroll.set_color(Color::Background2);
+
+        for variant in layout.get_variants() {
+            Self::create_state_selection_pack(&scroll, variant, sender);
+        }
+
+        scroll.end();
+
+        return scroll;
+    }
+
+    fn create_state_selection_pack(
+        scroll: &group::Scroll,
+        variant: &Variant,
+        sender: &app::Sender<Message>,
+    ) -> group::Pack {
+        let mut pack = group::Pack::default()
+            .with_size(scroll.w() - 8, 64)
+            .with_type(PackType::Horizontal)
+            .center_of_parent();
+
+        pack.set_spacing(4);
+
+        for j in 0..variant.manual_zones_until() {
+            let mut b = button::Button::default()
+                .with_size(64, 0)
+                .with_label((j + 1).to_string().as_str());
+
+            b.set_color(Color::Background2);
+
+            b.set_selection_color(Color::Background);
+
+            b.emit(sender.clone(), Message::SelectedVariantStateChanged(j));
+        }
+
+        pack.end();
+
+        pack.hide();
+
+        return pack;
+    }
+
+    fn create_variant_state_buttons(sender: &app::Sender<Message>) -> group::Flex {
+        let mut flex = group::Flex::default_fill().row();
+
+        let flex_w = flex.w();
+
+        WidgetExt::set_size(&mut flex, flex_w, 32);
+
+        flex.set_pad(4);
+
+        let mut new_button = button::Button::default().with_label("New");
+
+        let mut clone_button = button::Button::default().with_label("Clone");
+
+        let mut delete_button = button::Button::default().with_label("Delete");
+
+        let _frame = frame::Frame::default();
+
+        let mut left_button = button::Button::default().with_label("@<");
+
+        let mut right_button = button::Button::default().with_label("@>");
+
+        flex.fixed(&new_button, 64);
+
+        flex.fixed(&clone_button, 80);
+
+        flex.fixed(&delete_button, 64);
+
+        flex.fixed(&left_button, 32);
+
+        flex.fixed(&right_button, 32);
+
+        flex.end();
+
+        new_button.emit(sender.clone(), Message::NewVariantState);
+
+        clone_button.emit(sender.clone(), Message::CloneVariantState);
+
+        delete_button.emit(sender.clone(), Message::DeleteVariantState);
+
+        left_button.emit(
+            sender.clone(),
+            Message::SwapVariantState(SwapDirection::Previous),
+        );
+
+        right_button.emit(
+            sender.clone(),
+            Message::SwapVariantState(SwapDirection::Next),
+        );
+
+        return flex;
+    }
+
+    /// The variant state selection above the buttons that add, remove and
+    /// move variant states, filling the current group.
+    fn create_variant_state_pack(
+        layout: &Layout,
+        sender: &app::Sender<Message>,
+    ) -> (group::Pack, group::Scroll) {
+        let mut variant_state_pack = group::Pack::default_fill();
+
+        variant_state_pack.set_spacing(4);
+
+        let variant_state_selection = Self::create_variant_state_selection(layout, sender);
+
+        let _variant_state_buttons = Self::create_variant_state_buttons(sender);
+
+        variant_state_pack.end();
+
+        return (variant_state_pack, variant_state_selection);
+    }
+
+    fn create_variant_state_display(
+        canvas: &group::Group,
+        layout: &Layout,
+        gaps: &GapSettings,
+        sender: &app::Sender<Message>,
+    ) -> group::Group {
+        let (x, y, w, h) = geometry::fit_aspect_ratio(
+            layout.get_monitor_rect().w(),
+            layout.get_monitor_rect().h(),
+            canvas.x(),
+            canvas.y(),
+            canvas.w(),
+            canvas.h(),
+        );
+
+        let group = group::Group::new(x, y, w, h, None);
+
+        for (variant_idx, variant) in layout.get_variants().iter().enumerate() {
+            let mut display = group::Group::default_fill();
+
+            for i in 0..variant.manual_zones_until() {
+                let mut g = Self::display_group_from_variant_state(
+                    layout.get_monitor_rect().w() as f64,
+                    layout.get_monitor_rect().h() as f64,
+                    variant,
+                    i,
+                    gaps.for_variant(variant_idx),
+                    sender,
+                );
+
+                g.hide();
+            }
+
+            display.end();
+
+            display.hide();
+        }
+
+        group.end();
+
+        return group;
+    }
+
+    fn update_highlighted_variant(&mut self, old_idx: usize, new_idx: usize) {
+        if let Some(p) = &self.variant_list.child(0) {
+            let pack = group::Pack::from_dyn_widget(p).unwrap();
+
+            if let Some(old_button) = &mut pack.child(old_idx as i32) {
+                old_button.set_frame(FrameType::NoBox);
+            }
+
+            if let Some(new_button) = &mut pack.child(new_idx as i32) {
+                new_button.set_frame(FrameType::UpBox);
+            }
+        }
+    }
+
+    fn update_shown_variant_state_selection(&mut self, old_idx: usize, new_idx: usize) {
+        if let Some(old_pack) = &mut self.variant_state_selection.child(old_idx as i32) {
+            old_pack.hide();
+        }
+
+        if let Some(new_pack) = &mut self.variant_state_selection.child(new_idx as i32) {
+            new_pack.show();
+        }
+    }
+
+    fn update_highlighted_variant_state_button(
+        &mut self,
+        old_idx: (usize, usize),
+        new_idx: (usize, usize),
+    ) {
+        if let Some(old_pack) = &mut self.variant_state_selection.child(old_idx.0 as i32) {
             if let Some(old_button) = &mut group::Pack::from_dyn_widget(old_pack)
                 .unwrap()
-                .child(old_idx.1 as i32)
-            {
-                old_button.set_color(Color::Background2);
+                .child(old_idx.1 as i32)
+            {
+                old_button.set_color(Color::Background2);
+            }
+
+            old_pack.hide();
+        }
+
+        if let Some(new_pack) = &mut self.variant_state_selection.child(new_idx.0 as i32) {
+            if let Some(new_button) = &mut group::Pack::from_dyn_widget(new_pack)
+                .unwrap()
+                .child(new_idx.1 as i32)
+            {
+                new_button.set_color(colors::html::DimGray);
+            }
+
+            new_pack.show();
+        }
+    }
+
+    fn update_shown_variant_state(&mut self, old_idx: (usize, usize), new_idx: (usize, usize)) {
+        if let Some(old_variant) = &mut self.variant_state_display.child(old_idx.0 as i32) {
+            if let Some(old_group) = &mut group::Group::from_dyn_widget(old_variant)
+                .unwrap()
+                .child(old_idx.1 as i32)
+            {
+                old_group.hide();
+            }
+
+            old_variant.hide();
+        }
+
+        if let Some(new_variant) = &mut self.variant_state_display.child(new_idx.0 as i32) {
+            if let Some(new_group) = &mut group::Group::from_dyn_widget(new_variant)
+                .unwrap()
+                .child(new_idx.1 as i32)
+            {
+                new_group.show();
+            }
+
+            new_variant.show();
+        }
+    }
+
+    fn highlight_selected_zone(&mut self, idx: usize) {
+        let selected_variant_idx = self.editor.selected_variant_idx();
+
+        let selected_variant_state_idx = self.editor.selected_variant_state_idx();
+
+        if let Some(variant) = group::Group::from_dyn_widget(
+            &self
+                .variant_state_display
+                .child(selected_variant_idx as i32)
+                .unwrap(),
+        ) {
+            if let Some(group) = group::Group::from_dyn_widget(
+                &variant.child(selected_variant_state_idx as i32).unwrap(),
+            ) {
+                if let Some(zone_button) =
+                    &mut button::Button::from_dyn_widget(&group.child(idx as i32).unwrap())
+                {
+                    zone_button.set_color(colors::html::DodgerBlue);
+
+                    zone_button.redraw();
+                }
+            }
+        }
+    }
+
+    fn dehighlight_zone(&mut self, variant_idx: usize, variant_state_idx: usize, zone_idx: usize) {
+        if let Some(variant) = group::Group::from_dyn_widget(
+            &self
+                .variant_state_display
+                .child(variant_idx as i32)
+                .unwrap(),
+        ) {
+            if let Some(group) =
+                group::Group::from_dyn_widget(&variant.child(variant_state_idx as i32).unwrap())
+            {
+                if let Some(zone_button) =
+                    &mut button::Button::from_dyn_widget(&group.child(zone_idx as i32).unwrap())
+                {
+                    zone_button.set_color(colors::html::Gainsboro);
+
+                    zone_button.redraw();
+                }
+            }
+        }
+    }
+
+    fn reset_zone_selection(&mut self) {
+        let variant_idx = self.editor.selected_variant_idx();
+
+        let variant_state_idx = self.editor.selected_variant_state_idx();
+
+        if let Some(zone_idx) = self.editor.selected_zone_idx1() {
+            self.dehighlight_zone(variant_idx, variant_state_idx, zone_idx);
+
+            if let Some(zone_idx) = self.editor.selected_zone_idx2() {
+                self.dehighlight_zone(variant_idx, variant_state_idx, zone_idx);
+            }
+        }
+
+        self.editor.clear_zone_selection();
+
+        self.update_zone_actions();
+    }
+
+    fn update_zone_actions(&mut self) {
+        self.update_zone_inspector();
+
+        if let None = self.editor.selected_zone_idx1() {
+            self.disable_split();
+
+            self.actions.merge_button.deactivate();
+
+            self.actions.swap_button.deactivate();
+
+            return;
+        }
+
+        if let None = self.editor.selected_zone_idx2() {
+            self.actions.split_button.set_label("Split");
+
+            self.actions.split_button.activate();
+
+            self.actions.split_evenly_button.activate();
+
+            self.update_split_bounds();
+
+            self.actions.merge_button.deactivate();
+
+            self.actions.swap_button.deactivate();
+
+            return;
+        }
+
+        self.actions.swap_button.activate();
+
+        self.actions.split_evenly_button.deactivate();
+
+        if self.editor.can_merge_selected_zones() {
+            self.actions.split_button.set_label("Merge and split");
+
+            self.actions.split_button.activate();
+
+            self.update_split_bounds();
+
+            self.actions.merge_button.activate();
+        } else {
+            self.disable_split();
+
+            self.actions.merge_button.deactivate();
+        }
+    }
+
+    fn update_zone_inspector(&mut self) {
+        let monitor_size = (
+            self.editor.layout().get_monitor_rect().w(),
+            self.editor.layout().get_monitor_rect().h(),
+        );
+
+        self.zone_inspector.update(
+            self.editor.selected_zones(),
+            self.editor.selected_zone_idx1(),
+            monitor_size,
+        );
+    }
+
+    /// Redraws the selected variant state after the zone at `zone_idx` was
+    /// edited from the zone inspector, keeping it selected. If the edit was
+    /// rejected, the inspector goes back to showing the zone as it is.
+    fn show_edited_zone(&mut self, zone_idx: usize, edited: bool, sender: &app::Sender<Message>) {
+        if !edited {
+            self.update_zone_inspector();
+
+            return;
+        }
+
+        self.update_variant_state_display(
+            self.editor.selected_variant_idx(),
+            self.editor.selected_variant_state_idx(),
+            sender,
+        );
+
+        if let Err(_) = self.editor.apply(Action::SelectZone(zone_idx)) {
+            return;
+        }
+
+        self.highlight_selected_zone(zone_idx);
+
+        self.update_zone_actions();
+    }
+
+    fn new_variant_state(&mut self, sender: &app::Sender<Message>) {
+        let variant_idx = self.editor.selected_variant_idx();
+
+        let variant_state_pack = &mut group::Pack::from_dyn_widget(
+            &self
+                .variant_state_selection
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        variant_state_pack.begin();
+
+        let mut b = button::Button::default()
+            .with_size(64, 0)
+            .with_label(variant_state_pack.children().to_string().as_str());
+
+        b.set_color(Color::Background2);
+
+        b.set_selection_color(Color::Background);
+
+        b.emit(
+            sender.clone(),
+            Message::SelectedVariantStateChanged(variant_state_pack.children() as usize - 1),
+        );
+
+        variant_state_pack.end();
+
+        let variant_state_display_group = &mut group::Group::from_dyn_widget(
+            &self
+                .variant_state_display
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        variant_state_display_group.begin();
+
+        let _g = Self::display_group_from_variant_state(
+            self.editor.layout().get_monitor_rect().w() as f64,
+            self.editor.layout().get_monitor_rect().h() as f64,
+            &self.editor.layout().get_variants()[variant_idx],
+            variant_state_pack.children() as usize - 1,
+            self.editor.gaps().for_variant(variant_idx),
+            sender,
+        );
+
+        variant_state_display_group.end();
+
+        sender.send(Message::SelectedVariantStateChanged(
+            variant_state_pack.children() as usize - 1,
+        ));
+
+        self.update_from_zones_controls(sender);
+    }
+
+    fn delete_variant_state(&mut self, variant_state_idx: usize, sender: &app::Sender<Message>) {
+        let variant_idx = self.editor.selected_variant_idx();
+
+        let variant_state_pack = &mut group::Pack::from_dyn_widget(
+            &self
+                .variant_state_selection
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        WidgetBase::delete(variant_state_pack.child(variant_state_idx as i32).unwrap());
+
+        let variant_state_display_group = &mut group::Group::from_dyn_widget(
+            &self
+                .variant_state_display
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        WidgetBase::delete(
+            variant_state_display_group
+                .child(variant_state_idx as i32)
+                .unwrap(),
+        );
+
+        self.decrement_all_variant_state_buttons_after(variant_idx, variant_state_idx, sender);
+
+        self.update_from_zones_controls(sender);
+    }
+
+    fn swap_variant_states(&mut self, idx: usize, swap_with: usize, sender: &app::Sender<Message>) {
+        let variant_idx = self.editor.selected_variant_idx();
+
+        let first_idx = std::cmp::min(idx, swap_with);
+        let second_idx = std::cmp::max(idx, swap_with);
+
+        let variant_state_pack = &mut group::Pack::from_dyn_widget(
+            &self
+                .variant_state_selection
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        let first_button = &mut button::Button::from_dyn_widget(
+            &variant_state_pack.child(first_idx as i32).unwrap(),
+        )
+        .unwrap();
+
+        let second_button = &mut button::Button::from_dyn_widget(
+            &variant_state_pack.child(second_idx as i32).unwrap(),
+        )
+        .unwrap();
+
+        variant_state_pack.remove_by_index(second_idx as i32);
+
+        variant_state_pack.remove_by_index(first_idx as i32);
+
+        first_button.set_label((second_idx + 1).to_string().as_str());
+
+        first_button.emit(
+            sender.clone(),
+            Message::SelectedVariantStateChanged(second_idx),
+        );
+
+        second_button.set_label((first_idx + 1).to_string().as_str());
+
+        second_button.emit(
+            sender.clone(),
+            Message::SelectedVariantStateChanged(first_idx),
+        );
+
+        variant_state_pack.insert(second_button, first_idx as i32);
+
+        variant_state_pack.insert(first_button, second_idx as i32);
+
+        let variant_state_display_group = &mut group::Group::from_dyn_widget(
+            &self
+                .variant_state_display
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        let first_variant_state_display = &group::Group::from_dyn_widget(
+            &variant_state_display_group.child(first_idx as i32).unwrap(),
+        )
+        .unwrap();
+
+        let second_variant_state_display = &group::Group::from_dyn_widget(
+            &variant_state_display_group
+                .child(second_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        variant_state_display_group.remove_by_index(second_idx as i32);
+
+        variant_state_display_group.remove_by_index(first_idx as i32);
+
+        variant_state_display_group.insert(second_variant_state_display, first_idx as i32);
+
+        variant_state_display_group.insert(first_variant_state_display, second_idx as i32);
+
+        sender.send(Message::SelectedVariantStateChanged(swap_with));
+
+        self.update_from_zones_controls(sender);
+    }
+
+    fn update_split_bounds(&mut self) {
+        let max = match self.editor.split_bound_max(self.actions.selected_direction) {
+            Some(val) => val,
+            None => return,
+        };
+
+        self.actions.split_bound_max = Some(max);
+
+        self.actions
+            .split_bounds_text
+            .set_label(format!("0 - {max}").as_str());
+    }
+
+    fn disable_split(&mut self) {
+        self.actions.split_bound_max = None;
+
+        self.actions.split_button.set_label("Split");
+
+        self.actions.split_button.deactivate();
+
+        self.actions.split_evenly_button.deactivate();
+
+        self.actions.split_bounds_text.set_label("");
+    }
+
+    fn update_variant_state_display(
+        &mut self,
+        variant_idx: usize,
+        variant_state_idx: usize,
+        sender: &app::Sender<Message>,
+    ) {
+        let w = self.editor.layout().get_monitor_rect().w() as f64;
+
+        let h = self.editor.layout().get_monitor_rect().h() as f64;
+
+        let variant = &self.editor.layout().get_variants()[variant_idx];
+
+        let variant_display_group = &mut group::Group::from_dyn_widget(
+            &self
+                .variant_state_display
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        variant_display_group.begin();
+
+        let mut new_display = EditorWidgets::display_group_from_variant_state(
+            w,
+            h,
+            variant,
+            variant_state_idx,
+            self.editor.gaps().for_variant(variant_idx),
+            sender,
+        );
+
+        variant_display_group.end();
+
+        variant_display_group.remove(&new_display);
+
+        if let Some(old_display) = variant_display_group.child(variant_state_idx as i32) {
+            WidgetBase::delete(old_display);
+        } else {
+            new_display.hide();
+        }
+
+        variant_display_group.insert(&new_display, variant_state_idx as i32);
+
+        self.reset_zone_selection();
+
+        self.update_from_zones_controls(sender);
+    }
+
+    fn update_diagnostics(&mut self) {
+        self.diagnostics.update(self.editor.layout());
+    }
+
+    fn update_end_zone_idx_choice(&mut self, sender: &app::Sender<Message>) {
+        let variant = self.editor.selected_variant();
+
+        let zones = &variant.get_zones()[variant.manual_zones_until() - 1];
+
+        self.end_behaviour_actions.zone_idx_choice.clear();
+
+        for idx in 0..zones.len() {
+            self.end_behaviour_actions.zone_idx_choice.add_emit(
+                (idx + 1).to_string().as_str(),
+                Shortcut::None,
+                MenuFlag::Normal,
+                sender.clone(),
+                Message::EndZoneIdxChanged(idx),
+            );
+        }
+
+        self.end_behaviour_actions
+            .zone_idx_choice
+            .set_value(variant.get_end_zone_idx() as i32);
+
+        self.update_from_zones_controls(sender);
+    }
+
+    fn update_end_behaviour_actions(&mut self, sender: &app::Sender<Message>) {
+        let variant = self.editor.selected_variant();
+
+        let end_behaviour_actions = &mut self.end_behaviour_actions;
+
+        match variant.get_end_tiling_behaviour() {
+            EndTilingBehaviour::Directional { direction, .. } => {
+                end_behaviour_actions.directional_radio_button.toggle(true);
+
+                end_behaviour_actions.repeating_radio_button.toggle(false);
+
+                let directional = &mut end_behaviour_actions.directional;
+
+                directional
+                    .horizontal_radio_button
+                    .toggle(matches!(direction, Direction::Horizontal));
+
+                directional
+                    .vertical_radio_button
+                    .toggle(matches!(direction, Direction::Vertical));
+
+                directional.widgets.show();
+
+                end_behaviour_actions.repeating.widgets.hide();
+            }
+            EndTilingBehaviour::Repeating { .. } => {
+                end_behaviour_actions.directional_radio_button.toggle(false);
+
+                end_behaviour_actions.repeating_radio_button.toggle(true);
+
+                end_behaviour_actions.directional.widgets.hide();
+
+                end_behaviour_actions.repeating.widgets.show();
+            }
+        }
+
+        end_behaviour_actions
+            .repeating
+            .update_rows(&repeating::repeating_splits(variant), sender);
+
+        self.update_from_zones_controls(sender);
+    }
+
+    fn set_repeating_splits(
+        &mut self,
+        splits: &[RepeatingSplitSpec],
+        update_rows: bool,
+        sender: &app::Sender<Message>,
+    ) {
+        let _ = self
+            .editor
+            .apply(Action::SetRepeatingSplits(splits.to_vec()));
+
+        if update_rows {
+            self.end_behaviour_actions
+                .repeating
+                .update_rows(splits, sender);
+        }
+    }
+
+    fn update_from_zones_controls(&mut self, sender: &app::Sender<Message>) {
+        self.end_behaviour_actions
+            .directional
+            .update_from_zones_controls(
+                self.editor.layout(),
+                self.editor.gaps(),
+                self.editor.selected_variant_idx(),
+                sender,
+            );
+    }
+
+    fn set_from_zones(&mut self, variant_state_idx: Option<usize>, sender: &app::Sender<Message>) {
+        // The controls are updated either way, to undo the choice if it was
+        // rejected
+        let _ = self.editor.apply(Action::SetFromZones(variant_state_idx));
+
+        self.update_from_zones_controls(sender);
+    }
+
+    /// Shows one more variant state extending the selected variant would
+    /// add. The previewed variant states are computed from a copy of the
+    /// variant, so the layout itself is never touched.
+    fn preview_extend(&mut self) {
+        self.end_behaviour_actions.preview_count += 1;
+
+        self.end_behaviour_actions.cancel_preview_button.activate();
+    }
+
+    fn cancel_extend_preview(&mut self) {
+        self.end_behaviour_actions.preview_count = 0;
+
+        self.end_behaviour_actions
+            .cancel_preview_button
+            .deactivate();
+    }
+
+    fn add_variant_widgets(&mut self, sender: &app::Sender<Message>) {
+        let mut variants_pack =
+            group::Pack::from_dyn_widget(&self.variant_list.child(0).unwrap()).unwrap();
+
+        let idx = variants_pack.children() as usize;
+
+        let variant = &self.editor.layout().get_variants()[idx];
+
+        let mut new_variant_button = button::Button::default().with_size(0, 20);
+
+        new_variant_button.set_label_size(16);
+
+        new_variant_button.set_color(colors::html::DodgerBlue);
+
+        new_variant_button.set_frame(FrameType::NoBox);
+
+        new_variant_button.set_label(idx.to_string().as_str());
+
+        new_variant_button.emit(sender.clone(), Message::SelectedVariantChanged(idx));
+
+        variants_pack.add(&new_variant_button);
+
+        let variant_state_selection = &mut self.variant_state_selection;
+
+        variant_state_selection.begin();
+
+        variant_state_selection.add(&EditorWidgets::create_state_selection_pack(
+            variant_state_selection,
+            variant,
+            sender,
+        ));
+
+        variant_state_selection.end();
+
+        self.variant_state_display.begin();
+
+        let mut new_variant_display_group = group::Group::default_fill();
+
+        self.variant_state_display.end();
+
+        let manual_zones_until = variant.manual_zones_until();
+
+        for i in 0..manual_zones_until {
+            self.update_variant_state_display(idx, i, sender);
+        }
+
+        new_variant_display_group.hide();
+    }
+
+    fn update_default_variant_label(&mut self, old_idx: usize, new_idx: usize) {
+        let variants_pack =
+            &mut group::Pack::from_dyn_widget(&self.variant_list.child(0).unwrap()).unwrap();
+
+        if let Some(button) = &mut variants_pack.child(old_idx as i32) {
+            button.set_label(old_idx.to_string().as_str());
+        }
+
+        variants_pack
+            .child(new_idx as i32)
+            .unwrap()
+            .set_label(format!("{new_idx} (default)").as_str());
+    }
+
+    fn decrement_all_variant_state_buttons_after(
+        &mut self,
+        variant_idx: usize,
+        variant_state_idx: usize,
+        sender: &app::Sender<Message>,
+    ) {
+        let variant_state_pack = &mut group::Pack::from_dyn_widget(
+            &self
+                .variant_state_selection
+                .child(variant_idx as i32)
+                .unwrap(),
+        )
+        .unwrap();
+
+        for i in variant_state_idx as i32..variant_state_pack.children() {
+            let b = &mut button::Button::from_dyn_widget(&variant_state_pack.child(i).unwrap())
+                .unwrap();
+
+            b.set_label((i + 1).to_string().as_str());
+
+            b.emit(
+                sender.clone(),
+                Message::SelectedVariantStateChanged(i as usize),
+            );
+        }
+    }
+
+    /// Shows the comparison and extend preview strips if they are in use,
+    /// and brings them up to date with the layout and the selection.
+    fn update_thumbnail_strips(&mut self, comparison: Option<Comparison>) {
+        let mut relayout = match comparison {
+            Some(val) => {
+                let (title, thumbnails) = self.compared_variant_states(val);
+
+                self.comparison_strip.update(&title, thumbnails);
+
+                self.comparison_strip.set_visible(true)
+            }
+            None => self.comparison_strip.set_visible(false),
+        };
+
+        let preview_count = self.end_behaviour_actions.preview_count;
+
+        relayout |= if preview_count > 0 {
+            let variant_idx = self.editor.selected_variant_idx();
+
+            let previewed = Some((self.editor.revision(), variant_idx, preview_count));
+
+            // Extending is only worth redoing if what it extends has changed
+            if self.previewed != previewed {
+                let title = format!("Extending variant {variant_idx} (preview only)");
+
+                let thumbnails = self.extended_variant_states(preview_count);
+
+                self.preview_strip.update(&title, thumbnails);
+
+                self.previewed = previewed;
+            }
+
+            self.preview_strip.set_visible(true)
+        } else {
+            self.previewed = None;
+
+            self.preview_strip.set_visible(false)
+        };
+
+        if relayout {
+            self.panels.centre.recalc();
+
+            self.panels.centre.redraw();
+        }
+    }
+
+    fn compared_variant_states(&self, comparison: Comparison) -> (String, Vec<StripThumbnail>) {
+        let layout = self.editor.layout();
+
+        let selected_variant_idx = self.editor.selected_variant_idx();
+
+        let selected_variant_state_idx = self.editor.selected_variant_state_idx();
+
+        let thumbnails = comparison
+            .thumbnails(layout, selected_variant_idx, selected_variant_state_idx)
+            .into_iter()
+            .map(|thumbnail| StripThumbnail {
+                label: format!(
+                    "{}: {}",
+                    thumbnail.variant_idx,
+                    thumbnail.variant_state_idx + 1
+                ),
+                zones: layout.get_variants()[thumbnail.variant_idx].get_zones()
+                    [thumbnail.variant_state_idx]
+                    .to_owned(),
+                gaps: self.editor.gaps().for_variant(thumbnail.variant_idx),
+                selected: thumbnail.selected,
+                selects: Some((thumbnail.variant_idx, thumbnail.variant_state_idx)),
+            })
+            .collect();
+
+        let title = match comparison {
+            Comparison::VariantStates => {
+                format!("Every variant state of variant {selected_variant_idx}")
+            }
+            Comparison::Variants => format!(
+                "Variant state {} of every variant",
+                selected_variant_state_idx + 1
+            ),
+        };
+
+        return (title, thumbnails);
+    }
+
+    /// The variant states extending the selected variant `count` times adds
+    /// after its manual variant states, which cannot be selected.
+    fn extended_variant_states(&self, count: usize) -> Vec<StripThumbnail> {
+        let variant_idx = self.editor.selected_variant_idx();
+
+        let manual_zones_until = self.editor.selected_variant().manual_zones_until();
+
+        let gaps = self.editor.gaps().for_variant(variant_idx);
+
+        return self
+            .editor
+            .preview_extend(count)
+            .get_zones()
+            .iter()
+            .enumerate()
+            .skip(manual_zones_until)
+            .map(|(idx, zones)| StripThumbnail {
+                label: format!("{} ({} zones)", idx + 1, zones.len()),
+                zones: zones.to_owned(),
+                gaps,
+                selected: false,
+                selects: None,
+            })
+            .collect();
+    }
+
+    /// Deletes the widgets, handing back the editor.
+    fn delete(self) -> LayoutEditor {
+        // Everything is inside the panels
+        WidgetBase::delete(self.panels.root);
+
+        return self.editor;
+    }
+}
+
+pub struct LayoutEditorGUI {
+    app: app::App,
+    window: window::Window,
+    sender: app::Sender<Message>,
+    receiver: app::Receiver<Message>,
+    editor_widgets: Option<EditorWidgets>,
+    file_path: Option<PathBuf>,
+    readonly: bool,
+    help_overlay: group::Group,
+    title: String,
+    panel_sizes: Rc<RefCell<PanelSizes>>,
+    comparison: Option<Comparison>,
+}
+
+impl LayoutEditorGUI {
+    pub fn create() -> Self {
+        initialize_colours();
+
+        let app = app::App::default();
+
+        let widget_scheme = WidgetScheme::new(SchemeType::Fluent);
+
+        widget_scheme.apply();
+
+        let (sender, receiver) = app::channel();
+
+        let saved_panel_sizes = PanelSizes::load();
+
+        let mut window = create_window(saved_panel_sizes);
+
+        let panel_sizes = Rc::new(RefCell::new(saved_panel_sizes.unwrap_or_default()));
+
+        window.begin();
+
+        create_menu_bar(&window, &sender);
+
+        let keybindings = match Keybindings::load() {
+            Ok(val) => val,
+            Err(err) => {
+                dialog::alert_default(
+                    format!("Could not load the key bindings, using the defaults instead:\n{err}")
+                        .as_str(),
+                );
+
+                Keybindings::default()
+            }
+        };
+
+        let help_overlay = create_help_overlay(&window, &keybindings);
+
+        window.end();
+
+        // Buttons would otherwise take the focus when clicked, and use the
+        // arrow keys and Tab to move it around instead of handing them on
+        app::set_visible_focus(false);
+
+        window.handle({
+            let sender = sender.clone();
+
+            let help_overlay = help_overlay.clone();
+
+            move |_, ev| match ev {
+                enums::Event::KeyDown | enums::Event::Shortcut => {
+                    if help_overlay.visible() && app::event_key() == enums::Key::Escape {
+                        sender.send(Message::ToggleHelp);
+
+                        return true;
+                    }
+
+                    let pressed = KeyCombo {
+                        key: app::event_key(),
+                        ctrl: app::is_event_ctrl(),
+                        shift: app::is_event_shift(),
+                        alt: app::is_event_alt(),
+                    };
+
+                    return match keybindings.command_for(pressed) {
+                        Some(command) => {
+                            sender.send(Message::from_command(command));
+
+                            true
+                        }
+                        None => false,
+                    };
+                }
+                _ => false,
+            }
+        });
+
+        window.set_callback({
+            let sender = sender.clone();
+
+            move |_| {
+                if app::event() == enums::Event::Close {
+                    sender.send(Message::Quit);
+                }
+            }
+        });
+
+        return LayoutEditorGUI {
+            app,
+            window,
+            sender,
+            receiver,
+            editor_widgets: None,
+            file_path: None,
+            readonly: false,
+            help_overlay,
+            title: String::new(),
+            panel_sizes,
+            comparison: None,
+        };
+    }
+
+    pub fn edit_layout(&mut self, layout: Layout) {
+        self.edit_layout_with_gaps(layout, GapSettings::default());
+    }
+
+    pub fn edit_layout_with_gaps(&mut self, layout: Layout, gaps: GapSettings) {
+        self.rebuild_editor_widgets(LayoutEditor::new(layout).with_gaps(gaps));
+    }
+
+    fn rebuild_editor_widgets(&mut self, editor: LayoutEditor) {
+        if let Some(editor_widgets) = self.editor_widgets.take() {
+            editor_widgets.delete();
+        }
+
+        self.window.begin();
+
+        let editor_widgets = EditorWidgets::initialize(
+            editor,
+            (
+                0,
+                MENU_BAR_HEIGHT,
+                self.window.w(),
+                self.window.h() - MENU_BAR_HEIGHT,
+            ),
+            &self.panel_sizes,
+            &self.sender,
+        );
+
+        self.window.end();
+
+        // Only the panels grow with the window, not the menu bar
+        self.window.resizable(&editor_widgets.panels.root);
+
+        self.editor_widgets = Some(editor_widgets);
+
+        if self.readonly {
+            self.deactivate_editing();
+        }
+
+        self.window.redraw();
+    }
+
+    pub fn new_layout(&mut self) {
+        if !self.confirm_discard_changes() {
+            return;
+        }
+
+        let (w, h) = app::screen_size();
+
+        self.edit_layout(Layout::new(w as i32, h as i32));
+
+        self.file_path = None;
+    }
+
+    fn new_layout_from_template(&mut self) {
+        if !self.confirm_discard_changes() {
+            return;
+        }
+
+        let (w, h) = app::screen_size();
+
+        let (template, params) = match choose_template(w as i32, h as i32) {
+            Some(val) => val,
+            None => return,
+        };
+
+        self.edit_layout(template.generate(&params, w as i32, h as i32));
+
+        self.file_path = None;
+
+        self.mark_unsaved();
+    }
+
+    pub fn open_layout(&mut self, path: &Path) -> Result<(), file::LayoutFileError> {
+        let (layout, gaps) = file::read_layout_with_gaps(path)?;
+
+        self.edit_layout_with_gaps(layout, gaps);
+
+        self.set_file_path(path.to_path_buf());
+
+        return Ok(());
+    }
+
+    pub fn set_file_path(&mut self, path: PathBuf) {
+        self.file_path = Some(path);
+    }
+
+    pub fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+
+        if readonly {
+            self.deactivate_editing();
+        }
+    }
+
+    fn deactivate_editing(&mut self) {
+        if let Some(editor_widgets) = &mut self.editor_widgets {
+            editor_widgets.actions.widgets.deactivate();
+
+            editor_widgets.variant_actions.widgets.deactivate();
+
+            editor_widgets.zone_inspector.widgets.deactivate();
+
+            editor_widgets.gap_actions.widgets.deactivate();
+
+            editor_widgets
+                .variant_state_pack
+                .child(1)
+                .unwrap()
+                .deactivate();
+
+            editor_widgets.end_behaviour_actions.widgets.deactivate();
+        }
+    }
+
+    fn open_layout_with_dialog(&mut self) {
+        if !self.confirm_discard_changes() {
+            return;
+        }
+
+        let path = match choose_file(
+            dialog::NativeFileChooserType::BrowseFile,
+            LAYOUT_FILE_FILTER,
+        ) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if let Err(err) = self.open_layout(&path) {
+            dialog::alert_default(format!("Could not open {}:\n{err}", path.display()).as_str());
+        }
+    }
+
+    fn save_layout(&mut self) {
+        if self.readonly {
+            return;
+        }
+
+        match self.file_path.to_owned() {
+            Some(path) => self.save_layout_to(&path),
+            None => self.save_layout_as(),
+        }
+    }
+
+    fn save_layout_as(&mut self) {
+        if let None = self.editor_widgets {
+            return;
+        }
+
+        let mut path = match choose_file(
+            dialog::NativeFileChooserType::BrowseSaveFile,
+            LAYOUT_FILE_FILTER,
+        ) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if let None = path.extension() {
+            path.set_extension("json");
+        }
+
+        if self.readonly && self.is_opened_path(&path) {
+            dialog::alert_default(
+                "The layout is open read-only and cannot be saved over, choose another file.",
+            );
+
+            return;
+        }
+
+        self.save_layout_to(&path);
+    }
+
+    fn is_opened_path(&self, path: &Path) -> bool {
+        let opened = match &self.file_path {
+            Some(val) => val,
+            None => return false,
+        };
+
+        return match (std::fs::canonicalize(opened), std::fs::canonicalize(path)) {
+            (Ok(opened), Ok(path)) => opened == path,
+            _ => opened == path,
+        };
+    }
+
+    fn save_layout_to(&mut self, path: &Path) {
+        let editor_widgets = match &mut self.editor_widgets {
+            Some(val) => val,
+            None => return,
+        };
+
+        if validation::has_errors(&validation::validate(editor_widgets.editor.layout())) {
+            let save_anyway = dialog::choice2_default(
+                "The layout has problems that himewm may not handle. Save anyway?",
+                "Cancel",
+                "Save anyway",
+                "",
+            ) == Some(1);
+
+            if !save_anyway {
+                return;
+            }
+        }
+
+        match file::write_layout_with_gaps(
+            editor_widgets.editor.layout(),
+            editor_widgets.editor.gaps(),
+            path,
+        ) {
+            Ok(()) => {
+                editor_widgets.editor.mark_saved();
+
+                self.file_path = Some(path.to_path_buf());
+            }
+            Err(err) => {
+                dialog::alert_default(
+                    format!("Could not save {}:\n{err}", path.display()).as_str(),
+                );
+            }
+        }
+    }
+
+    fn undo(&mut self) {
+        match &self.editor_widgets {
+            Some(editor_widgets) if editor_widgets.editor.can_undo() => {}
+            _ => return,
+        }
+
+        let mut editor = self.editor_widgets.take().unwrap().delete();
+
+        let _ = editor.apply(Action::Undo);
+
+        self.rebuild_editor_widgets(editor);
+    }
+
+    fn redo(&mut self) {
+        match &self.editor_widgets {
+            Some(editor_widgets) if editor_widgets.editor.can_redo() => {}
+            _ => return,
+        }
+
+        let mut editor = self.editor_widgets.take().unwrap().delete();
+
+        let _ = editor.apply(Action::Redo);
+
+        self.rebuild_editor_widgets(editor);
+    }
+
+    fn import_layout(&mut self, format: import::Format) {
+        if !self.confirm_discard_changes() {
+            return;
+        }
+
+        let source = match format {
+            import::Format::Tmux => {
+                match dialog::input_default("tmux layout string (#{window_layout}):", "") {
+                    Some(val) => val,
+                    None => return,
+                }
+            }
+            import::Format::I3 | import::Format::FancyZones => {
+                let path = match choose_file(
+                    dialog::NativeFileChooserType::BrowseFile,
+                    JSON_FILE_FILTER,
+                ) {
+                    Some(path) => path,
+                    None => return,
+                };
+
+                match std::fs::read_to_string(&path) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        dialog::alert_default(
+                            format!("Could not open {}:\n{err}", path.display()).as_str(),
+                        );
+
+                        return;
+                    }
+                }
+            }
+        };
+
+        let (w, h) = app::screen_size();
+
+        let imported = match format.parse(&source, w as i32, h as i32) {
+            Ok(val) => val,
+            Err(err) => {
+                dialog::alert_default(format!("Could not import the layout:\n{err}").as_str());
+
+                return;
+            }
+        };
+
+        self.edit_layout(imported.layout);
+
+        self.file_path = None;
+
+        self.mark_unsaved();
+
+        if !imported.unrepresented.is_empty() {
+            let unrepresented: Vec<String> = imported
+                .unrepresented
+                .iter()
+                .map(|unrepresented| format!("- {unrepresented}"))
+                .collect();
+
+            dialog::message_default(
+                format!(
+                    "Some of the layout could not be imported as it was:\n{}",
+                    unrepresented.join("\n")
+                )
+                .as_str(),
+            );
+        }
+    }
+
+    fn export_image(&self) {
+        let editor_widgets = match &self.editor_widgets {
+            Some(val) => val,
+            None => return,
+        };
+
+        let extended_states =
+            match dialog::input_default("Number of extended variant states to include:", "0") {
+                Some(val) => val,
+                None => return,
+            };
+
+        let extended_states: usize = match extended_states.trim().parse() {
+            Ok(val) => val,
+            Err(_) => {
+                dialog::alert_default(
+                    format!("'{extended_states}' is not a number of variant states").as_str(),
+                );
+
+                return;
+            }
+        };
+
+        let mut path = match choose_file(
+            dialog::NativeFileChooserType::BrowseSaveFile,
+            IMAGE_FILE_FILTER,
+        ) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if let None = path.extension() {
+            path.set_extension("svg");
+        }
+
+        if let Err(err) = export::export_image(
+            editor_widgets.editor.layout(),
+            editor_widgets.editor.gaps(),
+            &path,
+            extended_states,
+        ) {
+            dialog::alert_default(format!("Could not export {}:\n{err}", path.display()).as_str());
+        }
+    }
+
+    /// Exports the selected variant.
+    fn export_layout(&self, format: export::Format) {
+        let editor_widgets = match &self.editor_widgets {
+            Some(val) => val,
+            None => return,
+        };
+
+        let (filter, extension) = match format {
+            export::Format::FancyZones | export::Format::I3 => (JSON_FILE_FILTER, "json"),
+            export::Format::Csv(_) => (CSV_FILE_FILTER, "csv"),
+        };
+
+        let mut path = match choose_file(dialog::NativeFileChooserType::BrowseSaveFile, filter) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if let None = path.extension() {
+            path.set_extension(extension);
+        }
+
+        if let Err(err) = export::export_layout(
+            editor_widgets.editor.layout(),
+            editor_widgets.editor.gaps(),
+            editor_widgets.editor.selected_variant_idx(),
+            format,
+            &path,
+        ) {
+            dialog::alert_default(format!("Could not export {}:\n{err}", path.display()).as_str());
+        }
+    }
+
+    fn change_monitor_size(&mut self) {
+        if self.readonly {
+            return;
+        }
+
+        let (w, h) = match &self.editor_widgets {
+            Some(editor_widgets) => {
+                let monitor_rect = editor_widgets.editor.layout().get_monitor_rect();
+
+                (monitor_rect.w(), monitor_rect.h())
+            }
+            None => return,
+        };
+
+        let size = match dialog::input_default(
+            "New monitor size (WIDTHxHEIGHT):",
+            format!("{w}x{h}").as_str(),
+        ) {
+            Some(val) => val,
+            None => return,
+        };
+
+        let (new_w, new_h) = match rescale::parse_monitor_size(&size) {
+            Some(val) => val,
+            None => {
+                dialog::alert_default(format!("'{size}' is not a monitor size").as_str());
+
+                return;
+            }
+        };
+
+        if (new_w, new_h) == (w, h) {
+            return;
+        }
+
+        let mut editor = self.editor_widgets.take().unwrap().delete();
+
+        if let Err(err) = editor.apply(Action::SetMonitorSize(new_w, new_h)) {
+            dialog::alert_default(err.to_string().as_str());
+        }
+
+        self.rebuild_editor_widgets(editor);
+    }
+
+    /// Applies the values of the gap controls, to the selected variant alone
+    /// if it is to have gaps of its own. Unchecking "Only this variant" goes
+    /// back to the gaps of the layout.
+    fn set_gaps(&mut self) {
+        let editor_widgets = match &mut self.editor_widgets {
+            Some(val) => val,
+            None => return,
+        };
+
+        let gaps = editor_widgets.gap_actions.value();
+
+        let own_gaps = editor_widgets
+            .gap_actions
+            .own_gaps_check_button
+            .is_checked();
+
+        let editor = &mut editor_widgets.editor;
+
+        let old_gaps = editor.gaps().to_owned();
+
+        let action = if own_gaps {
+            Action::SetVariantGaps(Some(gaps))
+        } else if old_gaps
+            .variants
+            .contains_key(&editor.selected_variant_idx())
+        {
+            Action::SetVariantGaps(None)
+        } else {
+            Action::SetLayoutGaps(gaps)
+        };
+
+        if let Err(_) = editor.apply(action) {
+            return;
+        }
+
+        if *editor.gaps() == old_gaps {
+            return;
+        }
+
+        let editor = self.editor_widgets.take().unwrap().delete();
+
+        self.rebuild_editor_widgets(editor);
+    }
+
+    /// Shows the variant states `comparison` picks below the canvas, or hides
+    /// them if they are already shown.
+    fn toggle_comparison(&mut self, comparison: Comparison) {
+        if self.comparison == Some(comparison) {
+            self.comparison = None;
+        } else {
+            self.comparison = Some(comparison);
+        }
+    }
+
+    fn preview_resolutions(&self) {
+        let editor = match &self.editor_widgets {
+            Some(editor_widgets) => &editor_widgets.editor,
+            None => return,
+        };
+
+        let monitor_rect = editor.layout().get_monitor_rect();
+
+        show_resolution_preview(
+            editor.selected_zones().to_owned(),
+            editor.gaps().for_variant(editor.selected_variant_idx()),
+            (monitor_rect.w(), monitor_rect.h()),
+        );
+    }
+
+    fn play_window_counts(&self) {
+        let editor_widgets = match &self.editor_widgets {
+            Some(val) => val,
+            None => return,
+        };
+
+        let layout = editor_widgets.editor.layout();
+
+        let variant_idx = editor_widgets.editor.selected_variant_idx();
+
+        let monitor_rect = layout.get_monitor_rect();
+
+        show_playback(
+            layout.get_variants()[variant_idx].to_owned(),
+            editor_widgets.editor.gaps().for_variant(variant_idx),
+            (monitor_rect.w(), monitor_rect.h()),
+        );
+    }
+
+    fn show_command_palette(&self) {
+        let commands: Vec<MenuCommand> = menu_commands()
+            .into_iter()
+            .filter(|command| !matches!(command.message, Message::ShowCommandPalette))
+            .collect();
+
+        let labels: Vec<String> = commands.iter().map(MenuCommand::label).collect();
+
+        let messages: Vec<Message> = commands
+            .into_iter()
+            .map(|command| command.message)
+            .collect();
+
+        let w = std::cmp::min(self.window.w() - 32, 480);
+
+        let h = std::cmp::min(self.window.h() - MENU_BAR_HEIGHT - 16, 320);
+
+        let mut window = window::Window::new(
+            self.window.x() + (self.window.w() - w) / 2,
+            self.window.y() + MENU_BAR_HEIGHT + 8,
+            w,
+            h,
+            "Command palette",
+        );
+
+        window.make_modal(true);
+
+        let mut widgets = group::Flex::default_fill().column();
+
+        widgets.set_margin(4);
+
+        let mut query_input = input::Input::default();
+
+        query_input.set_tooltip("Type part of a command, then press Enter");
+
+        widgets.fixed(&query_input, 32);
+
+        let mut results = browser::HoldBrowser::default();
+
+        widgets.end();
+
+        window.end();
+
+        let shown = Rc::new(RefCell::new(Vec::new()));
+
+        let mut filter = {
+            let shown = shown.clone();
+
+            let mut results = results.clone();
+
+            move |query: &str| {
+                *shown.borrow_mut() = palette::rank(query, &labels);
+
+                results.clear();
+
+                for idx in shown.borrow().iter() {
+                    results.add(labels[*idx].as_str());
+                }
+
+                if results.size() > 0 {
+                    results.select(1);
+                }
+            }
+        };
+
+        filter("");
+
+        let dispatch = {
+            let sender = self.sender.clone();
+
+            let results = results.clone();
+
+            let mut window = window.clone();
+
+            move || {
+                if let Some(idx) = shown.borrow().get((results.value() - 1) as usize) {
+                    sender.send(messages[*idx].clone());
+                }
+
+                window.hide();
             }
+        };
+
+        query_input.set_trigger(enums::CallbackTrigger::Changed);
+
+        query_input.set_callback(move |i| filter(&i.value()));
+
+        query_input.handle({
+            let mut results = results.clone();
+
+            let mut dispatch = dispatch.clone();
+
+            let mut window = window.clone();
+
+            move |_, ev| {
+                if ev != enums::Event::KeyDown {
+                    return false;
+                }
+
+                let selected = results.value();
+
+                match app::event_key() {
+                    enums::Key::Up if selected > 1 => results.select(selected - 1),
+                    enums::Key::Down if selected < results.size() => results.select(selected + 1),
+                    enums::Key::Enter | enums::Key::KPEnter => dispatch(),
+                    enums::Key::Escape => window.hide(),
+                    _ => return false,
+                }
+
+                return true;
+            }
+        });
+
+        results.set_callback({
+            let mut dispatch = dispatch;
+
+            move |_| {
+                if app::event_clicks() {
+                    dispatch();
+                }
+            }
+        });
+
+        window.show();
+
+        let _ = query_input.take_focus();
+    }
+
+    fn toggle_help(&mut self) {
+        if self.help_overlay.visible() {
+            self.help_overlay.hide();
+        } else {
+            // Raise the overlay above the editor widgets, which are added to
+            // the window after it
+            self.window.remove(&self.help_overlay);
+
+            self.window.add(&self.help_overlay);
+
+            self.help_overlay.resize(
+                0,
+                MENU_BAR_HEIGHT,
+                self.window.w(),
+                self.window.h() - MENU_BAR_HEIGHT,
+            );
+
+            self.help_overlay.show();
+        }
+
+        self.window.redraw();
+    }
+
+    fn quit(&mut self) {
+        if !self.confirm_discard_changes() {
+            return;
+        }
+
+        let mut panel_sizes = self.panel_sizes.borrow_mut();
+
+        panel_sizes.window_w = self.window.w();
+
+        panel_sizes.window_h = self.window.h();
+
+        // Forgetting the panel sizes is not worth keeping anyone from quitting
+        // over
+        let _ = panel_sizes.save();
+
+        self.window.hide();
+    }
+
+    /// Whether the layout has changed since it was opened or last saved.
+    fn modified(&self) -> bool {
+        return match &self.editor_widgets {
+            Some(editor_widgets) => editor_widgets.editor.is_modified(),
+            None => false,
+        };
+    }
+
+    /// Marks a layout that did not come from a file as having unsaved
+    /// changes.
+    fn mark_unsaved(&mut self) {
+        if let Some(editor_widgets) = &mut self.editor_widgets {
+            editor_widgets.editor.mark_unsaved();
+        }
+    }
+
+    fn confirm_discard_changes(&self) -> bool {
+        if !self.modified() {
+            return true;
+        }
+
+        return dialog::choice2_default("Discard unsaved changes?", "Cancel", "Discard", "")
+            == Some(1);
+    }
+
+    /// Shows the name of the open file in the title bar, marked with `*` if
+    /// it has unsaved changes.
+    fn update_title(&mut self) {
+        let file_name = match &self.file_path {
+            Some(path) => match path.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => path.display().to_string(),
+            },
+            None => "Untitled".to_string(),
+        };
+
+        let modified_marker = if self.modified() { "*" } else { "" };
+
+        let readonly_marker = if self.readonly { " (read-only)" } else { "" };
+
+        let title = format!("{file_name}{modified_marker}{readonly_marker} - himewm layout editor");
+
+        if self.title != title {
+            self.window.set_label(title.as_str());
+
+            self.title = title;
+        }
+    }
+
+    pub fn run(mut self) {
+        self.update_title();
+
+        self.window.show();
+
+        while self.app.wait() {
+            handle_events(&mut self);
+
+            self.update_title();
+        }
+    }
+}
+
+fn initialize_colours() {
+    app::background(16, 16, 16);
+
+    app::background2(32, 32, 32);
+}
+
+/// Opens the window at the size it was left at, or at half the size of the
+/// primary screen the first time.
+fn create_window(saved_panel_sizes: Option<PanelSizes>) -> window::Window {
+    let primary_screen = app::Screen::new(0).unwrap();
+
+    let (w, h) = match saved_panel_sizes {
+        Some(sizes) => (
+            sizes.window_w.clamp(320, primary_screen.w()),
+            sizes.window_h.clamp(240, primary_screen.h()),
+        ),
+        None => (primary_screen.w() / 2, primary_screen.h() / 2),
+    };
+
+    let mut window = window::Window::new(
+        primary_screen.w() / 2 - w / 2,
+        primary_screen.h() / 2 - h / 2,
+        w,
+        h,
+        "himewm layout editor",
+    );
+
+    window.make_resizable(true);
+
+    window.end();
+
+    return window;
+}
+
+/// Lists every key binding, for `toggle_help` to show on top of the editor.
+fn create_help_overlay(window: &window::Window, keybindings: &Keybindings) -> group::Group {
+    let mut overlay = group::Group::new(
+        0,
+        MENU_BAR_HEIGHT,
+        window.w(),
+        window.h() - MENU_BAR_HEIGHT,
+        None,
+    );
+
+    overlay.set_frame(FrameType::FlatBox);
+
+    overlay.set_color(Color::Background);
+
+    let mut browser = browser::Browser::new(
+        overlay.x() + 16,
+        overlay.y() + 16,
+        overlay.w() - 32,
+        overlay.h() - 32,
+        None,
+    );
+
+    browser.set_column_char('\t');
 
-            old_pack.hide();
-        }
+    browser.set_column_widths(&[200, 280]);
 
-        if let Some(new_pack) = &mut self.variant_state_selection.child(new_idx.0 as i32) {
-            if let Some(new_button) = &mut group::Pack::from_dyn_widget(new_pack)
-                .unwrap()
-                .child(new_idx.1 as i32)
-            {
-                new_button.set_color(colors::html::DimGray);
-            }
+    browser.add("@bKeys\t@bCommand\t@bName in the key bindings file");
 
-            new_pack.show();
-        }
+    for (command, keys) in keybindings.bindings() {
+        let keys: Vec<String> = keys.iter().map(KeyCombo::to_string).collect();
+
+        let keys = if keys.is_empty() {
+            "(unbound)".to_string()
+        } else {
+            keys.join(", ")
+        };
+
+        browser.add(format!("{keys}\t{}\t{}", command.description(), command.name()).as_str());
     }
 
-    fn update_shown_variant_state(&mut self, old_idx: (usize, usize), new_idx: (usize, usize)) {
-        if let Some(old_variant) = &mut self.variant_state_display.child(old_idx.0 as i32) {
-            if let Some(old_group) = &mut group::Group::from_dyn_widget(old_variant)
-                .unwrap()
-                .child(old_idx.1 as i32)
-            {
-                old_group.hide();
-            }
+    browser.add("Shift\tAdd the zone to the selection instead");
 
-            old_variant.hide();
-        }
+    browser.add("");
 
-        if let Some(new_variant) = &mut self.variant_state_display.child(new_idx.0 as i32) {
-            if let Some(new_group) = &mut group::Group::from_dyn_widget(new_variant)
-                .unwrap()
-                .child(new_idx.1 as i32)
-            {
-                new_group.show();
-            }
+    let config_path = match Keybindings::config_path() {
+        Some(path) => path.display().to_string(),
+        None => "keybindings.json".to_string(),
+    };
 
-            new_variant.show();
-        }
-    }
+    browser.add(format!("@iKey bindings can be changed in {config_path}").as_str());
 
-    fn highlight_selected_zone(&mut self, idx: usize) {
-        let selected_variant_idx = self.editor.selected_variant_idx;
+    browser.add("@iPress Escape to close this help");
 
-        let selected_variant_state_idx = self.editor.selected_variant_state_idx;
+    overlay.end();
 
-        if let Some(variant) = group::Group::from_dyn_widget(
-            &self
-                .variant_state_display
-                .child(selected_variant_idx as i32)
-                .unwrap(),
-        ) {
-            if let Some(group) = group::Group::from_dyn_widget(
-                &variant.child(selected_variant_state_idx as i32).unwrap(),
-            ) {
-                if let Some(zone_button) =
-                    &mut button::Button::from_dyn_widget(&group.child(idx as i32).unwrap())
-                {
-                    zone_button.set_color(colors::html::DodgerBlue);
+    overlay.resizable(&browser);
 
-                    zone_button.redraw();
-                }
-            }
-        }
+    overlay.hide();
+
+    return overlay;
+}
+
+/// An entry of the menu bar, which the command palette offers as well.
+struct MenuCommand {
+    path: &'static str,
+    shortcut: Shortcut,
+    flag: MenuFlag,
+    message: Message,
+}
+
+impl MenuCommand {
+    /// The path without its access keys, such as `File > Save As...`.
+    fn label(&self) -> String {
+        return self
+            .path
+            .trim_end_matches('\t')
+            .replace('&', "")
+            .replace('/', " > ");
     }
+}
 
-    fn dehighlight_zone(&mut self, variant_idx: usize, variant_state_idx: usize, zone_idx: usize) {
-        if let Some(variant) = group::Group::from_dyn_widget(
-            &self
-                .variant_state_display
-                .child(variant_idx as i32)
-                .unwrap(),
-        ) {
-            if let Some(group) =
-                group::Group::from_dyn_widget(&variant.child(variant_state_idx as i32).unwrap())
-            {
-                if let Some(zone_button) =
-                    &mut button::Button::from_dyn_widget(&group.child(zone_idx as i32).unwrap())
-                {
-                    zone_button.set_color(colors::html::Gainsboro);
+fn menu_commands() -> Vec<MenuCommand> {
+    return vec![
+        MenuCommand {
+            path: "&File/&New\t",
+            shortcut: Shortcut::Ctrl | 'n',
+            flag: MenuFlag::Normal,
+            message: Message::NewLayout,
+        },
+        MenuCommand {
+            path: "&File/New from &template...\t",
+            shortcut: Shortcut::Ctrl | Shortcut::Shift | 'n',
+            flag: MenuFlag::Normal,
+            message: Message::NewLayoutFromTemplate,
+        },
+        MenuCommand {
+            path: "&File/&Open...\t",
+            shortcut: Shortcut::Ctrl | 'o',
+            flag: MenuFlag::Normal,
+            message: Message::OpenLayout,
+        },
+        MenuCommand {
+            path: "&File/&Save\t",
+            shortcut: Shortcut::Ctrl | 's',
+            flag: MenuFlag::Normal,
+            message: Message::SaveLayout,
+        },
+        MenuCommand {
+            path: "&File/Save &As...\t",
+            shortcut: Shortcut::Ctrl | Shortcut::Shift | 's',
+            flag: MenuFlag::MenuDivider,
+            message: Message::SaveLayoutAs,
+        },
+        MenuCommand {
+            path: "&File/&Import/i3 or sway layout...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ImportLayout(import::Format::I3),
+        },
+        MenuCommand {
+            path: "&File/&Import/tmux layout...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ImportLayout(import::Format::Tmux),
+        },
+        MenuCommand {
+            path: "&File/&Import/FancyZones layouts...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ImportLayout(import::Format::FancyZones),
+        },
+        MenuCommand {
+            path: "&File/&Export/&Image...\t",
+            shortcut: Shortcut::Ctrl | 'e',
+            flag: MenuFlag::Normal,
+            message: Message::ExportImage,
+        },
+        MenuCommand {
+            path: "&File/&Export/&FancyZones layouts...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ExportLayout(export::Format::FancyZones),
+        },
+        MenuCommand {
+            path: "&File/&Export/i3 layouts...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ExportLayout(export::Format::I3),
+        },
+        MenuCommand {
+            path: "&File/&Export/CSV in pixels...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ExportLayout(export::Format::Csv(export::Units::Pixels)),
+        },
+        MenuCommand {
+            path: "&File/&Export/CSV in percentages...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ExportLayout(export::Format::Csv(export::Units::Percent)),
+        },
+        MenuCommand {
+            path: "&File/&Quit\t",
+            shortcut: Shortcut::Ctrl | 'q',
+            flag: MenuFlag::Normal,
+            message: Message::Quit,
+        },
+        MenuCommand {
+            path: "&Edit/&Undo\t",
+            shortcut: Shortcut::Ctrl | 'z',
+            flag: MenuFlag::Normal,
+            message: Message::Undo,
+        },
+        MenuCommand {
+            path: "&Edit/&Redo\t",
+            shortcut: Shortcut::Ctrl | Shortcut::Shift | 'z',
+            flag: MenuFlag::MenuDivider,
+            message: Message::Redo,
+        },
+        MenuCommand {
+            path: "&Edit/Change &monitor size...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ChangeMonitorSize,
+        },
+        MenuCommand {
+            path: "&Variant/&New variant\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::NewVariant,
+        },
+        MenuCommand {
+            path: "&Variant/&Clone variant\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::CloneVariant,
+        },
+        MenuCommand {
+            path: "&Variant/&Delete variant\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::DeleteVariant,
+        },
+        MenuCommand {
+            path: "&Variant/Set as &default\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::SetVariantAsDefault,
+        },
+        MenuCommand {
+            path: "&Variant/Move &up\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SwapVariant(SwapDirection::Previous),
+        },
+        MenuCommand {
+            path: "&Variant/Move do&wn\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::SwapVariant(SwapDirection::Next),
+        },
+        MenuCommand {
+            path: "&Variant/&Previous variant\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SelectAdjacentVariant(SwapDirection::Previous),
+        },
+        MenuCommand {
+            path: "&Variant/Ne&xt variant\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SelectAdjacentVariant(SwapDirection::Next),
+        },
+        MenuCommand {
+            path: "&State/&New variant state\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::NewVariantState,
+        },
+        MenuCommand {
+            path: "&State/&Clone variant state\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::CloneVariantState,
+        },
+        MenuCommand {
+            path: "&State/&Delete variant state\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::DeleteVariantState,
+        },
+        MenuCommand {
+            path: "&State/Move &earlier\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SwapVariantState(SwapDirection::Previous),
+        },
+        MenuCommand {
+            path: "&State/Move &later\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::SwapVariantState(SwapDirection::Next),
+        },
+        MenuCommand {
+            path: "&State/&Previous variant state\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SelectAdjacentVariantState(SwapDirection::Previous),
+        },
+        MenuCommand {
+            path: "&State/Ne&xt variant state\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SelectAdjacentVariantState(SwapDirection::Next),
+        },
+        MenuCommand {
+            path: "&Zone/Select the &next zone\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::SelectNextZone,
+        },
+        MenuCommand {
+            path: "&Zone/Split side by &side\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SetSplitDirection(Direction::Horizontal),
+        },
+        MenuCommand {
+            path: "&Zone/Split on &top of each other\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::SetSplitDirection(Direction::Vertical),
+        },
+        MenuCommand {
+            path: "&Zone/S&plit\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::Split,
+        },
+        MenuCommand {
+            path: "&Zone/Split &evenly\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SplitEvenly,
+        },
+        MenuCommand {
+            path: "&Zone/&Merge\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::Merge,
+        },
+        MenuCommand {
+            path: "&Zone/S&wap\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::Swap,
+        },
+        MenuCommand {
+            path: "E&nd behaviour/Switch between &directional and repeating\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SwapEndTilingBehaviour,
+        },
+        MenuCommand {
+            path: "E&nd behaviour/Switch the &end tiling direction\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::SwapEndTilingDirection,
+        },
+        MenuCommand {
+            path: "E&nd behaviour/&Add a repeating split\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::AddRepeatingSplit,
+        },
+        MenuCommand {
+            path: "E&nd behaviour/&Preview extend\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::PreviewExtend,
+        },
+        MenuCommand {
+            path: "E&nd behaviour/&Stop previewing\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::CancelPreview,
+        },
+        MenuCommand {
+            path: "&View/Compare variant &states\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ToggleComparison(Comparison::VariantStates),
+        },
+        MenuCommand {
+            path: "&View/Compare &variants\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::ToggleComparison(Comparison::Variants),
+        },
+        MenuCommand {
+            path: "&View/P&lay window counts...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::PlayWindowCounts,
+        },
+        MenuCommand {
+            path: "&View/&Preview at other resolutions...\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::MenuDivider,
+            message: Message::PreviewResolutions,
+        },
+        MenuCommand {
+            path: "&View/&Command palette...\t",
+            shortcut: Shortcut::Ctrl | Shortcut::Shift | 'p',
+            flag: MenuFlag::Normal,
+            message: Message::ShowCommandPalette,
+        },
+        MenuCommand {
+            path: "&Help/&Key bindings\t",
+            shortcut: Shortcut::None,
+            flag: MenuFlag::Normal,
+            message: Message::ToggleHelp,
+        },
+    ];
+}
 
-                    zone_button.redraw();
-                }
-            }
-        }
+fn create_menu_bar(window: &window::Window, sender: &app::Sender<Message>) {
+    let mut menu_bar = menu::MenuBar::new(0, 0, window.w(), MENU_BAR_HEIGHT, None);
+
+    for command in menu_commands() {
+        menu_bar.add_emit(
+            command.path,
+            command.shortcut,
+            command.flag,
+            sender.clone(),
+            command.message,
+        );
     }
+}
 
-    fn reset_zone_selection(&mut self) {
-        self.disable_split();
+/// Scales the part of `zone` a window is placed in, with `gaps` taken off,
+/// the same way as `geometry::scale_zone`.
+fn scale_zone_window(
+    zone: &Zone,
+    gaps: Gaps,
+    variant_width: f64,
+    variant_height: f64,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+) -> (i32, i32, i32, i32) {
+    let window_rect = gaps.window_rect(zone, variant_width as i32, variant_height as i32);
+
+    return geometry::scale_rect(window_rect, variant_width, variant_height, x, y, w, h);
+}
 
-        let variant_idx = self.editor.selected_variant_idx;
+fn draw_zones(
+    zones: &[Zone],
+    gaps: Gaps,
+    variant_width: f64,
+    variant_height: f64,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+) {
+    for (i, zone) in zones.iter().enumerate() {
+        let (zone_x, zone_y, zone_w, zone_h) =
+            scale_zone_window(zone, gaps, variant_width, variant_height, x, y, w, h);
+
+        draw::draw_rect_fill(zone_x, zone_y, zone_w, zone_h, colors::html::Gainsboro);
+
+        draw::set_draw_color(Color::Black);
+
+        draw::draw_rect(zone_x, zone_y, zone_w, zone_h);
+
+        draw::set_font(enums::Font::Helvetica, 12);
+
+        draw::draw_text2(
+            (i + 1).to_string().as_str(),
+            zone_x,
+            zone_y,
+            zone_w,
+            zone_h,
+            Align::Center,
+        );
+    }
+}
 
-        let variant_state_idx = self.editor.selected_variant_state_idx;
+/// Opens a window showing `zones` rescaled to several monitor sizes side by
+/// side, with `gaps` taken off them. The sizes can be edited as a comma
+/// separated list.
+fn show_resolution_preview(zones: Vec<Zone>, gaps: Gaps, monitor_size: (i32, i32)) {
+    let primary_screen = app::Screen::new(0).unwrap();
 
-        if let Some(zone_idx) = self.editor.selected_zone_idx1 {
-            self.dehighlight_zone(variant_idx, variant_state_idx, zone_idx);
+    let mut window = window::Window::new(
+        primary_screen.w() / 4,
+        primary_screen.h() / 3,
+        primary_screen.w() / 2,
+        primary_screen.h() / 3,
+        "Preview at other resolutions",
+    );
 
-            self.editor.selected_zone_idx1 = None;
+    window.make_resizable(true);
 
-            if let Some(zone_idx) = self.editor.selected_zone_idx2 {
-                self.dehighlight_zone(variant_idx, variant_state_idx, zone_idx);
+    let mut widgets = group::Flex::default_fill().column();
 
-                self.editor.selected_zone_idx2 = None;
-            }
+    widgets.set_margin(4);
+
+    let sizes = Rc::new(RefCell::new(rescale::PREVIEW_SIZES.to_vec()));
+
+    let mut sizes_input = input::Input::default();
+
+    sizes_input.set_tooltip("Monitor sizes to preview, separated by commas");
+
+    sizes_input.set_value(
+        rescale::PREVIEW_SIZES
+            .iter()
+            .map(|(w, h)| format!("{w}x{h}"))
+            .collect::<Vec<String>>()
+            .join(", ")
+            .as_str(),
+    );
+
+    widgets.fixed(&sizes_input, 32);
+
+    let mut canvas = frame::Frame::default();
+
+    canvas.draw({
+        let sizes = sizes.clone();
+
+        move |f| draw_resolution_preview(f, &zones, gaps, monitor_size, &sizes.borrow())
+    });
+
+    sizes_input.set_trigger(enums::CallbackTrigger::EnterKeyAlways);
+
+    sizes_input.set_callback({
+        let mut canvas = canvas.clone();
+
+        move |i| {
+            *sizes.borrow_mut() = i
+                .value()
+                .split(',')
+                .filter_map(rescale::parse_monitor_size)
+                .collect();
+
+            canvas.redraw();
         }
+    });
+
+    widgets.end();
+
+    window.end();
+
+    window.show();
+}
+
+fn draw_resolution_preview(
+    f: &frame::Frame,
+    zones: &[Zone],
+    gaps: Gaps,
+    monitor_size: (i32, i32),
+    sizes: &[(i32, i32)],
+) {
+    draw::draw_rect_fill(f.x(), f.y(), f.w(), f.h(), Color::Background2);
+
+    draw::set_draw_color(Color::Foreground);
+
+    draw::set_font(enums::Font::Helvetica, 12);
+
+    if sizes.is_empty() {
+        draw::draw_text2(
+            "No monitor sizes to preview",
+            f.x(),
+            f.y(),
+            f.w(),
+            f.h(),
+            Align::Center,
+        );
+
+        return;
     }
 
-    fn new_variant_state(&mut self, sender: &app::Sender<Message>) {
-        let variant_idx = self.editor.selected_variant_idx;
+    let label_h = 20;
 
-        let variant_state_pack = &mut group::Pack::from_dyn_widget(
-            &self
-                .variant_state_selection
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+    let slot_w = f.w() / sizes.len() as i32;
 
-        variant_state_pack.begin();
+    let available_w = slot_w - 8;
 
-        let mut b = button::Button::default()
-            .with_size(64, 0)
-            .with_label(variant_state_pack.children().to_string().as_str());
+    let available_h = f.h() - label_h - 8;
 
-        b.set_color(Color::Background2);
+    for (i, &(monitor_w, monitor_h)) in sizes.iter().enumerate() {
+        let mut rescaled = zones.to_owned();
 
-        b.set_selection_color(Color::Background);
+        rescale::rescale_zones(&mut rescaled, monitor_size, (monitor_w, monitor_h));
 
-        b.emit(
-            sender.clone(),
-            Message::SelectedVariantStateChanged(variant_state_pack.children() as usize - 1),
+        let slot_x = f.x() + i as i32 * slot_w;
+
+        let scale = f64::min(
+            available_w as f64 / monitor_w as f64,
+            available_h as f64 / monitor_h as f64,
         );
 
-        variant_state_pack.end();
+        let w = (monitor_w as f64 * scale).round() as i32;
 
-        let variant_state_display_group = &mut group::Group::from_dyn_widget(
-            &self
-                .variant_state_display
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+        let h = (monitor_h as f64 * scale).round() as i32;
 
-        variant_state_display_group.begin();
+        let x = slot_x + (slot_w - w) / 2;
 
-        let _g = Self::display_group_from_variant_state(
-            self.editor.layout.get_monitor_rect().w() as f64,
-            self.editor.layout.get_monitor_rect().h() as f64,
-            &self.editor.layout.get_variants()[variant_idx],
-            variant_state_pack.children() as usize - 1,
-            sender,
+        let y = f.y() + 4 + (available_h - h) / 2;
+
+        draw_zones(
+            &rescaled,
+            gaps,
+            monitor_w as f64,
+            monitor_h as f64,
+            x,
+            y,
+            w,
+            h,
         );
 
-        variant_state_display_group.end();
+        draw::set_draw_color(Color::Foreground);
 
-        sender.send(Message::SelectedVariantStateChanged(
-            variant_state_pack.children() as usize - 1,
-        ));
+        draw::draw_text2(
+            format!("{monitor_w}x{monitor_h}").as_str(),
+            slot_x,
+            f.y() + f.h() - label_h,
+            slot_w,
+            label_h,
+            Align::Center,
+        );
     }
+}
 
-    fn delete_variant_state(&mut self, sender: &app::Sender<Message>, custom_idx: Option<usize>) {
-        let variant_idx = self.editor.selected_variant_idx;
+/// Opens a window that plays `variant` through an increasing number of
+/// windows, showing the variant state each number of windows is tiled into.
+fn show_playback(variant: Variant, gaps: Gaps, monitor_size: (i32, i32)) {
+    let primary_screen = app::Screen::new(0).unwrap();
 
-        let variant_state_idx = match custom_idx {
-            Some(idx) => idx,
-            None => self.editor.selected_variant_state_idx,
-        };
+    let mut window = window::Window::new(
+        primary_screen.w() / 4,
+        primary_screen.h() / 4,
+        primary_screen.w() / 2,
+        primary_screen.h() / 2,
+        "Play window counts",
+    );
 
-        let variant_state_pack = &mut group::Pack::from_dyn_widget(
-            &self
-                .variant_state_selection
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+    window.make_resizable(true);
 
-        WidgetBase::delete(variant_state_pack.child(variant_state_idx as i32).unwrap());
+    let mut widgets = group::Flex::default_fill().column();
 
-        let variant_state_display_group = &mut group::Group::from_dyn_widget(
-            &self
-                .variant_state_display
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+    widgets.set_margin(4);
 
-        WidgetBase::delete(
-            variant_state_display_group
-                .child(variant_state_idx as i32)
-                .unwrap(),
-        );
+    let max_window_count = playback::default_max_window_count(&variant);
 
-        self.decrement_all_variant_state_buttons_after(variant_idx, variant_state_idx, sender);
+    let progression = Rc::new(RefCell::new(playback::Progression::new(
+        &variant,
+        max_window_count,
+    )));
 
-        if let None = custom_idx {
-            if variant_state_idx
-                == self.editor.layout.get_variants()[self.editor.selected_variant_idx]
-                    .manual_zones_until()
-            {
-                self.editor.selected_variant_state_idx -= 1;
-            }
+    let mut canvas = frame::Frame::default();
 
-            sender.send(Message::SelectedVariantStateChanged(
-                self.editor.selected_variant_state_idx,
-            ));
-        }
+    let mut controls = group::Flex::default().row();
+
+    let mut play_button = button::Button::default().with_label("@>");
+
+    play_button.set_tooltip("Play or pause");
+
+    controls.fixed(&play_button, 32);
+
+    let mut slider = valuator::HorNiceSlider::default();
+
+    slider.set_tooltip("Number of windows");
+
+    slider.set_step(1.0, 1);
+
+    slider.set_bounds(
+        1.0,
+        std::cmp::max(progression.borrow().max_window_count(), 1) as f64,
+    );
+
+    slider.set_value(1.0);
+
+    let speed_label = frame::Frame::default()
+        .with_label("Windows per second")
+        .with_align(Align::Right | Align::Inside);
+
+    controls.fixed(&speed_label, 144);
+
+    let mut speed_choice = menu::Choice::default();
+
+    for speed in playback::SPEEDS {
+        speed_choice.add_choice(format!("{speed}").as_str());
     }
 
-    fn swap_variant_states(&mut self, swap_with: usize, sender: &app::Sender<Message>) {
-        let variant_idx = self.editor.selected_variant_idx;
+    speed_choice.set_value(1);
 
-        let first_idx = std::cmp::min(self.editor.selected_variant_state_idx, swap_with);
-        let second_idx = std::cmp::max(self.editor.selected_variant_state_idx, swap_with);
+    controls.fixed(&speed_choice, 64);
 
-        let variant_state_pack = &mut group::Pack::from_dyn_widget(
-            &self
-                .variant_state_selection
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+    let max_label = frame::Frame::default()
+        .with_label("Up to")
+        .with_align(Align::Right | Align::Inside);
 
-        let first_button = &mut button::Button::from_dyn_widget(
-            &variant_state_pack.child(first_idx as i32).unwrap(),
-        )
-        .unwrap();
+    controls.fixed(&max_label, 48);
 
-        let second_button = &mut button::Button::from_dyn_widget(
-            &variant_state_pack.child(second_idx as i32).unwrap(),
-        )
-        .unwrap();
+    let mut max_spinner = misc::Spinner::default();
 
-        variant_state_pack.remove_by_index(second_idx as i32);
+    max_spinner.set_type(misc::SpinnerType::Int);
 
-        variant_state_pack.remove_by_index(first_idx as i32);
+    max_spinner.set_tooltip("Number of windows to play through");
 
-        first_button.set_label((second_idx + 1).to_string().as_str());
+    max_spinner.set_range(1.0, playback::MAX_WINDOW_COUNT as f64);
 
-        first_button.emit(
-            sender.clone(),
-            Message::SelectedVariantStateChanged(second_idx),
-        );
+    max_spinner.set_step(1.0);
 
-        second_button.set_label((first_idx + 1).to_string().as_str());
+    max_spinner.set_value(max_window_count as f64);
+
+    controls.fixed(&max_spinner, 64);
+
+    controls.end();
+
+    widgets.fixed(&controls, 32);
+
+    widgets.end();
+
+    window.end();
+
+    canvas.draw({
+        let progression = progression.clone();
+
+        let slider = slider.clone();
+
+        move |f| {
+            draw_playback(
+                f,
+                &progression.borrow(),
+                slider.value() as usize,
+                gaps,
+                monitor_size,
+            )
+        }
+    });
+
+    slider.set_callback({
+        let mut canvas = canvas.clone();
+
+        move |_| canvas.redraw()
+    });
+
+    max_spinner.set_callback({
+        let progression = progression.clone();
+
+        let mut slider = slider.clone();
+
+        let mut canvas = canvas.clone();
+
+        move |s| {
+            let new_progression = playback::Progression::new(&variant, s.value() as usize);
+
+            let max_window_count = std::cmp::max(new_progression.max_window_count(), 1);
+
+            slider.set_bounds(1.0, max_window_count as f64);
+
+            slider.set_value(f64::min(slider.value(), max_window_count as f64));
+
+            *progression.borrow_mut() = new_progression;
+
+            canvas.redraw();
+        }
+    });
+
+    let timeout: Rc<RefCell<Option<app::TimeoutHandle>>> = Rc::new(RefCell::new(None));
+
+    play_button.set_callback({
+        let timeout = timeout.clone();
+
+        move |b| {
+            if let Some(handle) = timeout.borrow_mut().take() {
+                app::remove_timeout3(handle);
+
+                b.set_label("@>");
+
+                return;
+            }
+
+            b.set_label("@||");
+
+            let handle = app::add_timeout3(playback_interval(&speed_choice), {
+                let progression = progression.clone();
 
-        second_button.emit(
-            sender.clone(),
-            Message::SelectedVariantStateChanged(first_idx),
-        );
+                let mut slider = slider.clone();
 
-        variant_state_pack.insert(second_button, first_idx as i32);
+                let mut canvas = canvas.clone();
 
-        variant_state_pack.insert(first_button, second_idx as i32);
+                let speed_choice = speed_choice.clone();
 
-        let variant_state_display_group = &mut group::Group::from_dyn_widget(
-            &self
-                .variant_state_display
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+                move |handle| {
+                    let window_count = playback::next_window_count(
+                        slider.value() as usize,
+                        progression.borrow().max_window_count(),
+                    );
 
-        let first_variant_state_display = &group::Group::from_dyn_widget(
-            &variant_state_display_group.child(first_idx as i32).unwrap(),
-        )
-        .unwrap();
+                    slider.set_value(window_count as f64);
 
-        let second_variant_state_display = &group::Group::from_dyn_widget(
-            &variant_state_display_group
-                .child(second_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+                    canvas.redraw();
 
-        variant_state_display_group.remove_by_index(second_idx as i32);
+                    app::repeat_timeout3(playback_interval(&speed_choice), handle);
+                }
+            });
 
-        variant_state_display_group.remove_by_index(first_idx as i32);
+            *timeout.borrow_mut() = Some(handle);
+        }
+    });
 
-        variant_state_display_group.insert(second_variant_state_display, first_idx as i32);
+    window.set_callback(move |w| {
+        if let Some(handle) = timeout.borrow_mut().take() {
+            app::remove_timeout3(handle);
+        }
 
-        variant_state_display_group.insert(first_variant_state_display, second_idx as i32);
+        w.hide();
+    });
 
-        sender.send(Message::SelectedVariantStateChanged(swap_with));
-    }
+    window.show();
+}
 
-    fn update_split_bounds(&mut self) {
-        let variant_state = &self.editor.layout.get_variants()[self.editor.selected_variant_idx]
-            .get_zones()[self.editor.selected_variant_state_idx];
+/// The seconds between window counts at the speed picked in `speed_choice`.
+fn playback_interval(speed_choice: &menu::Choice) -> f64 {
+    let speed = match playback::SPEEDS.get(speed_choice.value() as usize) {
+        Some(val) => *val,
+        None => 1.0,
+    };
 
-        let selected_zone1 = &variant_state[self.editor.selected_zone_idx1.unwrap()];
+    return 1.0 / speed;
+}
 
-        let max = match self.actions.selected_direction {
-            Direction::Horizontal => {
-                if let Some(idx) = self.editor.selected_zone_idx2 {
-                    let selected_zone2 = &variant_state[idx];
+fn draw_playback(
+    f: &frame::Frame,
+    progression: &playback::Progression,
+    window_count: usize,
+    gaps: Gaps,
+    monitor_size: (i32, i32),
+) {
+    draw::draw_rect_fill(f.x(), f.y(), f.w(), f.h(), Color::Background);
+
+    let zones = match progression.zones(window_count) {
+        Some(val) => val,
+        None => return,
+    };
+
+    let label_h = 20;
+
+    let (x, y, w, h) = geometry::fit_aspect_ratio(
+        monitor_size.0,
+        monitor_size.1,
+        f.x(),
+        f.y() + label_h,
+        f.w(),
+        f.h() - label_h,
+    );
 
-                    std::cmp::max(selected_zone1.right, selected_zone2.right)
-                        - std::cmp::min(selected_zone1.left, selected_zone2.left)
-                } else {
-                    selected_zone1.w()
-                }
-            }
-            Direction::Vertical => {
-                if let Some(idx) = self.editor.selected_zone_idx2 {
-                    let selected_zone2 = &variant_state[idx];
+    draw_zones(
+        zones,
+        gaps,
+        monitor_size.0 as f64,
+        monitor_size.1 as f64,
+        x,
+        y,
+        w,
+        h,
+    );
 
-                    std::cmp::max(selected_zone1.bottom, selected_zone2.bottom)
-                        - std::cmp::min(selected_zone1.top, selected_zone2.top)
-                } else {
-                    selected_zone1.h()
-                }
-            }
-        };
+    let mut label = match window_count {
+        1 => "1 window".to_string(),
+        _ => format!("{window_count} windows"),
+    };
 
-        self.actions.split_bound_max = Some(max);
+    if let Some(idx) = progression.variant_state_idx(window_count) {
+        label += format!(", variant state {}", idx + 1).as_str();
+    }
 
-        self.actions
-            .split_bounds_text
-            .set_label(format!("0 - {max}").as_str());
+    if progression.is_extended(window_count) {
+        label += " (extended)";
     }
 
-    fn disable_split(&mut self) {
-        self.actions.split_bound_max = None;
+    draw::set_draw_color(Color::Foreground);
 
-        self.actions.split_button.set_label("Split");
+    draw::set_font(enums::Font::Helvetica, 14);
 
-        self.actions.split_button.deactivate();
+    draw::draw_text2(
+        label.as_str(),
+        f.x(),
+        f.y(),
+        f.w(),
+        label_h,
+        Align::Left | Align::Inside,
+    );
+}
 
-        self.actions.split_bounds_text.set_label("");
-    }
+/// Adds a row to `parent` holding a label and an integer spinner.
+fn labelled_spinner(parent: &mut group::Flex, label: &str) -> misc::Spinner {
+    let mut row = group::Flex::default().row();
 
-    fn update_variant_state_display(
-        &mut self,
-        variant_idx: usize,
-        variant_state_idx: usize,
-        sender: &app::Sender<Message>,
-    ) {
-        let w = self.editor.layout.get_monitor_rect().w() as f64;
+    frame::Frame::default()
+        .with_label(label)
+        .with_align(Align::Left | Align::Inside);
 
-        let h = self.editor.layout.get_monitor_rect().h() as f64;
+    let mut spinner = misc::Spinner::default();
 
-        let variant = &self.editor.layout.get_variants()[variant_idx];
+    spinner.set_type(misc::SpinnerType::Int);
 
-        let variant_display_group = &mut group::Group::from_dyn_widget(
-            &self
-                .variant_state_display
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+    spinner.set_step(1.0);
 
-        variant_display_group.begin();
+    row.fixed(&spinner, 96);
 
-        let mut new_display = EditorWidgets::display_group_from_variant_state(
-            w,
-            h,
-            variant,
-            variant_state_idx,
-            sender,
-        );
+    row.end();
 
-        variant_display_group.end();
+    parent.fixed(&row, 32);
 
-        variant_display_group.remove(&new_display);
+    return spinner;
+}
 
-        if let Some(old_display) = variant_display_group.child(variant_state_idx as i32) {
-            WidgetBase::delete(old_display);
-        } else {
-            new_display.hide();
-        }
+/// A row of the zone inspector, with an input that sends `message` when Enter
+/// is pressed and the value as a share of the monitor next to it.
+fn zone_inspector_row(
+    parent: &mut group::Flex,
+    label: &str,
+    message: Message,
+    sender: &app::Sender<Message>,
+) -> (input::Input, frame::Frame) {
+    let mut row = group::Flex::default().row();
 
-        variant_display_group.insert(&new_display, variant_state_idx as i32);
+    let label_frame = frame::Frame::default()
+        .with_label(label)
+        .with_align(Align::Left | Align::Inside);
 
-        self.reset_zone_selection();
-    }
+    row.fixed(&label_frame, 64);
 
-    fn update_end_zone_idx_choice(&mut self, sender: &app::Sender<Message>) {
-        let variant = &self.editor.layout.get_variants()[self.editor.selected_variant_idx];
+    let mut input = input::Input::default();
 
-        let zones = &variant.get_zones()[variant.manual_zones_until() - 1];
+    input.set_tooltip("Pixels (640) or a percentage of the monitor (25%), applied with Enter");
 
-        self.end_behaviour_actions.zone_idx_choice.clear();
+    input.set_trigger(enums::CallbackTrigger::EnterKeyAlways);
 
-        for idx in 0..zones.len() {
-            self.end_behaviour_actions.zone_idx_choice.add_emit(
-                (idx + 1).to_string().as_str(),
-                Shortcut::None,
-                MenuFlag::Normal,
-                sender.clone(),
-                Message::EndZoneIdxChanged(idx),
-            );
-        }
+    input.emit(sender.clone(), message);
 
-        self.end_behaviour_actions
-            .zone_idx_choice
-            .set_value(variant.get_end_zone_idx() as i32);
-    }
+    input.deactivate();
 
-    fn add_new_variant(&mut self, variant: Variant, sender: &app::Sender<Message>) {
-        let mut variants_pack =
-            group::Pack::from_dyn_widget(&self.variant_list.child(0).unwrap()).unwrap();
+    let percentage = frame::Frame::default().with_align(Align::Right | Align::Inside);
 
-        let idx = variants_pack.children() as usize;
+    row.fixed(&percentage, 64);
 
-        let mut new_variant_button = button::Button::default().with_size(0, 20);
+    row.end();
 
-        new_variant_button.set_label_size(16);
+    parent.fixed(&row, 28);
 
-        new_variant_button.set_color(colors::html::DodgerBlue);
+    return (input, percentage);
+}
 
-        new_variant_button.set_frame(FrameType::NoBox);
+/// Asks for a template and its parameters, previewing the last manual
+/// variant state it generates.
+fn choose_template(
+    monitor_w: i32,
+    monitor_h: i32,
+) -> Option<(templates::Template, templates::TemplateParams)> {
+    let primary_screen = app::Screen::new(0).unwrap();
 
-        new_variant_button.set_label(idx.to_string().as_str());
+    let mut window = window::Window::new(
+        primary_screen.w() / 3,
+        primary_screen.h() / 4,
+        primary_screen.w() / 3,
+        primary_screen.h() / 2,
+        "New from template",
+    );
 
-        new_variant_button.emit(sender.clone(), Message::SelectedVariantChanged(idx));
+    window.make_modal(true);
 
-        variants_pack.add(&new_variant_button);
+    let mut widgets = group::Flex::default_fill().column();
 
-        let variant_state_selection = &mut self.variant_state_selection;
+    widgets.set_margin(4);
 
-        variant_state_selection.begin();
+    let mut template_choice = menu::Choice::default();
 
-        variant_state_selection.add(&EditorWidgets::create_state_selection_pack(
-            variant_state_selection,
-            &variant,
-            sender,
-        ));
+    for template in templates::Template::ALL {
+        template_choice.add_choice(template.name());
+    }
 
-        variant_state_selection.end();
+    template_choice.set_value(0);
 
-        self.variant_state_display.begin();
+    widgets.fixed(&template_choice, 32);
 
-        let mut new_variant_display_group = group::Group::default_fill();
+    let defaults = templates::TemplateParams::default();
 
-        self.variant_state_display.end();
+    let mut master_ratio_spinner = labelled_spinner(&mut widgets, "Master ratio");
 
-        let manual_zones_until = variant.manual_zones_until();
+    master_ratio_spinner.set_type(misc::SpinnerType::Float);
 
-        self.editor.layout.get_variants_mut().push(variant);
+    master_ratio_spinner.set_range(0.05, 0.95);
 
-        for i in 0..manual_zones_until {
-            self.update_variant_state_display(idx, i, sender);
-        }
+    master_ratio_spinner.set_step(0.05);
 
-        new_variant_display_group.hide();
-    }
+    master_ratio_spinner.set_value(defaults.master_ratio);
 
-    fn update_default_variant_label(&mut self, old_idx: usize, new_idx: usize) {
-        let variants_pack =
-            &mut group::Pack::from_dyn_widget(&self.variant_list.child(0).unwrap()).unwrap();
+    let mut master_count_spinner = labelled_spinner(&mut widgets, "Master count");
 
-        if let Some(button) = &mut variants_pack.child(old_idx as i32) {
-            button.set_label(old_idx.to_string().as_str());
-        }
+    master_count_spinner.set_range(1.0, 8.0);
 
-        variants_pack
-            .child(new_idx as i32)
-            .unwrap()
-            .set_label(format!("{new_idx} (default)").as_str());
-    }
+    master_count_spinner.set_value(defaults.master_count as f64);
 
-    fn preview_extend(
-        &mut self,
-        variant_idx: usize,
-        update_count: bool,
-        sender: &app::Sender<Message>,
-    ) {
-        if let None = self.buffers {
-            let layout_buffer = self.editor.layout.clone();
+    let mut max_states_spinner = labelled_spinner(&mut widgets, "Manual variant states");
 
-            let mut display_buffer =
-                EditorWidgets::create_variant_state_display(&self.editor.layout, sender);
+    max_states_spinner.set_range(1.0, 16.0);
 
-            display_buffer.set_pos(
-                self.variant_state_display.x(),
-                self.variant_state_display.y(),
-            );
+    max_states_spinner.set_value(defaults.max_manual_states as f64);
 
-            display_buffer.hide();
+    let mut canvas = frame::Frame::default();
 
-            let selection_buffer =
-                EditorWidgets::create_variant_state_selection(&self.editor.layout, sender);
+    let mut buttons = group::Flex::default().row();
 
-            let state_buttons_buffer =
-                EditorWidgets::create_variant_state_buttons(sender).below_of(&selection_buffer, 4);
+    frame::Frame::default();
 
-            let state_pack_buffer_h = selection_buffer.h() + state_buttons_buffer.h();
+    let mut cancel_button = button::Button::default().with_label("Cancel");
 
-            let mut variant_state_pack_buffer =
-                group::Pack::default().with_size(selection_buffer.w(), state_pack_buffer_h);
+    let mut create_button = button::ReturnButton::default().with_label("Create");
 
-            variant_state_pack_buffer.end();
+    buttons.fixed(&cancel_button, 96);
 
-            variant_state_pack_buffer.set_spacing(4);
+    buttons.fixed(&create_button, 96);
 
-            variant_state_pack_buffer.resize_callback(move |p, _, _, _, h| {
-                if h != state_pack_buffer_h {
-                    p.widget_resize(p.x(), p.y(), p.w(), state_pack_buffer_h);
-                }
-            });
+    buttons.end();
 
-            variant_state_pack_buffer.add(&selection_buffer);
+    widgets.fixed(&buttons, 32);
 
-            variant_state_pack_buffer.add(&state_buttons_buffer);
+    widgets.end();
 
-            variant_state_pack_buffer
-                .set_pos(self.variant_state_pack.x(), self.variant_state_pack.y());
+    window.end();
 
-            variant_state_pack_buffer.hide();
+    let selection = {
+        let template_choice = template_choice.clone();
 
-            self.buffers = Some(Buffers::new(
-                layout_buffer,
-                selection_buffer,
-                variant_state_pack_buffer,
-                display_buffer,
-            ));
+        let master_ratio_spinner = master_ratio_spinner.clone();
 
-            self.variant_state_pack.child(1).unwrap().deactivate();
+        let master_count_spinner = master_count_spinner.clone();
 
-            self.editor.layout.get_variants_mut()[variant_idx].update_from_zones();
+        let max_states_spinner = max_states_spinner.clone();
 
-            if self.editor.layout.get_variants()[variant_idx].using_from_zones() {
-                self.delete_variant_state(
-                    sender,
-                    Some(self.editor.layout.get_variants()[variant_idx].manual_zones_until()),
-                );
-            }
-        }
+        move || {
+            let template =
+                templates::Template::ALL[std::cmp::max(template_choice.value(), 0) as usize];
 
-        if update_count {
-            self.end_behaviour_actions.preview_count += 1;
+            let params = templates::TemplateParams {
+                master_ratio: master_ratio_spinner.value(),
+                master_count: master_count_spinner.value() as usize,
+                max_manual_states: max_states_spinner.value() as usize,
+            };
+
+            (template, params)
         }
+    };
 
-        self.editor.layout.get_variants_mut()[variant_idx].extend();
+    let preview = Rc::new(RefCell::new(Vec::new()));
 
-        self.new_variant_state(sender);
-    }
+    let mut update = {
+        let selection = selection.clone();
 
-    fn remove_extend_preview(&mut self) {
-        let Buffers {
-            layout,
-            variant_state_selection,
-            variant_state_pack,
-            variant_state_display,
-        } = match &self.buffers {
-            Some(buffers) => buffers.to_owned(),
-            None => return,
-        };
+        let preview = preview.clone();
 
-        self.buffers = None;
+        let mut master_ratio_spinner = master_ratio_spinner.clone();
 
-        self.editor.layout = layout;
+        let mut master_count_spinner = master_count_spinner.clone();
 
-        WidgetBase::delete(self.variant_state_display.to_owned());
+        let mut canvas = canvas.clone();
 
-        self.variant_state_display = variant_state_display;
+        move || {
+            let (template, params) = selection();
 
-        self.variant_state_display.show();
+            if template.uses_master_ratio() {
+                master_ratio_spinner.activate();
+            } else {
+                master_ratio_spinner.deactivate();
+            }
 
-        WidgetBase::delete(self.variant_state_selection.to_owned());
+            if template.uses_master_count() {
+                master_count_spinner.activate();
+            } else {
+                master_count_spinner.deactivate();
+            }
 
-        self.variant_state_selection = variant_state_selection;
+            let layout = template.generate(&params, monitor_w, monitor_h);
 
-        WidgetBase::delete(self.variant_state_pack.to_owned());
+            *preview.borrow_mut() = layout.get_variants()[0]
+                .get_zones()
+                .last()
+                .unwrap()
+                .to_owned();
 
-        self.variant_state_pack = variant_state_pack;
+            canvas.redraw();
+        }
+    };
 
-        self.variant_state_pack.show();
+    update();
 
-        self.variant_list.redraw();
-    }
+    canvas.draw({
+        let preview = preview.clone();
 
-    fn decrement_all_variant_state_buttons_after(
-        &mut self,
-        variant_idx: usize,
-        variant_state_idx: usize,
-        sender: &app::Sender<Message>,
-    ) {
-        let variant_state_pack = &mut group::Pack::from_dyn_widget(
-            &self
-                .variant_state_selection
-                .child(variant_idx as i32)
-                .unwrap(),
-        )
-        .unwrap();
+        move |f| {
+            draw::draw_rect_fill(f.x(), f.y(), f.w(), f.h(), Color::Background2);
 
-        for i in variant_state_idx as i32..variant_state_pack.children() {
-            let b = &mut button::Button::from_dyn_widget(&variant_state_pack.child(i).unwrap())
-                .unwrap();
+            let scale = f64::min(
+                (f.w() - 8) as f64 / monitor_w as f64,
+                (f.h() - 8) as f64 / monitor_h as f64,
+            );
 
-            b.set_label((i + 1).to_string().as_str());
+            let w = (monitor_w as f64 * scale).round() as i32;
 
-            b.emit(
-                sender.clone(),
-                Message::SelectedVariantStateChanged(i as usize),
-            );
-        }
-    }
-}
+            let h = (monitor_h as f64 * scale).round() as i32;
 
-pub struct LayoutEditorGUI {
-    app: app::App,
-    window: window::Window,
-    sender: app::Sender<Message>,
-    receiver: app::Receiver<Message>,
-    editor_widgets: Option<EditorWidgets>,
-}
+            let x = f.x() + (f.w() - w) / 2;
 
-impl LayoutEditorGUI {
-    pub fn create() -> Self {
-        initialize_colours();
+            let y = f.y() + (f.h() - h) / 2;
 
-        let app = app::App::default();
+            draw_zones(
+                &preview.borrow(),
+                Gaps::default(),
+                monitor_w as f64,
+                monitor_h as f64,
+                x,
+                y,
+                w,
+                h,
+            );
+        }
+    });
 
-        let widget_scheme = WidgetScheme::new(SchemeType::Fluent);
+    template_choice.set_callback({
+        let mut update = update.clone();
 
-        widget_scheme.apply();
+        move |_| update()
+    });
 
-        let (sender, receiver) = app::channel();
+    master_ratio_spinner.set_callback({
+        let mut update = update.clone();
 
-        return LayoutEditorGUI {
-            app,
-            window: create_window(),
-            sender,
-            receiver,
-            editor_widgets: None,
-        };
-    }
+        move |_| update()
+    });
 
-    pub fn edit_layout(&mut self, layout: Layout) {
-        self.window.begin();
+    master_count_spinner.set_callback({
+        let mut update = update.clone();
 
-        self.editor_widgets = Some(EditorWidgets::initialize(layout, &self.sender));
+        move |_| update()
+    });
 
-        self.window.end();
+    max_states_spinner.set_callback({
+        let mut update = update.clone();
 
-        // Test code
+        move |_| update()
+    });
 
-        if let Some(editor) = &mut self.editor_widgets {
-            editor
-                .variant_state_pack
-                .set_pos(self.window.w() / 2 - editor.variant_state_pack.w() / 2, 0);
+    let chosen = Rc::new(RefCell::new(None));
 
-            editor.variant_state_display.set_pos(
-                self.window.w() / 2 - editor.variant_state_display.w() / 2,
-                self.window.h() / 2 - editor.variant_state_display.h() / 2,
-            );
+    create_button.set_callback({
+        let chosen = chosen.clone();
 
-            editor
-                .actions
-                .widgets
-                .set_pos(self.window.w() - editor.actions.widgets.w() - 4, 0);
+        let mut window = window.clone();
 
-            editor
-                .variant_actions
-                .widgets
-                .set_pos(0, editor.variant_list.h() + 4);
+        move |_| {
+            *chosen.borrow_mut() = Some(selection());
 
-            editor.end_behaviour_actions.widgets.set_pos(
-                self.window.w() - editor.end_behaviour_actions.widgets.w(),
-                self.window.h() / 2,
-            );
+            window.hide();
         }
-    }
+    });
 
-    pub fn run(mut self) {
-        self.window.show();
+    cancel_button.set_callback({
+        let mut window = window.clone();
 
-        while self.app.wait() {
-            handle_events(&mut self);
-        }
+        move |_| window.hide()
+    });
+
+    window.show();
+
+    while window.shown() {
+        app::wait();
     }
+
+    return chosen.take();
 }
 
-fn initialize_colours() {
-    app::background(16, 16, 16);
+fn choose_file(chooser_type: dialog::NativeFileChooserType, filter: &str) -> Option<PathBuf> {
+    let save = matches!(chooser_type, dialog::NativeFileChooserType::BrowseSaveFile);
 
-    app::background2(32, 32, 32);
-}
+    let mut chooser = dialog::NativeFileChooser::new(chooser_type);
 
-fn create_window() -> window::Window {
-    let primary_screen = app::Screen::new(0).unwrap();
+    chooser.set_filter(filter);
 
-    let mut window = window::Window::new(
-        primary_screen.w() / 4,
-        primary_screen.h() / 4,
-        primary_screen.w() / 2,
-        primary_screen.h() / 2,
-        "PLACEHOLDER",
-    );
+    if save {
+        chooser.set_option(dialog::NativeFileChooserOptions::SaveAsConfirm);
+    }
 
-    window.make_resizable(true);
+    chooser.show();
 
-    window.end();
+    let path = chooser.filename();
 
-    return window;
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+
+    return Some(path);
 }
@@ -1,5 +1,351 @@
-fn main() {
-    let app = himewm_layout_editor::LayoutEditorGUI::create();
+use himewm_layout::Layout;
+use himewm_layout_editor::{
+    export,
+    file::{self, LayoutFileError},
+    gaps::GapSettings,
+    rescale, text, LayoutEditorGUI,
+};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+const USAGE: &str = "\
+Usage: himewm_layout_editor [--readonly] [<file.json> | --new <WIDTH>x<HEIGHT>]
+       himewm_layout_editor export <file.json> <image.svg|image.png> [--extend <N>]
+       himewm_layout_editor print <file.json> [--variant <N>] [--state <N>] [--width <N>] [--ascii]
+
+Options:
+  --new <WIDTH>x<HEIGHT>  Start a new layout for a monitor of the given size
+  --readonly              Open the layout for inspection only
+  -h, --help              Print this message
+
+Export options:
+  --extend <N>            Also render N variant states past the manual ones
+
+Print options:
+  --variant <N>           The variant to print, counting from 0 (default: the
+                          default variant)
+  --state <N>             The variant state to print, counting from 1
+                          (default: every manual variant state)
+  --width <N>             The width of the drawing in characters
+  --ascii                 Draw with ASCII characters only";
+
+const EXIT_USAGE: u8 = 2;
+
+const EXIT_UNREADABLE_FILE: u8 = 3;
+
+const EXIT_INVALID_LAYOUT: u8 = 4;
+
+const EXIT_WRITE_FAILED: u8 = 5;
+
+enum Source {
+    Default,
+    File(PathBuf),
+    New(i32, i32),
+}
+
+struct Args {
+    source: Source,
+    readonly: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut source = Source::Default;
+
+    let mut readonly = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--readonly" => readonly = true,
+            "--new" => {
+                let size = args
+                    .next()
+                    .ok_or("--new requires a size, e.g. --new 1920x1080")?;
+
+                let (w, h) =
+                    rescale::parse_monitor_size(&size).ok_or(format!("invalid size '{size}'"))?;
+
+                if let Source::Default = source {
+                    source = Source::New(w, h);
+                } else {
+                    return Err("only one of <file.json> and --new may be given".to_string());
+                }
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => {
+                if let Source::Default = source {
+                    source = Source::File(PathBuf::from(arg));
+                } else {
+                    return Err("only one of <file.json> and --new may be given".to_string());
+                }
+            }
+        }
+    }
+
+    return Ok(Some(Args { source, readonly }));
+}
+
+struct ExportArgs {
+    layout_path: PathBuf,
+    image_path: PathBuf,
+    extended_states: usize,
+}
+
+fn parse_export_args(mut args: impl Iterator<Item = String>) -> Result<Option<ExportArgs>, String> {
+    let mut paths = Vec::new();
+
+    let mut extended_states = 0;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--extend" => extended_states = parse_number("--extend", args.next())?,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    let [layout_path, image_path]: [PathBuf; 2] = paths
+        .try_into()
+        .map_err(|_| "export requires a layout file and an image file".to_string())?;
+
+    if let None = export::ImageFormat::from_path(&image_path) {
+        return Err(format!(
+            "cannot tell the image format of {}, use .svg or .png",
+            image_path.display()
+        ));
+    }
+
+    return Ok(Some(ExportArgs {
+        layout_path,
+        image_path,
+        extended_states,
+    }));
+}
+
+fn parse_number(option: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or(format!("{option} requires a number"))?;
+
+    return value
+        .parse()
+        .map_err(|_| format!("invalid number '{value}' for {option}"));
+}
+
+fn read_layout(path: &Path) -> Result<(Layout, GapSettings), ExitCode> {
+    return file::read_layout_with_gaps(path).map_err(|err| {
+        eprintln!("error: could not open {}: {err}", path.display());
+
+        match err {
+            LayoutFileError::Io(_) => ExitCode::from(EXIT_UNREADABLE_FILE),
+            LayoutFileError::Json(_) | LayoutFileError::InvalidGaps => {
+                ExitCode::from(EXIT_INVALID_LAYOUT)
+            }
+        }
+    });
+}
+
+fn run_export(args: impl Iterator<Item = String>) -> ExitCode {
+    let args = match parse_export_args(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    let (layout, gaps) = match read_layout(&args.layout_path) {
+        Ok(val) => val,
+        Err(code) => return code,
+    };
+
+    if let Err(err) = export::export_image(&layout, &gaps, &args.image_path, args.extended_states) {
+        eprintln!(
+            "error: could not write {}: {err}",
+            args.image_path.display()
+        );
+
+        return ExitCode::from(EXIT_WRITE_FAILED);
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+struct PrintArgs {
+    layout_path: PathBuf,
+    variant_idx: Option<usize>,
+    variant_state_idx: Option<usize>,
+    columns: usize,
+    ascii: bool,
+}
+
+fn parse_print_args(mut args: impl Iterator<Item = String>) -> Result<Option<PrintArgs>, String> {
+    let mut layout_path = None;
+
+    let mut variant_idx = None;
+
+    let mut variant_state_idx = None;
+
+    let mut columns = text::DEFAULT_COLUMNS;
+
+    let mut ascii = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--variant" => variant_idx = Some(parse_number("--variant", args.next())?),
+            "--state" => {
+                let state = parse_number("--state", args.next())?;
+
+                if state == 0 {
+                    return Err("variant states are counted from 1".to_string());
+                }
+
+                variant_state_idx = Some(state - 1);
+            }
+            "--width" => columns = parse_number("--width", args.next())?,
+            "--ascii" => ascii = true,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ => {
+                if let Some(_) = layout_path {
+                    return Err("print takes a single layout file".to_string());
+                }
+
+                layout_path = Some(PathBuf::from(arg));
+            }
+        }
+    }
+
+    let layout_path = layout_path.ok_or("print requires a layout file")?;
+
+    return Ok(Some(PrintArgs {
+        layout_path,
+        variant_idx,
+        variant_state_idx,
+        columns,
+        ascii,
+    }));
+}
+
+fn run_print(args: impl Iterator<Item = String>) -> ExitCode {
+    let args = match parse_print_args(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    let (layout, _) = match read_layout(&args.layout_path) {
+        Ok(val) => val,
+        Err(code) => return code,
+    };
+
+    let variant_idx = args.variant_idx.unwrap_or(layout.default_variant_idx());
+
+    let variant = match layout.get_variants().get(variant_idx) {
+        Some(val) => val,
+        None => {
+            eprintln!(
+                "error: there is no variant {variant_idx}, the layout has {}",
+                layout.variants_len()
+            );
+
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    let variant_state_idxs = match args.variant_state_idx {
+        Some(idx) if idx >= variant.manual_zones_until() => {
+            eprintln!(
+                "error: there is no variant state {}, variant {variant_idx} has {}",
+                idx + 1,
+                variant.manual_zones_until()
+            );
+
+            return ExitCode::from(EXIT_USAGE);
+        }
+        Some(idx) => idx..idx + 1,
+        None => 0..variant.manual_zones_until(),
+    };
+
+    for idx in variant_state_idxs {
+        let grid = text::ZoneGrid::from_layout(&layout, variant_idx, idx)
+            .unwrap()
+            .with_columns(args.columns)
+            .with_ascii(args.ascii);
+
+        if let None = args.variant_state_idx {
+            println!("State {}", idx + 1);
+        }
+
+        print!("{grid}");
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("export") => return run_export(args.skip(1)),
+        Some("print") => return run_print(args.skip(1)),
+        _ => {}
+    }
+
+    let args = match parse_args(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    let (layout, path) = match args.source {
+        Source::Default => (None, None),
+        Source::New(w, h) => (Some((Layout::new(w, h), GapSettings::default())), None),
+        Source::File(path) => match read_layout(&path) {
+            Ok(layout) => (Some(layout), Some(path)),
+            Err(code) => return code,
+        },
+    };
+
+    let mut app = LayoutEditorGUI::create();
+
+    app.set_readonly(args.readonly);
+
+    match layout {
+        Some((layout, gaps)) => {
+            app.edit_layout_with_gaps(layout, gaps);
+
+            if let Some(path) = path {
+                app.set_file_path(path);
+            }
+        }
+        None => app.new_layout(),
+    }
 
     app.run();
+
+    return ExitCode::SUCCESS;
 }
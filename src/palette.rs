@@ -0,0 +1,54 @@
+/// Scores how well `query` matches `candidate`, or `None` if the characters
+/// of `query` do not all appear in `candidate` in order. Case is ignored.
+/// Matches at the start of words and runs of consecutive characters score
+/// higher, so that `sa` ranks "Save As" above "State".
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.chars().collect();
+
+    let mut score = 0;
+
+    let mut position = 0;
+
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        let found = (position..candidate.len())
+            .find(|i| candidate[*i].to_lowercase().eq(query_char.to_lowercase()))?;
+
+        let word_start = found == 0 || !candidate[found - 1].is_alphanumeric();
+
+        score += match (word_start, previous_match) {
+            (_, Some(previous)) if previous + 1 == found => 5,
+            (true, _) => 8,
+            _ => 1,
+        };
+
+        // Skipping characters costs a little, so tighter matches win
+        score -= (found - position) as i32 / 4;
+
+        previous_match = Some(found);
+
+        position = found + 1;
+    }
+
+    return Some(score);
+}
+
+/// The indices of the candidates that match `query`, best first. Of the
+/// candidates that score the same, shorter ones come first, since less of
+/// them is left unmatched. An empty query keeps every candidate in order.
+pub fn rank(query: &str, candidates: &[String]) -> Vec<usize> {
+    if query.trim().is_empty() {
+        return (0..candidates.len()).collect();
+    }
+
+    let mut scored: Vec<(i32, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| fuzzy_score(query, candidate).map(|score| (score, i)))
+        .collect();
+
+    scored.sort_by_key(|(score, i)| (-score, candidates[*i].len(), *i));
+
+    return scored.into_iter().map(|(_, i)| i).collect();
+}
@@ -0,0 +1,121 @@
+use serde_json::{json, Value};
+use std::{fs, io, path::PathBuf};
+
+/// The narrowest the sidebar and the inspector can be made.
+pub const MIN_PANEL_WIDTH: i32 = 96;
+
+/// The narrowest the canvas between the sidebar and the inspector can be
+/// squeezed to.
+pub const MIN_CANVAS_WIDTH: i32 = 160;
+
+/// The size of the main window and of the panels on either side of the
+/// canvas, kept between sessions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PanelSizes {
+    pub window_w: i32,
+    pub window_h: i32,
+    pub sidebar_w: i32,
+    pub inspector_w: i32,
+}
+
+impl Default for PanelSizes {
+    fn default() -> Self {
+        return PanelSizes {
+            window_w: 1280,
+            window_h: 800,
+            sidebar_w: 160,
+            inspector_w: 360,
+        };
+    }
+}
+
+impl PanelSizes {
+    pub fn config_path() -> Option<PathBuf> {
+        let project_dirs = directories::ProjectDirs::from("", "", "himewm_layout_editor")?;
+
+        return Some(project_dirs.config_dir().join("panels.json"));
+    }
+
+    /// Reads the sizes saved by the last session, if there are any that can
+    /// be read.
+    pub fn load() -> Option<Self> {
+        let json = fs::read_to_string(Self::config_path()?).ok()?;
+
+        return Self::from_json(&json);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = match Self::config_path() {
+            Some(val) => val,
+            None => return Ok(()),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        return fs::write(path, self.to_json());
+    }
+
+    /// Parses an object of sizes in pixels. Sizes that are left out keep
+    /// their defaults.
+    pub fn from_json(json: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(json).ok()?;
+
+        let object = value.as_object()?;
+
+        let mut sizes = PanelSizes::default();
+
+        for (key, size) in [
+            ("window_width", &mut sizes.window_w),
+            ("window_height", &mut sizes.window_h),
+            ("sidebar_width", &mut sizes.sidebar_w),
+            ("inspector_width", &mut sizes.inspector_w),
+        ] {
+            if let Some(val) = object.get(key) {
+                *size = i32::try_from(val.as_i64()?).ok()?;
+            }
+        }
+
+        return Some(sizes);
+    }
+
+    pub fn to_json(&self) -> String {
+        let value = json!({
+            "window_width": self.window_w,
+            "window_height": self.window_h,
+            "sidebar_width": self.sidebar_w,
+            "inspector_width": self.inspector_w,
+        });
+
+        return format!("{value:#}");
+    }
+
+    /// Shrinks the sidebar and the inspector in proportion, so that together
+    /// they leave at least `MIN_CANVAS_WIDTH` of `available_w` for the
+    /// canvas.
+    pub fn fitted(&self, available_w: i32) -> Self {
+        let mut sidebar_w = std::cmp::max(self.sidebar_w, MIN_PANEL_WIDTH);
+
+        let mut inspector_w = std::cmp::max(self.inspector_w, MIN_PANEL_WIDTH);
+
+        let panels_w = std::cmp::max(available_w - MIN_CANVAS_WIDTH, 2 * MIN_PANEL_WIDTH);
+
+        if sidebar_w + inspector_w > panels_w {
+            let sidebar_share = sidebar_w as f64 / (sidebar_w + inspector_w) as f64;
+
+            sidebar_w = std::cmp::max(
+                (panels_w as f64 * sidebar_share).round() as i32,
+                MIN_PANEL_WIDTH,
+            );
+
+            inspector_w = std::cmp::max(panels_w - sidebar_w, MIN_PANEL_WIDTH);
+        }
+
+        return PanelSizes {
+            sidebar_w,
+            inspector_w,
+            ..*self
+        };
+    }
+}
@@ -0,0 +1,119 @@
+use himewm_layout::*;
+
+/// The most windows playback can step through.
+pub const MAX_WINDOW_COUNT: usize = 32;
+
+/// How many more windows than its manual variant states have zones for a
+/// variant is played through by default.
+pub const EXTRA_WINDOWS: usize = 4;
+
+/// The playback speeds offered, in windows per second.
+pub const SPEEDS: [f64; 5] = [0.5, 1.0, 2.0, 4.0, 8.0];
+
+/// The variant states windows are tiled into as they open one after the
+/// other: the manual variant states of the variant, followed by as many
+/// extended ones as are needed for `max_window_count` windows.
+#[derive(Clone)]
+pub struct Progression {
+    variant: Variant,
+    manual_zones_until: usize,
+    variant_state_idxs: Vec<usize>,
+}
+
+impl Progression {
+    pub fn new(variant: &Variant, max_window_count: usize) -> Self {
+        let manual_zones_until = variant.manual_zones_until();
+
+        let mut variant = variant.clone();
+
+        variant.update_from_zones();
+
+        let mut variant_state_idxs = Vec::new();
+
+        for window_count in 1..=max_window_count {
+            let mut extended = 0;
+
+            // Each window count uses the first variant state with a zone for
+            // every window
+            let variant_state_idx = loop {
+                if let Some(idx) = variant
+                    .get_zones()
+                    .iter()
+                    .position(|zones| zones.len() >= window_count)
+                {
+                    break Some(idx);
+                }
+
+                // Extending adds at least one zone unless the variant cannot
+                // be extended at all
+                if extended == max_window_count {
+                    break None;
+                }
+
+                variant.extend();
+
+                extended += 1;
+            };
+
+            match variant_state_idx {
+                Some(idx) => variant_state_idxs.push(idx),
+                None => break,
+            }
+        }
+
+        return Progression {
+            variant,
+            manual_zones_until,
+            variant_state_idxs,
+        };
+    }
+
+    /// The number of windows the progression goes up to, which is less than
+    /// requested if the variant runs out of zones.
+    pub fn max_window_count(&self) -> usize {
+        return self.variant_state_idxs.len();
+    }
+
+    /// The index of the variant state `window_count` windows are tiled into.
+    pub fn variant_state_idx(&self, window_count: usize) -> Option<usize> {
+        return self
+            .variant_state_idxs
+            .get(window_count.checked_sub(1)?)
+            .copied();
+    }
+
+    pub fn zones(&self, window_count: usize) -> Option<&[Zone]> {
+        let idx = self.variant_state_idx(window_count)?;
+
+        return Some(&self.variant.get_zones()[idx]);
+    }
+
+    /// Whether the variant state for `window_count` windows comes from
+    /// extending the variant rather than from its manual variant states.
+    pub fn is_extended(&self, window_count: usize) -> bool {
+        return match self.variant_state_idx(window_count) {
+            Some(idx) => idx >= self.manual_zones_until,
+            None => false,
+        };
+    }
+}
+
+/// The window count playback shows after `window_count`, starting over from
+/// a single window once `max_window_count` is reached.
+pub fn next_window_count(window_count: usize, max_window_count: usize) -> usize {
+    if window_count >= max_window_count {
+        return 1;
+    }
+
+    return window_count + 1;
+}
+
+/// The default number of windows to play `variant` through.
+pub fn default_max_window_count(variant: &Variant) -> usize {
+    let manual_zone_count = match variant.get_zones()[..variant.manual_zones_until()].last() {
+        Some(zones) => zones.len(),
+        None => 0,
+    };
+
+    return std::cmp::min(manual_zone_count + EXTRA_WINDOWS, MAX_WINDOW_COUNT);
+}
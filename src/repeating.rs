@@ -0,0 +1,62 @@
+use himewm_layout::{Direction, EndTilingBehaviour, Variant};
+
+#[derive(Clone, Copy)]
+pub struct RepeatingSplitSpec {
+    pub direction: Direction,
+    pub split_ratio: f64,
+    pub zone_idx: usize,
+    pub swap: bool,
+}
+
+impl RepeatingSplitSpec {
+    pub fn following(previous: Option<&RepeatingSplitSpec>) -> Self {
+        let direction = match previous {
+            Some(split) => split.direction.other(),
+            None => Direction::Horizontal,
+        };
+
+        return RepeatingSplitSpec {
+            direction,
+            split_ratio: 0.5,
+            zone_idx: 0,
+            swap: false,
+        };
+    }
+}
+
+pub fn repeating_splits(variant: &Variant) -> Vec<RepeatingSplitSpec> {
+    return match variant.get_end_tiling_behaviour() {
+        EndTilingBehaviour::Repeating {
+            splits,
+            zone_idx: _,
+        } => splits
+            .iter()
+            .map(|split| RepeatingSplitSpec {
+                direction: split.get_direction(),
+                split_ratio: split.get_split_ratio(),
+                zone_idx: split.get_zone_idx(),
+                swap: split.get_swap(),
+            })
+            .collect(),
+        EndTilingBehaviour::Directional { .. } => Vec::new(),
+    };
+}
+
+/// Replaces the repeating splits of `variant`, keeping the zone new windows
+/// are placed in.
+pub fn set_repeating_splits(variant: &mut Variant, splits: &[RepeatingSplitSpec]) {
+    let end_zone_idx = variant.get_end_zone_idx();
+
+    variant.set_end_tiling_behaviour(EndTilingBehaviour::default_repeating());
+
+    variant.set_end_zone_idx(end_zone_idx);
+
+    for split in splits {
+        variant.add_repeating_split(
+            split.direction,
+            split.split_ratio,
+            split.zone_idx,
+            split.swap,
+        );
+    }
+}
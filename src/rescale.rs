@@ -0,0 +1,88 @@
+use himewm_layout::*;
+
+/// Resolutions offered when previewing a layout on other monitors.
+pub const PREVIEW_SIZES: [(i32, i32); 4] = [(1920, 1080), (2560, 1440), (3440, 1440), (1080, 1920)];
+
+pub fn parse_monitor_size(size: &str) -> Option<(i32, i32)> {
+    let (w, h) = size.split_once(['x', 'X'])?;
+
+    let w: i32 = w.trim().parse().ok()?;
+
+    let h: i32 = h.trim().parse().ok()?;
+
+    if w <= 0 || h <= 0 {
+        return None;
+    }
+
+    return Some((w, h));
+}
+
+/// Maps a coordinate on an axis of length `from` to one of length `to`.
+/// Every coordinate is rounded on its own, so zones that shared an edge
+/// before rescaling still share it afterwards, and the monitor borders map
+/// exactly onto the new borders.
+fn rescale_coordinate(value: i32, from: i32, to: i32) -> i32 {
+    return ((value as i64 * to as i64) as f64 / from as f64).round() as i32;
+}
+
+pub fn rescale_zones(zones: &mut [Zone], from: (i32, i32), to: (i32, i32)) {
+    for zone in zones {
+        zone.left = rescale_coordinate(zone.left, from.0, to.0);
+
+        zone.top = rescale_coordinate(zone.top, from.1, to.1);
+
+        zone.right = rescale_coordinate(zone.right, from.0, to.0);
+
+        zone.bottom = rescale_coordinate(zone.bottom, from.1, to.1);
+    }
+}
+
+/// Rescales every variant state of `variant`, along with the zones
+/// directional end tiling compresses from. Repeating splits are ratios and
+/// are left as they are.
+pub fn rescale_variant(variant: &mut Variant, from: (i32, i32), to: (i32, i32)) {
+    for zones in variant.get_zones_mut().iter_mut() {
+        rescale_zones(zones, from, to);
+    }
+
+    if let EndTilingBehaviour::Directional {
+        direction,
+        from_zones: Some(from_zones),
+        zone_idx,
+    } = variant.get_end_tiling_behaviour()
+    {
+        let direction = direction.to_owned();
+
+        let zone_idx = zone_idx.to_owned();
+
+        let mut from_zones = from_zones.to_owned();
+
+        rescale_zones(&mut from_zones, from, to);
+
+        variant.set_end_tiling_behaviour(EndTilingBehaviour::Directional {
+            direction,
+            from_zones: Some(from_zones),
+            zone_idx,
+        });
+    }
+}
+
+/// A copy of `layout` for a monitor of `w` by `h` pixels, with every zone
+/// scaled proportionally.
+pub fn rescale_layout(layout: &Layout, w: i32, h: i32) -> Layout {
+    let from = (layout.get_monitor_rect().w(), layout.get_monitor_rect().h());
+
+    let mut variants = layout.get_variants().to_owned();
+
+    for variant in &mut variants {
+        rescale_variant(variant, from, (w, h));
+    }
+
+    let mut rescaled = Layout::new(w, h);
+
+    *rescaled.get_variants_mut() = variants;
+
+    rescaled.set_default_variant_idx(layout.default_variant_idx());
+
+    return rescaled;
+}
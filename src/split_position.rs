@@ -0,0 +1,40 @@
+/// Parses the position of a split within a zone that is `extent` pixels
+/// wide or tall. Accepts an absolute pixel offset (`640`), a percentage
+/// (`50%`), a fraction (`1/3`), a ratio between the resulting zones (`2:1`)
+/// or a decimal proportion (`0.618`). Positions outside the zone, such as
+/// `150%` or `-3`, are rejected.
+pub fn parse_split_position(input: &str, extent: i32) -> Option<i32> {
+    let input = input.trim();
+
+    let proportion = if let Some(percentage) = input.strip_suffix('%') {
+        percentage.trim().parse::<f64>().ok()? / 100.0
+    } else if let Some((numerator, denominator)) = input.split_once('/') {
+        let numerator: f64 = numerator.trim().parse().ok()?;
+
+        let denominator: f64 = denominator.trim().parse().ok()?;
+
+        numerator / denominator
+    } else if let Some((first, second)) = input.split_once(':') {
+        let first: f64 = first.trim().parse().ok()?;
+
+        let second: f64 = second.trim().parse().ok()?;
+
+        first / (first + second)
+    } else if input.contains('.') {
+        input.parse::<f64>().ok()?
+    } else {
+        let position: i32 = input.parse().ok()?;
+
+        if position < 0 || position > extent {
+            return None;
+        }
+
+        return Some(position);
+    };
+
+    if !proportion.is_finite() || proportion < 0.0 || proportion > 1.0 {
+        return None;
+    }
+
+    return Some((extent as f64 * proportion).round() as i32);
+}
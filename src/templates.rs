@@ -0,0 +1,418 @@
+use himewm_layout::*;
+
+/// The side of the monitor the master area is placed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// A well-known tiling arrangement that a layout can be generated from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Template {
+    /// Master windows on one side, the rest stacked on the other.
+    MasterStack(Side),
+    /// Master windows in the middle, the rest stacked on either side.
+    CenteredMaster,
+    /// Every window splits the last one, turning clockwise.
+    Spiral,
+    /// Every window splits the last one, always towards the bottom right.
+    Dwindle,
+    /// Rows of equal columns, filled row by row.
+    Grid,
+    /// Equal columns side by side.
+    Columns,
+    /// A single zone covering the monitor.
+    Monocle,
+}
+
+impl Template {
+    pub const ALL: [Template; 10] = [
+        Template::MasterStack(Side::Left),
+        Template::MasterStack(Side::Right),
+        Template::MasterStack(Side::Top),
+        Template::MasterStack(Side::Bottom),
+        Template::CenteredMaster,
+        Template::Spiral,
+        Template::Dwindle,
+        Template::Grid,
+        Template::Columns,
+        Template::Monocle,
+    ];
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            Template::MasterStack(Side::Left) => "Master and stack (master left)",
+            Template::MasterStack(Side::Right) => "Master and stack (master right)",
+            Template::MasterStack(Side::Top) => "Master and stack (master top)",
+            Template::MasterStack(Side::Bottom) => "Master and stack (master bottom)",
+            Template::CenteredMaster => "Centered master",
+            Template::Spiral => "Spiral",
+            Template::Dwindle => "Dwindle",
+            Template::Grid => "Grid",
+            Template::Columns => "Columns",
+            Template::Monocle => "Monocle",
+        };
+    }
+
+    pub fn uses_master_ratio(self) -> bool {
+        return matches!(
+            self,
+            Template::MasterStack(_)
+                | Template::CenteredMaster
+                | Template::Spiral
+                | Template::Dwindle
+        );
+    }
+
+    pub fn uses_master_count(self) -> bool {
+        return matches!(self, Template::MasterStack(_) | Template::CenteredMaster);
+    }
+
+    /// Generates a layout with a single variant, holding one manual variant
+    /// state per window count up to `params.max_manual_states`. Windows
+    /// beyond that are tiled into the last zone. Monocle layouts only ever
+    /// have the one variant state.
+    pub fn generate(self, params: &TemplateParams, monitor_w: i32, monitor_h: i32) -> Layout {
+        let params = params.clamped();
+
+        let mut layout = Layout::new(monitor_w, monitor_h);
+
+        let variant = &mut layout.get_variants_mut()[0];
+
+        let window_counts = match self {
+            Template::Monocle => 1,
+            _ => params.max_manual_states,
+        };
+
+        for window_count in 2..=window_counts {
+            variant.new_zone_vec(monitor_w, monitor_h);
+
+            let state = window_count - 1;
+
+            match self {
+                Template::MasterStack(side) => {
+                    master_stack(variant, state, side, window_count, &params)
+                }
+                Template::CenteredMaster => centered_master(variant, state, window_count, &params),
+                Template::Spiral => spiral(variant, state, window_count, &params, true),
+                Template::Dwindle => spiral(variant, state, window_count, &params, false),
+                Template::Grid => grid(variant, state, window_count),
+                Template::Columns => {
+                    split_evenly(variant, state, 0, Direction::Horizontal, window_count)
+                }
+                Template::Monocle => {}
+            }
+        }
+
+        let zone_count = variant.get_zones()[variant.manual_zones_until() - 1].len();
+
+        let direction = match self {
+            Template::MasterStack(Side::Top) | Template::MasterStack(Side::Bottom) => {
+                Direction::Horizontal
+            }
+            Template::MasterStack(_) | Template::CenteredMaster | Template::Grid => {
+                Direction::Vertical
+            }
+            Template::Spiral | Template::Dwindle => match zone_count % 2 {
+                0 => Direction::Vertical,
+                _ => Direction::Horizontal,
+            },
+            Template::Columns | Template::Monocle => Direction::Horizontal,
+        };
+
+        variant.set_end_tiling_behaviour(EndTilingBehaviour::Directional {
+            direction,
+            from_zones: None,
+            zone_idx: zone_count - 1,
+        });
+
+        return layout;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TemplateParams {
+    /// The share of the monitor taken up by the master area, or by the first
+    /// window of a spiral.
+    pub master_ratio: f64,
+    /// How many windows share the master area.
+    pub master_count: usize,
+    /// The number of windows the last manual variant state is made for.
+    pub max_manual_states: usize,
+}
+
+impl Default for TemplateParams {
+    fn default() -> Self {
+        return TemplateParams {
+            master_ratio: 0.5,
+            master_count: 1,
+            max_manual_states: 5,
+        };
+    }
+}
+
+impl TemplateParams {
+    fn clamped(&self) -> Self {
+        return TemplateParams {
+            master_ratio: self.master_ratio.clamp(0.05, 0.95),
+            master_count: std::cmp::max(self.master_count, 1),
+            max_manual_states: std::cmp::max(self.max_manual_states, 1),
+        };
+    }
+}
+
+fn extent(zone: &Zone, direction: Direction) -> (i32, i32) {
+    return match direction {
+        Direction::Horizontal => (zone.left, zone.right),
+        Direction::Vertical => (zone.top, zone.bottom),
+    };
+}
+
+/// Splits a zone at `ratio` of its width or height, returning the index of
+/// the right or bottom part, or `None` if the zone is too small to split.
+fn split_at_ratio(
+    variant: &mut Variant,
+    state: usize,
+    zone_idx: usize,
+    direction: Direction,
+    ratio: f64,
+) -> Option<usize> {
+    let (start, end) = extent(&variant.get_zones()[state][zone_idx], direction);
+
+    let position = start + ((end - start) as f64 * ratio).round() as i32;
+
+    if position <= start || position >= end {
+        return None;
+    }
+
+    let split_direction = match direction {
+        Direction::Horizontal => SplitDirection::Horizontal(position),
+        Direction::Vertical => SplitDirection::Vertical(position),
+    };
+
+    variant.split(state, zone_idx, split_direction);
+
+    return Some(variant.get_zones()[state].len() - 1);
+}
+
+/// Splits a zone into `parts` equal zones. The first one keeps the index of
+/// the zone, the others are appended in order.
+fn split_evenly(
+    variant: &mut Variant,
+    state: usize,
+    zone_idx: usize,
+    direction: Direction,
+    parts: usize,
+) {
+    let mut current = zone_idx;
+
+    for remaining in (2..=parts).rev() {
+        current = match split_at_ratio(variant, state, current, direction, 1.0 / remaining as f64) {
+            Some(val) => val,
+            None => return,
+        };
+    }
+}
+
+/// Reorders the zones of a variant state by `key`, which decides the order
+/// windows are placed in.
+fn sort_zones<K: Ord>(variant: &mut Variant, state: usize, key: impl Fn(&Zone) -> K) {
+    let len = variant.get_zones()[state].len();
+
+    for i in 0..len {
+        let smallest = (i..len)
+            .min_by_key(|j| key(&variant.get_zones()[state][*j]))
+            .unwrap();
+
+        if smallest != i {
+            variant.swap_zones(state, i, smallest);
+        }
+    }
+}
+
+fn master_stack(
+    variant: &mut Variant,
+    state: usize,
+    side: Side,
+    window_count: usize,
+    params: &TemplateParams,
+) {
+    let stack_direction = match side {
+        Side::Left | Side::Right => Direction::Vertical,
+        Side::Top | Side::Bottom => Direction::Horizontal,
+    };
+
+    if window_count <= params.master_count {
+        split_evenly(variant, state, 0, stack_direction, window_count);
+
+        return;
+    }
+
+    let (direction, ratio) = match side {
+        Side::Left => (Direction::Horizontal, params.master_ratio),
+        Side::Right => (Direction::Horizontal, 1.0 - params.master_ratio),
+        Side::Top => (Direction::Vertical, params.master_ratio),
+        Side::Bottom => (Direction::Vertical, 1.0 - params.master_ratio),
+    };
+
+    let other = match split_at_ratio(variant, state, 0, direction, ratio) {
+        Some(val) => val,
+        None => return,
+    };
+
+    let (master, stack) = match side {
+        Side::Left | Side::Top => (0, other),
+        Side::Right | Side::Bottom => (other, 0),
+    };
+
+    split_evenly(
+        variant,
+        state,
+        stack,
+        stack_direction,
+        window_count - params.master_count,
+    );
+
+    split_evenly(variant, state, master, stack_direction, params.master_count);
+
+    let master_left = variant.get_zones()[state][master].left;
+
+    let master_top = variant.get_zones()[state][master].top;
+
+    // Masters first, then the stack, each from the top left
+    sort_zones(variant, state, |zone| {
+        let in_stack = match side {
+            Side::Left | Side::Right => zone.left != master_left,
+            Side::Top | Side::Bottom => zone.top != master_top,
+        };
+
+        (in_stack, zone.top, zone.left)
+    });
+}
+
+/// With a single stack window, the master is placed on the left. Beyond that
+/// the stack windows alternate between the right and the left of the master.
+fn centered_master(
+    variant: &mut Variant,
+    state: usize,
+    window_count: usize,
+    params: &TemplateParams,
+) {
+    if window_count <= params.master_count + 1 {
+        master_stack(variant, state, Side::Left, window_count, params);
+
+        return;
+    }
+
+    let side_ratio = (1.0 - params.master_ratio) / 2.0;
+
+    let master = match split_at_ratio(variant, state, 0, Direction::Horizontal, side_ratio) {
+        Some(val) => val,
+        None => return,
+    };
+
+    let right = match split_at_ratio(
+        variant,
+        state,
+        master,
+        Direction::Horizontal,
+        params.master_ratio / (1.0 - side_ratio),
+    ) {
+        Some(val) => val,
+        None => return,
+    };
+
+    let stack_count = window_count - params.master_count;
+
+    split_evenly(
+        variant,
+        state,
+        right,
+        Direction::Vertical,
+        (stack_count + 1) / 2,
+    );
+
+    split_evenly(variant, state, 0, Direction::Vertical, stack_count / 2);
+
+    split_evenly(
+        variant,
+        state,
+        master,
+        Direction::Vertical,
+        params.master_count,
+    );
+
+    let master_left = variant.get_zones()[state][master].left;
+
+    let right_left = variant.get_zones()[state][right].left;
+
+    // Masters first, then the right stack, then the left stack
+    sort_zones(variant, state, |zone| {
+        let column = if zone.left == master_left {
+            0
+        } else if zone.left == right_left {
+            1
+        } else {
+            2
+        };
+
+        (column, zone.top)
+    });
+}
+
+/// Each window splits the previous one, side by side and on top of each other
+/// in turn. In a spiral, the new window goes left and up every other turn,
+/// otherwise it always takes the right or bottom part.
+fn spiral(
+    variant: &mut Variant,
+    state: usize,
+    window_count: usize,
+    params: &TemplateParams,
+    turn: bool,
+) {
+    for k in 1..window_count {
+        let direction = match k % 2 {
+            1 => Direction::Horizontal,
+            _ => Direction::Vertical,
+        };
+
+        let ratio = match k {
+            1 => params.master_ratio,
+            _ => 0.5,
+        };
+
+        let new = match split_at_ratio(variant, state, k - 1, direction, ratio) {
+            Some(val) => val,
+            None => return,
+        };
+
+        if turn && (k - 1) % 4 >= 2 {
+            variant.swap_zones(state, k - 1, new);
+        }
+    }
+}
+
+/// As many columns as rows or one more, with the last row taking up what is
+/// left over.
+fn grid(variant: &mut Variant, state: usize, window_count: usize) {
+    let columns = (window_count as f64).sqrt().ceil() as usize;
+
+    let rows = window_count.div_ceil(columns);
+
+    split_evenly(variant, state, 0, Direction::Vertical, rows);
+
+    for row in 0..rows {
+        let row_count = if row == rows - 1 {
+            window_count - columns * (rows - 1)
+        } else {
+            columns
+        };
+
+        split_evenly(variant, state, row, Direction::Horizontal, row_count);
+    }
+
+    sort_zones(variant, state, |zone| (zone.top, zone.left));
+}
@@ -0,0 +1,184 @@
+use himewm_layout::*;
+use std::fmt;
+
+pub const DEFAULT_COLUMNS: usize = 48;
+
+const UP: u8 = 0b0001;
+
+const DOWN: u8 = 0b0010;
+
+const LEFT: u8 = 0b0100;
+
+const RIGHT: u8 = 0b1000;
+
+/// Draws the zones of a variant state as a grid of box-drawing characters,
+/// with every zone numbered the way the editor numbers them.
+///
+/// ```text
+/// ┌───────┬───────┐
+/// │       │   2   │
+/// │   1   ├───────┤
+/// │       │   3   │
+/// └───────┴───────┘
+/// ```
+pub struct ZoneGrid<'a> {
+    zones: &'a [Zone],
+    monitor_w: i32,
+    monitor_h: i32,
+    columns: usize,
+    ascii: bool,
+}
+
+impl<'a> ZoneGrid<'a> {
+    pub fn new(zones: &'a [Zone], monitor_w: i32, monitor_h: i32) -> Self {
+        return ZoneGrid {
+            zones,
+            monitor_w,
+            monitor_h,
+            columns: DEFAULT_COLUMNS,
+            ascii: false,
+        };
+    }
+
+    /// The zones of a variant state of `layout`, or `None` if there is no
+    /// such variant state.
+    pub fn from_layout(
+        layout: &'a Layout,
+        variant_idx: usize,
+        variant_state_idx: usize,
+    ) -> Option<Self> {
+        let zones = layout
+            .get_variants()
+            .get(variant_idx)?
+            .get_zones()
+            .get(variant_state_idx)?;
+
+        return Some(ZoneGrid::new(
+            zones,
+            layout.get_monitor_rect().w(),
+            layout.get_monitor_rect().h(),
+        ));
+    }
+
+    /// Sets the width of the grid in characters. The height follows from the
+    /// monitor's aspect ratio, taking characters to be twice as tall as they
+    /// are wide.
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = std::cmp::max(columns, 2);
+
+        return self;
+    }
+
+    /// Draws with `+`, `-` and `|` instead of box-drawing characters.
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+
+        return self;
+    }
+
+    fn rows(&self) -> usize {
+        let rows = self.columns as f64 * self.monitor_h as f64 / self.monitor_w as f64 / 2.0;
+
+        return std::cmp::max(rows.round() as usize, 2);
+    }
+
+    fn column_at(&self, x: i32) -> usize {
+        let column = (x as f64 * (self.columns - 1) as f64 / self.monitor_w as f64).round();
+
+        return column.clamp(0.0, (self.columns - 1) as f64) as usize;
+    }
+
+    fn row_at(&self, y: i32, rows: usize) -> usize {
+        let row = (y as f64 * (rows - 1) as f64 / self.monitor_h as f64).round();
+
+        return row.clamp(0.0, (rows - 1) as f64) as usize;
+    }
+
+    fn border_char(&self, connections: u8) -> char {
+        if self.ascii {
+            return match connections {
+                0 => ' ',
+                LEFT | RIGHT | 0b1100 => '-',
+                UP | DOWN | 0b0011 => '|',
+                _ => '+',
+            };
+        }
+
+        return match connections {
+            0 => ' ',
+            LEFT | RIGHT | 0b1100 => '─',
+            UP | DOWN | 0b0011 => '│',
+            0b1010 => '┌',
+            0b0110 => '┐',
+            0b1001 => '└',
+            0b0101 => '┘',
+            0b1011 => '├',
+            0b0111 => '┤',
+            0b1110 => '┬',
+            0b1101 => '┴',
+            _ => '┼',
+        };
+    }
+}
+
+impl fmt::Display for ZoneGrid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.rows();
+
+        let mut connections = vec![vec![0_u8; self.columns]; rows];
+
+        let mut labels = Vec::new();
+
+        for (i, zone) in self.zones.iter().enumerate() {
+            let left = self.column_at(zone.left);
+
+            let right = self.column_at(zone.right);
+
+            let top = self.row_at(zone.top, rows);
+
+            let bottom = self.row_at(zone.bottom, rows);
+
+            for column in left..right {
+                for row in [top, bottom] {
+                    connections[row][column] |= RIGHT;
+
+                    connections[row][column + 1] |= LEFT;
+                }
+            }
+
+            for row in top..bottom {
+                for column in [left, right] {
+                    connections[row][column] |= DOWN;
+
+                    connections[row + 1][column] |= UP;
+                }
+            }
+
+            let label = (i + 1).to_string();
+
+            // Only label zones with room for the number inside their borders
+            if right - left > label.len() && bottom - top >= 2 {
+                let column = (left + right + 1 - label.len()) / 2;
+
+                labels.push(((top + bottom) / 2, column, label));
+            }
+        }
+
+        let mut grid: Vec<Vec<char>> = connections
+            .iter()
+            .map(|row| row.iter().map(|c| self.border_char(*c)).collect())
+            .collect();
+
+        for (row, column, label) in labels {
+            for (i, c) in label.chars().enumerate() {
+                grid[row][column + i] = c;
+            }
+        }
+
+        for row in grid {
+            writeln!(f, "{}", row.into_iter().collect::<String>().trim_end())?;
+        }
+
+        return Ok(());
+    }
+}
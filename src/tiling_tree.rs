@@ -0,0 +1,157 @@
+use himewm_layout::{Direction, Zone};
+
+/// A tiling tree, the common form layouts of other window managers are
+/// translated through. Children carry their share of their parent's width or
+/// height.
+#[derive(Clone, Debug)]
+pub enum Container {
+    Window,
+    Split {
+        direction: Direction,
+        children: Vec<(f64, Container)>,
+    },
+}
+
+/// A rectangle in the coordinates of its monitor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl Rect {
+    pub fn from_zone(zone: &Zone) -> Self {
+        return Rect {
+            left: zone.left as f64,
+            top: zone.top as f64,
+            right: zone.right as f64,
+            bottom: zone.bottom as f64,
+        };
+    }
+
+    fn extent(&self, direction: Direction) -> (f64, f64) {
+        return match direction {
+            Direction::Horizontal => (self.left, self.right),
+            Direction::Vertical => (self.top, self.bottom),
+        };
+    }
+
+    pub fn area(&self) -> f64 {
+        return (self.right - self.left) * (self.bottom - self.top);
+    }
+}
+
+/// Builds a tiling tree out of free-standing rectangles by repeatedly
+/// cutting `region` along lines that no rectangle crosses. Rectangles that
+/// cannot be separated this way are dropped and counted in `dropped`.
+pub fn cut_rects(region: Rect, rects: Vec<Rect>, dropped: &mut usize) -> Container {
+    if rects.len() <= 1 {
+        return Container::Window;
+    }
+
+    for direction in [Direction::Horizontal, Direction::Vertical] {
+        let (start, end) = region.extent(direction);
+
+        let mut cuts: Vec<f64> = rects
+            .iter()
+            .map(|rect| rect.extent(direction).0)
+            .filter(|position| *position > start && *position < end)
+            .filter(|position| {
+                rects.iter().all(|rect| {
+                    let (rect_start, rect_end) = rect.extent(direction);
+
+                    *position <= rect_start || *position >= rect_end
+                })
+            })
+            .collect();
+
+        if cuts.is_empty() {
+            continue;
+        }
+
+        cuts.sort_by(f64::total_cmp);
+
+        cuts.dedup();
+
+        let mut bounds = vec![start];
+
+        bounds.extend(cuts);
+
+        bounds.push(end);
+
+        let children = bounds
+            .windows(2)
+            .filter_map(|bound| {
+                let mut child_region = region;
+
+                match direction {
+                    Direction::Horizontal => {
+                        child_region.left = bound[0];
+
+                        child_region.right = bound[1];
+                    }
+                    Direction::Vertical => {
+                        child_region.top = bound[0];
+
+                        child_region.bottom = bound[1];
+                    }
+                }
+
+                let child_rects: Vec<Rect> = rects
+                    .iter()
+                    .filter(|rect| {
+                        let (rect_start, rect_end) = rect.extent(direction);
+
+                        rect_start >= bound[0] && rect_end <= bound[1]
+                    })
+                    .copied()
+                    .collect();
+
+                // A slice only containing uncovered area
+                if child_rects.is_empty() {
+                    return None;
+                }
+
+                return Some((child_region, child_rects));
+            })
+            .collect::<Vec<(Rect, Vec<Rect>)>>();
+
+        // Shares are taken out of the slices that have zones, so the area of
+        // empty slices is shared out among the others
+        let total: f64 = children
+            .iter()
+            .map(|(child_region, _)| {
+                let (child_start, child_end) = child_region.extent(direction);
+
+                child_end - child_start
+            })
+            .sum();
+
+        if children.len() == 1 {
+            let (child_region, child_rects) = children.into_iter().next().unwrap();
+
+            return cut_rects(child_region, child_rects, dropped);
+        }
+
+        return Container::Split {
+            direction,
+            children: children
+                .into_iter()
+                .map(|(child_region, child_rects)| {
+                    let (child_start, child_end) = child_region.extent(direction);
+
+                    (
+                        (child_end - child_start) / total,
+                        cut_rects(child_region, child_rects, dropped),
+                    )
+                })
+                .collect(),
+        };
+    }
+
+    *dropped += rects.len() - 1;
+
+    return Container::Window;
+}
@@ -0,0 +1,254 @@
+use crate::repeating;
+use himewm_layout::*;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    EmptyZone,
+    ZoneOutsideMonitor,
+    OverlappingZones(usize),
+    UncoveredArea(i64),
+    ZoneCountNotIncreasing {
+        previous: usize,
+        current: usize,
+    },
+    EndZoneIdxOutOfRange {
+        end_zone_idx: usize,
+        zone_count: usize,
+    },
+    RepeatingSplitZoneOutOfRange {
+        split_idx: usize,
+        zone_idx: usize,
+    },
+}
+
+/// A problem found in a layout, along with where it was found. States and
+/// zones are only given when the problem is specific to one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub variant_idx: usize,
+    pub variant_state_idx: Option<usize>,
+    pub zone_idx: Option<usize>,
+    pub problem: Problem,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        return match self.problem {
+            Problem::UncoveredArea(_) | Problem::ZoneCountNotIncreasing { .. } => Severity::Warning,
+            _ => Severity::Error,
+        };
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Variant {}", self.variant_idx)?;
+
+        if let Some(idx) = self.variant_state_idx {
+            write!(f, ", state {}", idx + 1)?;
+        }
+
+        if let Some(idx) = self.zone_idx {
+            write!(f, ", zone {}", idx + 1)?;
+        }
+
+        match &self.problem {
+            Problem::EmptyZone => write!(f, ": the zone has no area"),
+            Problem::ZoneOutsideMonitor => write!(f, ": the zone extends past the monitor"),
+            Problem::OverlappingZones(other) => write!(f, ": overlaps zone {}", other + 1),
+            Problem::UncoveredArea(area) => {
+                write!(f, ": {area} pixels are not covered by any zone")
+            }
+            Problem::ZoneCountNotIncreasing { previous, current } => write!(
+                f,
+                ": has {current} zones but the state before it has {previous}"
+            ),
+            Problem::EndZoneIdxOutOfRange {
+                end_zone_idx,
+                zone_count,
+            } => write!(
+                f,
+                ": new windows go into zone {} but the last state only has {zone_count}",
+                end_zone_idx + 1
+            ),
+            Problem::RepeatingSplitZoneOutOfRange {
+                split_idx,
+                zone_idx,
+            } => write!(
+                f,
+                ": repeating split {} splits zone {}, which is never created",
+                split_idx + 1,
+                zone_idx + 1
+            ),
+        }
+    }
+}
+
+pub fn validate(layout: &Layout) -> Vec<Diagnostic> {
+    let monitor_w = layout.get_monitor_rect().w();
+
+    let monitor_h = layout.get_monitor_rect().h();
+
+    let mut diagnostics = Vec::new();
+
+    for (variant_idx, variant) in layout.get_variants().iter().enumerate() {
+        let manual_zones_until = variant.manual_zones_until();
+
+        let variant_states = &variant.get_zones()[..manual_zones_until];
+
+        for (variant_state_idx, zones) in variant_states.iter().enumerate() {
+            let mut report = |zone_idx: Option<usize>, problem: Problem| {
+                diagnostics.push(Diagnostic {
+                    variant_idx,
+                    variant_state_idx: Some(variant_state_idx),
+                    zone_idx,
+                    problem,
+                });
+            };
+
+            for (zone_idx, zone) in zones.iter().enumerate() {
+                if zone.w() <= 0 || zone.h() <= 0 {
+                    report(Some(zone_idx), Problem::EmptyZone);
+
+                    continue;
+                }
+
+                if zone.left < 0
+                    || zone.top < 0
+                    || zone.right > monitor_w
+                    || zone.bottom > monitor_h
+                {
+                    report(Some(zone_idx), Problem::ZoneOutsideMonitor);
+                }
+
+                for (other_idx, other) in zones.iter().enumerate().skip(zone_idx + 1) {
+                    if overlaps(zone, other) {
+                        report(Some(zone_idx), Problem::OverlappingZones(other_idx));
+                    }
+                }
+            }
+
+            let uncovered_area = uncovered_area(zones, monitor_w, monitor_h);
+
+            if uncovered_area > 0 {
+                report(None, Problem::UncoveredArea(uncovered_area));
+            }
+
+            if variant_state_idx > 0 {
+                let previous = variant_states[variant_state_idx - 1].len();
+
+                if zones.len() <= previous {
+                    report(
+                        None,
+                        Problem::ZoneCountNotIncreasing {
+                            previous,
+                            current: zones.len(),
+                        },
+                    );
+                }
+            }
+        }
+
+        let zone_count = variant_states[manual_zones_until - 1].len();
+
+        let end_zone_idx = variant.get_end_zone_idx();
+
+        if end_zone_idx >= zone_count {
+            diagnostics.push(Diagnostic {
+                variant_idx,
+                variant_state_idx: Some(manual_zones_until - 1),
+                zone_idx: None,
+                problem: Problem::EndZoneIdxOutOfRange {
+                    end_zone_idx,
+                    zone_count,
+                },
+            });
+        }
+
+        let splits = repeating::repeating_splits(variant);
+
+        // Every split adds a zone, so after one repetition there are this
+        // many zones to choose from
+        let repeated_zone_count = zone_count + splits.len();
+
+        for (split_idx, split) in splits.iter().enumerate() {
+            if split.zone_idx >= repeated_zone_count {
+                diagnostics.push(Diagnostic {
+                    variant_idx,
+                    variant_state_idx: None,
+                    zone_idx: None,
+                    problem: Problem::RepeatingSplitZoneOutOfRange {
+                        split_idx,
+                        zone_idx: split.zone_idx,
+                    },
+                });
+            }
+        }
+    }
+
+    return diagnostics;
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    return diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity() == Severity::Error);
+}
+
+fn overlaps(zone: &Zone, other: &Zone) -> bool {
+    return zone.left < other.right
+        && other.left < zone.right
+        && zone.top < other.bottom
+        && other.top < zone.bottom;
+}
+
+/// The area of the monitor not covered by any zone, found by cutting the
+/// monitor into cells along every zone edge.
+fn uncovered_area(zones: &[Zone], monitor_w: i32, monitor_h: i32) -> i64 {
+    let mut xs = vec![0, monitor_w];
+
+    let mut ys = vec![0, monitor_h];
+
+    for zone in zones {
+        xs.extend([
+            zone.left.clamp(0, monitor_w),
+            zone.right.clamp(0, monitor_w),
+        ]);
+
+        ys.extend([
+            zone.top.clamp(0, monitor_h),
+            zone.bottom.clamp(0, monitor_h),
+        ]);
+    }
+
+    xs.sort();
+
+    xs.dedup();
+
+    ys.sort();
+
+    ys.dedup();
+
+    let mut area = 0;
+
+    for x in xs.windows(2) {
+        for y in ys.windows(2) {
+            let covered = zones.iter().any(|zone| {
+                zone.left <= x[0] && x[1] <= zone.right && zone.top <= y[0] && y[1] <= zone.bottom
+            });
+
+            if !covered {
+                area += (x[1] - x[0]) as i64 * (y[1] - y[0]) as i64;
+            }
+        }
+    }
+
+    return area;
+}
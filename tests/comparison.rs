@@ -0,0 +1,92 @@
+use himewm_layout::*;
+use himewm_layout_editor::comparison::{self, Comparison, Thumbnail};
+
+fn layout() -> Layout {
+    let mut layout = Layout::new(1920, 1200);
+
+    layout.get_variants_mut()[0].new_zone_vec(1920, 1200);
+
+    layout.get_variants_mut()[0].new_zone_vec(1920, 1200);
+
+    layout.clone_variant(0);
+
+    layout.get_variants_mut()[1].delete_zones(2);
+
+    return layout;
+}
+
+fn shown(thumbnails: Vec<Thumbnail>) -> Vec<(usize, usize, bool)> {
+    return thumbnails
+        .into_iter()
+        .map(|thumbnail| {
+            (
+                thumbnail.variant_idx,
+                thumbnail.variant_state_idx,
+                thumbnail.selected,
+            )
+        })
+        .collect();
+}
+
+#[test]
+fn variant_states_of_the_selected_variant() {
+    let layout = layout();
+
+    let states = layout.get_variants()[0].manual_zones_until();
+
+    let thumbnails = shown(Comparison::VariantStates.thumbnails(&layout, 0, 1));
+
+    assert_eq!(thumbnails.len(), states);
+
+    assert!(thumbnails
+        .iter()
+        .all(|&(variant_idx, _, _)| variant_idx == 0));
+
+    assert_eq!(
+        thumbnails
+            .iter()
+            .filter(|&&(_, _, selected)| selected)
+            .collect::<Vec<_>>(),
+        vec![&(0, 1, true)]
+    );
+}
+
+#[test]
+fn variants_without_the_variant_state_are_left_out() {
+    let layout = layout();
+
+    let last_state_idx = layout.get_variants()[0].manual_zones_until() - 1;
+
+    assert_eq!(
+        shown(Comparison::Variants.thumbnails(&layout, 1, 0)),
+        vec![(0, 0, false), (1, 0, true)]
+    );
+
+    assert_eq!(
+        shown(Comparison::Variants.thumbnails(&layout, 0, last_state_idx)),
+        vec![(0, last_state_idx, true)]
+    );
+}
+
+#[test]
+fn thumbnails_are_as_large_as_they_fit() {
+    // Four 16:10 thumbnails in a wide strip go side by side
+    let grid = comparison::thumbnail_grid(4, 1920, 1200, (0, 0, 1000, 116), 8, 16);
+
+    assert_eq!(grid.len(), 4);
+
+    assert!(grid
+        .iter()
+        .all(|&(_, y, w, h)| y == 0 && (w, h) == (160, 100)));
+
+    assert!(grid.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    // In a square area they wrap onto a second row
+    let grid = comparison::thumbnail_grid(4, 1920, 1200, (0, 0, 400, 400), 8, 16);
+
+    assert_eq!(grid[0].1, grid[1].1);
+
+    assert!(grid[2].1 > grid[0].1);
+
+    assert!(comparison::thumbnail_grid(0, 1920, 1200, (0, 0, 400, 400), 8, 16).is_empty());
+}
@@ -1,5 +1,3 @@
-use directories::UserDirs;
-
 use himewm_layout::*;
 
 #[test]
@@ -36,7 +34,7 @@ fn create_vertical_stack() {
 
     layout_group.set_default_variant_idx(2);
 
-    export_layout_to_downloads(&layout_group, "vertical_stack").unwrap();
+    export_layout(&layout_group, "vertical_stack").unwrap();
 }
 
 #[test]
@@ -80,7 +78,7 @@ fn create_spiral() {
 
     layout_group.set_default_variant_idx(2);
 
-    export_layout_to_downloads(&layout_group, "spiral").unwrap();
+    export_layout(&layout_group, "spiral").unwrap();
 }
 
 #[test]
@@ -149,17 +147,16 @@ fn create_horizontal_stack_starting_at_3() {
 
     layout_group.set_default_variant_idx(2);
 
-    export_layout_to_downloads(&layout_group, "horizontal_stack_starting_at_3").unwrap();
+    export_layout(&layout_group, "horizontal_stack_starting_at_3").unwrap();
 }
 
-fn export_layout_to_downloads(layout: &Layout, name: &str) -> std::io::Result<()> {
-    let path = UserDirs::new()
-        .unwrap()
-        .download_dir()
-        .unwrap()
-        .join(std::path::Path::new(name).with_extension("json"));
+fn export_layout(layout: &Layout, name: &str) -> std::io::Result<()> {
+    let dir = std::env::temp_dir().join("himewm_layout_editor");
+
+    std::fs::create_dir_all(&dir)?;
 
-    let output_file = std::fs::File::create_new(path)?;
+    let output_file =
+        std::fs::File::create(dir.join(std::path::Path::new(name).with_extension("json")))?;
 
     serde_json::to_writer_pretty(output_file, layout).unwrap();
 
@@ -0,0 +1,499 @@
+use himewm_layout::*;
+use himewm_layout_editor::{
+    editor::{Action, EditError, LayoutEditor, SwapDirection},
+    gaps::Gaps,
+    geometry::{self, ZoneSide},
+};
+
+fn replay(actions: Vec<Action>) -> LayoutEditor {
+    let mut editor = LayoutEditor::new(Layout::new(1920, 1200));
+
+    for action in actions {
+        editor.apply(action).unwrap();
+    }
+
+    return editor;
+}
+
+fn zones(editor: &LayoutEditor) -> Vec<(i32, i32, i32, i32)> {
+    return editor
+        .selected_zones()
+        .iter()
+        .map(|zone| (zone.left, zone.top, zone.right, zone.bottom))
+        .collect();
+}
+
+fn two_zones() -> Vec<Action> {
+    return vec![
+        Action::NewVariantState,
+        Action::SelectVariantState(1),
+        Action::SelectZone(0),
+        Action::Split(Direction::Horizontal, 960),
+    ];
+}
+
+#[test]
+fn split_selected_zone() {
+    let editor = replay(two_zones());
+
+    assert_eq!(
+        zones(&editor),
+        vec![(0, 0, 960, 1200), (960, 0, 1920, 1200)]
+    );
+
+    assert_eq!(editor.selected_zone_idx1(), None);
+}
+
+#[test]
+fn split_evenly() {
+    let editor = replay(vec![
+        Action::NewVariantState,
+        Action::SelectVariantState(1),
+        Action::SelectZone(0),
+        Action::SplitEvenly(Direction::Vertical, 3),
+    ]);
+
+    assert_eq!(
+        zones(&editor),
+        vec![(0, 0, 1920, 400), (0, 400, 1920, 800), (0, 800, 1920, 1200)]
+    );
+}
+
+#[test]
+fn merge_zones() {
+    let mut actions = two_zones();
+
+    actions.extend([
+        Action::SelectZone(0),
+        Action::AddZoneToSelection(1),
+        Action::Merge,
+    ]);
+
+    let editor = replay(actions);
+
+    assert_eq!(zones(&editor), vec![(0, 0, 1920, 1200)]);
+}
+
+#[test]
+fn swap_zones() {
+    let mut actions = two_zones();
+
+    actions.extend([
+        Action::SelectZone(0),
+        Action::AddZoneToSelection(1),
+        Action::Swap,
+    ]);
+
+    let editor = replay(actions);
+
+    assert_eq!(
+        zones(&editor),
+        vec![(960, 0, 1920, 1200), (0, 0, 960, 1200)]
+    );
+}
+
+#[test]
+fn merge_and_split_keeps_zone_order() {
+    for (first, second) in [(1, 2), (2, 1)] {
+        let editor = replay(vec![
+            Action::NewVariantState,
+            Action::SelectVariantState(1),
+            Action::SelectZone(0),
+            Action::Split(Direction::Horizontal, 640),
+            Action::SelectZone(1),
+            Action::Split(Direction::Horizontal, 640),
+            Action::SelectZone(first),
+            Action::AddZoneToSelection(second),
+            Action::Split(Direction::Horizontal, 320),
+        ]);
+
+        assert_eq!(
+            zones(&editor),
+            vec![(0, 0, 640, 1200), (640, 0, 960, 1200), (960, 0, 1920, 1200)]
+        );
+    }
+}
+
+#[test]
+fn move_zone_edge() {
+    let mut editor = replay(two_zones());
+
+    let edge =
+        geometry::find_edge(editor.selected_zones(), Direction::Horizontal, 960, 600).unwrap();
+
+    editor
+        .apply(Action::MoveZoneEdge(edge.clone(), 640))
+        .unwrap();
+
+    assert_eq!(
+        zones(&editor),
+        vec![(0, 0, 640, 1200), (640, 0, 1920, 1200)]
+    );
+
+    assert_eq!(
+        editor.apply(Action::MoveZoneEdge(edge, 4)),
+        Err(EditError::EdgeOutOfBounds(4))
+    );
+}
+
+#[test]
+fn set_zone_side_and_size() {
+    let mut editor = replay(two_zones());
+
+    editor.apply(Action::SelectZone(0)).unwrap();
+
+    editor
+        .apply(Action::SetZoneSide(0, ZoneSide::Right, 640))
+        .unwrap();
+
+    assert_eq!(
+        zones(&editor),
+        vec![(0, 0, 640, 1200), (640, 0, 1920, 1200)]
+    );
+
+    assert_eq!(editor.selected_zone_idx1(), Some(0));
+
+    // The right side of the second zone is on the border of the monitor, so
+    // its left side moves instead
+    editor
+        .apply(Action::SetZoneSize(1, Direction::Horizontal, 800))
+        .unwrap();
+
+    assert_eq!(
+        zones(&editor),
+        vec![(0, 0, 1120, 1200), (1120, 0, 1920, 1200)]
+    );
+
+    assert_eq!(
+        editor.apply(Action::SetZoneSide(0, ZoneSide::Left, 100)),
+        Err(EditError::MonitorBorder)
+    );
+
+    assert_eq!(
+        editor.apply(Action::SetZoneSize(0, Direction::Vertical, 600)),
+        Err(EditError::MonitorBorder)
+    );
+
+    assert_eq!(
+        editor.apply(Action::SetZoneSide(1, ZoneSide::Left, 1910)),
+        Err(EditError::EdgeOutOfBounds(1910))
+    );
+}
+
+#[test]
+fn zone_selection() {
+    let mut editor = replay(two_zones());
+
+    editor.apply(Action::SelectZone(0)).unwrap();
+
+    editor.apply(Action::AddZoneToSelection(1)).unwrap();
+
+    assert_eq!(editor.selected_zone_idx1(), Some(0));
+
+    assert_eq!(editor.selected_zone_idx2(), Some(1));
+
+    editor.apply(Action::SelectZone(1)).unwrap();
+
+    assert_eq!(editor.selected_zone_idx1(), Some(1));
+
+    assert_eq!(editor.selected_zone_idx2(), None);
+
+    editor.apply(Action::SelectZone(1)).unwrap();
+
+    assert_eq!(editor.selected_zone_idx1(), None);
+
+    assert_eq!(
+        editor.apply(Action::SelectZone(2)),
+        Err(EditError::ZoneOutOfRange(2))
+    );
+}
+
+#[test]
+fn delete_variant_state() {
+    let mut editor = replay(vec![
+        Action::NewVariantState,
+        Action::SelectVariantState(1),
+        Action::DeleteVariantState,
+    ]);
+
+    assert_eq!(editor.selected_variant_state_idx(), 0);
+
+    assert_eq!(editor.selected_variant().manual_zones_until(), 1);
+
+    assert_eq!(
+        editor.apply(Action::DeleteVariantState),
+        Err(EditError::LastVariantState)
+    );
+}
+
+#[test]
+fn swap_variant_state_moves_selection() {
+    let editor = replay(vec![
+        Action::NewVariantState,
+        Action::SelectVariantState(1),
+        Action::SelectZone(0),
+        Action::Split(Direction::Horizontal, 960),
+        Action::SwapVariantState(SwapDirection::Previous),
+    ]);
+
+    assert_eq!(editor.selected_variant_state_idx(), 0);
+
+    assert_eq!(zones(&editor).len(), 2);
+}
+
+#[test]
+fn delete_variant() {
+    let mut editor = replay(vec![
+        Action::NewVariant,
+        Action::SelectVariant(1),
+        Action::DeleteVariant,
+    ]);
+
+    assert_eq!(editor.layout().variants_len(), 1);
+
+    assert_eq!(editor.selected_variant_idx(), 0);
+
+    assert_eq!(
+        editor.apply(Action::DeleteVariant),
+        Err(EditError::LastVariant)
+    );
+}
+
+#[test]
+fn swap_variant_moves_selection() {
+    let mut editor = replay(vec![Action::NewVariantState, Action::NewVariant]);
+
+    assert_eq!(
+        editor.apply(Action::SwapVariant(SwapDirection::Previous)),
+        Err(EditError::CannotSwap)
+    );
+
+    editor
+        .apply(Action::SwapVariant(SwapDirection::Next))
+        .unwrap();
+
+    assert_eq!(editor.selected_variant_idx(), 1);
+
+    assert_eq!(editor.selected_variant().manual_zones_until(), 2);
+
+    assert_eq!(editor.layout().get_variants()[0].manual_zones_until(), 1);
+}
+
+#[test]
+fn set_default_variant() {
+    let editor = replay(vec![
+        Action::CloneVariant,
+        Action::SelectVariant(1),
+        Action::SetVariantAsDefault,
+    ]);
+
+    assert_eq!(editor.layout().default_variant_idx(), 1);
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut editor = replay(two_zones());
+
+    editor.apply(Action::Undo).unwrap();
+
+    assert_eq!(zones(&editor), vec![(0, 0, 1920, 1200)]);
+
+    assert_eq!(editor.selected_variant_state_idx(), 1);
+
+    editor.apply(Action::Redo).unwrap();
+
+    assert_eq!(zones(&editor).len(), 2);
+
+    assert_eq!(editor.apply(Action::Redo), Err(EditError::NothingToRedo));
+}
+
+#[test]
+fn rejected_edits_are_not_recorded() {
+    let mut editor = LayoutEditor::new(Layout::new(1920, 1200));
+
+    editor.apply(Action::SelectZone(0)).unwrap();
+
+    assert_eq!(
+        editor.apply(Action::Split(Direction::Horizontal, 1920)),
+        Err(EditError::SplitOutOfBounds(1920))
+    );
+
+    assert_eq!(
+        editor.apply(Action::Merge),
+        Err(EditError::TwoZonesRequired)
+    );
+
+    assert!(!editor.can_undo());
+}
+
+#[test]
+fn preview_leaves_layout_untouched() {
+    let editor = replay(two_zones());
+
+    let before = serde_json::to_value(editor.layout()).unwrap();
+
+    let preview = editor.preview_extend(2);
+
+    assert!(preview.get_zones().len() > editor.selected_variant().manual_zones_until());
+
+    assert_eq!(serde_json::to_value(editor.layout()).unwrap(), before);
+
+    assert_eq!(zones(&editor).len(), 2);
+}
+
+#[test]
+fn variant_gaps_move_with_their_variant() {
+    let own = Gaps { inner: 4, outer: 0 };
+
+    let mut editor = replay(vec![
+        Action::SetLayoutGaps(Gaps {
+            inner: 8,
+            outer: 16,
+        }),
+        Action::NewVariant,
+        Action::SelectVariant(1),
+        Action::SetVariantGaps(Some(own)),
+        Action::CloneVariant,
+        Action::SwapVariant(SwapDirection::Previous),
+    ]);
+
+    assert_eq!(editor.gaps().for_variant(0), own);
+
+    assert_eq!(editor.gaps().for_variant(1).inner, 8);
+
+    assert_eq!(editor.gaps().for_variant(2), own);
+
+    editor.apply(Action::DeleteVariant).unwrap();
+
+    assert_eq!(editor.gaps().for_variant(0).inner, 8);
+
+    assert_eq!(editor.gaps().for_variant(1), own);
+
+    editor.apply(Action::Undo).unwrap();
+
+    assert_eq!(editor.gaps().for_variant(0), own);
+}
+
+#[test]
+fn only_edits_change_the_revision() {
+    let mut editor = LayoutEditor::new(Layout::new(1920, 1200));
+
+    let revision = editor.revision();
+
+    editor.apply(Action::SelectZone(0)).unwrap();
+
+    let _ = editor.apply(Action::Split(Direction::Horizontal, 1920));
+
+    editor.apply(Action::SetVariantAsDefault).unwrap();
+
+    editor
+        .apply(Action::SetLayoutGaps(Gaps::default()))
+        .unwrap();
+
+    assert_eq!(editor.revision(), revision);
+
+    editor.apply(Action::NewVariantState).unwrap();
+
+    assert_ne!(editor.revision(), revision);
+}
+
+#[test]
+fn undoing_back_to_the_saved_revision_is_unmodified() {
+    let mut editor = replay(two_zones());
+
+    assert!(editor.is_modified());
+
+    editor.mark_saved();
+
+    editor.apply(Action::Merge).unwrap_err();
+
+    assert!(!editor.is_modified());
+
+    editor.apply(Action::NewVariant).unwrap();
+
+    assert!(editor.is_modified());
+
+    editor.apply(Action::Undo).unwrap();
+
+    assert!(!editor.is_modified());
+
+    editor.apply(Action::Undo).unwrap();
+
+    assert!(editor.is_modified());
+
+    editor.apply(Action::Redo).unwrap();
+
+    assert!(!editor.is_modified());
+
+    editor.mark_unsaved();
+
+    assert!(editor.is_modified());
+}
+
+#[test]
+fn new_edits_after_undoing_are_modified() {
+    let mut editor = replay(two_zones());
+
+    editor.mark_saved();
+
+    editor.apply(Action::Undo).unwrap();
+
+    editor
+        .apply(Action::Split(Direction::Horizontal, 960))
+        .unwrap();
+
+    assert!(editor.is_modified());
+}
+
+#[test]
+fn end_tiling_edits() {
+    let mut editor = replay(two_zones());
+
+    editor.apply(Action::SetEndZoneIdx(1)).unwrap();
+
+    assert_eq!(editor.selected_variant().get_end_zone_idx(), 1);
+
+    assert_eq!(
+        editor.apply(Action::SetEndZoneIdx(2)),
+        Err(EditError::ZoneOutOfRange(2))
+    );
+
+    editor
+        .apply(Action::SetEndTilingBehaviour(
+            EndTilingBehaviour::default_directional(),
+        ))
+        .unwrap();
+
+    let direction = editor
+        .selected_variant()
+        .get_end_tiling_direction()
+        .unwrap()
+        .other();
+
+    editor
+        .apply(Action::SetEndTilingDirection(direction))
+        .unwrap();
+
+    assert_eq!(
+        editor
+            .selected_variant()
+            .get_end_tiling_direction()
+            .map(|direction| matches!(direction, Direction::Horizontal)),
+        Some(matches!(direction, Direction::Horizontal))
+    );
+
+    assert_eq!(
+        editor.apply(Action::SetFromZones(Some(2))),
+        Err(EditError::VariantStateOutOfRange(2))
+    );
+
+    editor.apply(Action::SetFromZones(Some(0))).unwrap();
+
+    assert!(matches!(
+        editor.selected_variant().get_end_tiling_behaviour(),
+        EndTilingBehaviour::Directional {
+            from_zones: Some(_),
+            ..
+        }
+    ));
+}
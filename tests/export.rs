@@ -0,0 +1,222 @@
+use himewm_layout::*;
+use himewm_layout_editor::{
+    export::{self, ExportError, Format, ImageFormat, Units},
+    gaps::{GapSettings, Gaps},
+    import,
+};
+use serde_json::{json, Value};
+use std::path::Path;
+
+fn two_zone_layout() -> Layout {
+    let mut layout = Layout::new(1920, 1200);
+
+    let variant = &mut layout.get_variants_mut()[0];
+
+    variant.new_zone_vec(1920, 1200);
+
+    variant.split(1, 0, SplitDirection::Horizontal(960));
+
+    return layout;
+}
+
+#[test]
+fn svg_contains_every_zone() {
+    let svg = export::svg(&two_zone_layout(), &GapSettings::default(), 0);
+
+    assert!(svg.starts_with("<svg "));
+
+    assert!(svg.contains("width=\"528\" height=\"226\""));
+
+    assert!(svg.contains("Variant 0 (default)"));
+
+    assert!(svg.contains("State 2</text>"));
+
+    // The background and three zones
+    assert_eq!(svg.matches("<rect ").count(), 4);
+
+    assert!(!svg.contains("(extended)"));
+}
+
+#[test]
+fn svg_marks_extended_states() {
+    let svg = export::svg(&two_zone_layout(), &GapSettings::default(), 2);
+
+    assert!(svg.contains("State 3 (extended)"));
+
+    assert!(svg.contains("State 4 (extended)"));
+}
+
+#[test]
+fn png_header() {
+    let png = export::png(&two_zone_layout(), &GapSettings::default(), 0);
+
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    assert_eq!(&png[12..16], b"IHDR");
+
+    assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 528);
+
+    assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 226);
+
+    assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+}
+
+#[test]
+fn image_format_from_extension() {
+    assert_eq!(
+        ImageFormat::from_path(Path::new("layout.SVG")),
+        Some(ImageFormat::Svg)
+    );
+
+    assert_eq!(
+        ImageFormat::from_path(Path::new("layout.png")),
+        Some(ImageFormat::Png)
+    );
+
+    assert_eq!(ImageFormat::from_path(Path::new("layout.json")), None);
+
+    assert!(matches!(
+        export::export_image(
+            &two_zone_layout(),
+            &GapSettings::default(),
+            Path::new("layout.json"),
+            0
+        ),
+        Err(ExportError::UnknownFormat)
+    ));
+}
+
+#[test]
+fn csv_in_pixels_and_percentages() {
+    let layout = two_zone_layout();
+
+    assert_eq!(
+        export::csv::write(&layout, 0, Units::Pixels),
+        "\
+state,zone,left,top,right,bottom,width,height
+1,1,0,0,1920,1200,1920,1200
+2,1,0,0,960,1200,960,1200
+2,2,960,0,1920,1200,960,1200
+"
+    );
+
+    assert!(export::csv::write(&layout, 0, Units::Percent)
+        .ends_with("2,2,50.00,0.00,100.00,100.00,50.00,100.00\n"));
+}
+
+#[test]
+fn fancyzones_canvas_layouts() {
+    let layout = two_zone_layout();
+
+    let json: Value = serde_json::from_str(&export::fancyzones::write(
+        &layout,
+        Gaps::default(),
+        0,
+        "Halves",
+    ))
+    .unwrap();
+
+    let layouts = json["custom-layouts"].as_array().unwrap();
+
+    assert_eq!(layouts.len(), 2);
+
+    assert_eq!(layouts[1]["type"], "canvas");
+
+    assert_eq!(layouts[1]["info"]["ref-width"], 1920);
+
+    assert_eq!(
+        layouts[1]["info"]["zones"][1],
+        json!({ "X": 960, "Y": 0, "width": 960, "height": 1200 })
+    );
+
+    assert_ne!(layouts[0]["uuid"], layouts[1]["uuid"]);
+
+    // The GUIDs must not change between releases, or FancyZones would keep
+    // copies of the layout from earlier exports
+    assert_eq!(layouts[0]["uuid"], "{FC74B54A-BFA6-6E3D-FE07-F553C7BD1850}");
+
+    // Exported layouts can be read back in
+    let imported = import::fancyzones::parse(&json.to_string(), 1920, 1200).unwrap();
+
+    assert_eq!(imported.layout.variants_len(), 2);
+}
+
+#[test]
+fn gaps_are_taken_off_exported_zones() {
+    let gaps = Gaps {
+        inner: 16,
+        outer: 8,
+    };
+
+    let json: Value = serde_json::from_str(&export::fancyzones::write(
+        &two_zone_layout(),
+        gaps,
+        0,
+        "Halves",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        json["custom-layouts"][1]["info"]["zones"][1],
+        json!({ "X": 968, "Y": 8, "width": 944, "height": 1184 })
+    );
+
+    let settings = GapSettings {
+        layout: gaps,
+        ..GapSettings::default()
+    };
+
+    let svg = export::svg(&two_zone_layout(), &settings, 0);
+
+    // The zone of the first state, 8 pixels in from every border of the
+    // monitor, on a thumbnail an eighth of its size
+    assert!(svg.contains("<rect x=\"17\" y=\"41\" width=\"238\" height=\"148\""));
+}
+
+#[test]
+fn i3_layouts_round_trip() {
+    let documents = export::i3::write(&two_zone_layout(), 0).unwrap();
+
+    assert_eq!(documents.len(), 2);
+
+    let split: Value = serde_json::from_str(&documents[1]).unwrap();
+
+    assert_eq!(split["layout"], "splith");
+
+    assert_eq!(split["nodes"][0]["percent"], 0.5);
+
+    let imported = import::i3::parse(&documents[1], 1920, 1200).unwrap();
+
+    let zones: Vec<(i32, i32, i32, i32)> = imported.layout.get_variants()[0]
+        .get_zones()
+        .last()
+        .unwrap()
+        .iter()
+        .map(|zone| (zone.left, zone.top, zone.right, zone.bottom))
+        .collect();
+
+    assert_eq!(zones, vec![(0, 0, 960, 1200), (960, 0, 1920, 1200)]);
+}
+
+#[test]
+fn i3_export_writes_one_file_per_state() {
+    let dir = std::env::temp_dir().join("himewm_layout_editor");
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let paths = export::export_layout(
+        &two_zone_layout(),
+        &GapSettings::default(),
+        0,
+        Format::I3,
+        &dir.join("halves.json"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        paths,
+        vec![dir.join("halves-1.json"), dir.join("halves-2.json")]
+    );
+
+    assert!(paths.iter().all(|path| path.exists()));
+}
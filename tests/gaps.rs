@@ -0,0 +1,156 @@
+use himewm_layout::*;
+use himewm_layout_editor::{
+    file::{self, LayoutFileError},
+    gaps::{GapSettings, Gaps},
+};
+use serde_json::json;
+
+fn quarters() -> Vec<Zone> {
+    let mut layout = Layout::new(1920, 1200);
+
+    let variant = &mut layout.get_variants_mut()[0];
+
+    variant.new_zone_vec(1920, 1200);
+
+    variant.split(1, 0, SplitDirection::Horizontal(960));
+
+    variant.split(1, 1, SplitDirection::Vertical(600));
+
+    variant.split(1, 0, SplitDirection::Vertical(600));
+
+    return variant.get_zones()[1].to_owned();
+}
+
+#[test]
+fn window_rects() {
+    let gaps = Gaps {
+        inner: 9,
+        outer: 20,
+    };
+
+    let mut rects: Vec<(i32, i32, i32, i32)> = quarters()
+        .iter()
+        .map(|zone| gaps.window_rect(zone, 1920, 1200))
+        .collect();
+
+    rects.sort_by_key(|&(left, top, _, _)| (top, left));
+
+    // Odd gaps are split unevenly, but neighbouring windows still end up
+    // exactly `inner` apart
+    assert_eq!(
+        rects,
+        vec![
+            (20, 20, 956, 596),
+            (965, 20, 1900, 596),
+            (20, 605, 956, 1180),
+            (965, 605, 1900, 1180)
+        ]
+    );
+
+    let huge = Gaps {
+        inner: 0,
+        outer: 2000,
+    };
+
+    let (left, top, right, bottom) = huge.window_rect(&quarters()[0], 1920, 1200);
+
+    assert_eq!((right - left, bottom - top), (1, 1));
+}
+
+#[test]
+fn variant_gaps_follow_their_variants() {
+    let own = Gaps { inner: 4, outer: 4 };
+
+    let mut settings = GapSettings {
+        layout: Gaps {
+            inner: 8,
+            outer: 16,
+        },
+        ..GapSettings::default()
+    };
+
+    settings.variants.insert(2, own);
+
+    assert_eq!(settings.for_variant(0), settings.layout);
+
+    assert_eq!(settings.for_variant(2), own);
+
+    settings.variants_swapped(1, 2);
+
+    assert_eq!(settings.for_variant(1), own);
+
+    assert_eq!(settings.for_variant(2), settings.layout);
+
+    settings.variant_deleted(0);
+
+    assert_eq!(settings.for_variant(0), own);
+
+    settings.variant_deleted(0);
+
+    assert!(settings.variants.is_empty());
+}
+
+#[test]
+fn gaps_json() {
+    let value = json!({ "inner": 8, "variants": { "1": { "outer": 12 } } });
+
+    let settings = GapSettings::from_json(&value).unwrap();
+
+    assert_eq!(settings.layout, Gaps { inner: 8, outer: 0 });
+
+    assert_eq!(
+        settings.for_variant(1),
+        Gaps {
+            inner: 0,
+            outer: 12
+        }
+    );
+
+    assert_eq!(GapSettings::from_json(&settings.to_json()), Some(settings));
+
+    assert_eq!(GapSettings::from_json(&json!({ "inner": -1 })), None);
+
+    assert_eq!(
+        GapSettings::from_json(&json!({ "variants": { "first": {} } })),
+        None
+    );
+}
+
+#[test]
+fn gaps_are_saved_alongside_the_layout() {
+    let dir = std::env::temp_dir().join("himewm_layout_editor");
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("gaps.json");
+
+    let settings = GapSettings {
+        layout: Gaps {
+            inner: 8,
+            outer: 16,
+        },
+        ..GapSettings::default()
+    };
+
+    file::write_layout_with_gaps(&Layout::new(1920, 1200), &settings, &path).unwrap();
+
+    let (layout, read_settings) = file::read_layout_with_gaps(&path).unwrap();
+
+    assert_eq!(layout.get_monitor_rect().w(), 1920);
+
+    assert_eq!(read_settings, settings);
+
+    // Layouts without gaps are written as before
+    file::write_layout(&layout, &path).unwrap();
+
+    assert!(!std::fs::read_to_string(&path)
+        .unwrap()
+        .contains(file::GAPS_KEY));
+
+    std::fs::write(&path, r#"{"gaps": {"inner": "wide"}}"#).unwrap();
+
+    assert!(matches!(
+        file::read_layout_with_gaps(&path),
+        Err(LayoutFileError::InvalidGaps)
+    ));
+}
@@ -0,0 +1,206 @@
+use himewm_layout::*;
+use himewm_layout_editor::import::{self, ImportError, Unrepresented};
+
+fn last_state(layout: &Layout, variant_idx: usize) -> Vec<(i32, i32, i32, i32)> {
+    return layout.get_variants()[variant_idx]
+        .get_zones()
+        .last()
+        .unwrap()
+        .iter()
+        .map(|zone| (zone.left, zone.top, zone.right, zone.bottom))
+        .collect();
+}
+
+#[test]
+fn tmux() {
+    let imported = import::tmux::parse(
+        "bb62,159x48,0,0{79x48,0,0,1,79x48,80,0[79x24,80,0,2,79x23,80,25,3]}",
+        1920,
+        1200,
+    )
+    .unwrap();
+
+    assert_eq!(
+        last_state(&imported.layout, 0),
+        vec![
+            (0, 0, 966, 1200),
+            (966, 0, 1920, 625),
+            (966, 625, 1920, 1200)
+        ]
+    );
+
+    // One variant state per window
+    assert_eq!(imported.layout.get_variants()[0].manual_zones_until(), 3);
+
+    assert!(imported.unrepresented.is_empty());
+}
+
+#[test]
+fn tmux_without_checksum_or_pane_ids() {
+    let imported = import::tmux::parse("159x48,0,0{79x48,0,0,79x48,80,0}", 1920, 1200).unwrap();
+
+    assert_eq!(
+        last_state(&imported.layout, 0),
+        vec![(0, 0, 966, 1200), (966, 0, 1920, 1200)]
+    );
+}
+
+#[test]
+fn tmux_syntax_error() {
+    assert!(matches!(
+        import::tmux::parse("159x48,0,0{79x48", 1920, 1200),
+        Err(ImportError::Syntax(_))
+    ));
+}
+
+#[test]
+fn i3_save_tree() {
+    let json = r#"
+// vim:ts=4:sw=4:et
+{
+    "layout": "splith",
+    "percent": 1,
+    "type": "con",
+    "nodes": [
+        {
+            "percent": 0.6,
+            "swallows": [
+                {
+                // "class": "^Firefox$",
+                }
+            ],
+            "type": "con"
+        },
+        {
+            "layout": "splitv",
+            "percent": 0.4,
+            "type": "con",
+            "nodes": [
+                {
+                    "swallows": [
+                        {
+                            "class": "^URxvt$",
+                        // "instance": "^urxvt$"
+                        }
+                    ],
+                    "type": "con"
+                },
+                {
+                    "layout": "tabbed",
+                    "type": "con",
+                    "nodes": [{ "type": "con" }, { "type": "con" }]
+                }
+            ]
+        }
+    ]
+}
+{
+    "type": "floating_con",
+    "nodes": [{ "type": "con" }]
+}
+"#;
+
+    let imported = import::i3::parse(json, 1920, 1200).unwrap();
+
+    assert_eq!(
+        last_state(&imported.layout, 0),
+        vec![
+            (0, 0, 1152, 1200),
+            (1152, 0, 1920, 600),
+            (1152, 600, 1920, 1200)
+        ]
+    );
+
+    assert_eq!(
+        imported.unrepresented,
+        vec![
+            Unrepresented::FloatingWindows(1),
+            Unrepresented::StackedContainer { windows: 2 }
+        ]
+    );
+}
+
+#[test]
+fn fancyzones_grid() {
+    let json = r#"{
+        "custom-layouts": [
+            {
+                "uuid": "{00000000-0000-0000-0000-000000000000}",
+                "name": "Main and two",
+                "type": "grid",
+                "info": {
+                    "rows": 2,
+                    "columns": 2,
+                    "rows-percentage": [5000, 5000],
+                    "columns-percentage": [5000, 5000],
+                    "cell-child-map": [[0, 1], [0, 2]],
+                    "show-spacing": true,
+                    "spacing": 16,
+                    "sensitivity-radius": 20
+                }
+            }
+        ]
+    }"#;
+
+    let imported = import::fancyzones::parse(json, 1920, 1200).unwrap();
+
+    assert_eq!(
+        last_state(&imported.layout, 0),
+        vec![
+            (0, 0, 960, 1200),
+            (960, 0, 1920, 600),
+            (960, 600, 1920, 1200)
+        ]
+    );
+
+    assert_eq!(imported.unrepresented, vec![Unrepresented::Spacing(16)]);
+}
+
+#[test]
+fn fancyzones_canvas() {
+    let json = r#"[
+        {
+            "type": "canvas",
+            "info": {
+                "ref-width": 1920,
+                "ref-height": 1080,
+                "zones": [
+                    { "X": 0, "Y": 0, "width": 900, "height": 1080 },
+                    { "X": 1000, "Y": 0, "width": 920, "height": 1080 }
+                ]
+            }
+        },
+        {
+            "type": "canvas",
+            "info": {
+                "ref-width": 1920,
+                "ref-height": 1080,
+                "zones": [
+                    { "X": 0, "Y": 0, "width": 1000, "height": 1080 },
+                    { "X": 900, "Y": 0, "width": 1020, "height": 1080 }
+                ]
+            }
+        }
+    ]"#;
+
+    let imported = import::fancyzones::parse(json, 2560, 1440).unwrap();
+
+    assert_eq!(imported.layout.get_monitor_rect().w(), 1920);
+
+    assert_eq!(imported.layout.variants_len(), 2);
+
+    assert_eq!(
+        last_state(&imported.layout, 0),
+        vec![(0, 0, 1000, 1080), (1000, 0, 1920, 1080)]
+    );
+
+    assert_eq!(last_state(&imported.layout, 1), vec![(0, 0, 1920, 1080)]);
+
+    assert_eq!(
+        imported.unrepresented,
+        vec![
+            Unrepresented::UncoveredArea,
+            Unrepresented::UnsplittableZones(1)
+        ]
+    );
+}
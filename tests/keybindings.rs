@@ -0,0 +1,120 @@
+use fltk::enums::Key;
+use himewm_layout_editor::keybindings::{Command, KeyCombo, Keybindings, KeybindingsError};
+
+fn pressed(combo: &str) -> KeyCombo {
+    return KeyCombo::parse(combo).unwrap();
+}
+
+#[test]
+fn default_bindings() {
+    let keybindings = Keybindings::default();
+
+    assert_eq!(
+        keybindings.command_for(pressed("3")),
+        Some(Command::SelectZone(2))
+    );
+
+    assert_eq!(
+        keybindings.command_for(pressed("Tab")),
+        Some(Command::SelectNextZone)
+    );
+
+    assert_eq!(keybindings.command_for(pressed("s")), Some(Command::Split));
+
+    assert_eq!(
+        keybindings.command_for(pressed("PageDown")),
+        Some(Command::NextVariant)
+    );
+
+    assert_eq!(keybindings.command_for(pressed("Ctrl+S")), None);
+}
+
+#[test]
+fn shift_adds_zones_only() {
+    let keybindings = Keybindings::default();
+
+    assert_eq!(
+        keybindings.command_for(pressed("Shift+2")),
+        Some(Command::SelectZone(1))
+    );
+
+    assert_eq!(
+        keybindings.command_for(pressed("Shift+Tab")),
+        Some(Command::SelectNextZone)
+    );
+
+    assert_eq!(keybindings.command_for(pressed("Shift+M")), None);
+}
+
+#[test]
+fn parse_key_combos() {
+    assert_eq!(
+        KeyCombo::parse("Ctrl+Shift+Tab"),
+        Some(KeyCombo {
+            key: Key::Tab,
+            ctrl: true,
+            shift: true,
+            alt: false,
+        })
+    );
+
+    assert_eq!(
+        KeyCombo::parse("ctrl++"),
+        Some(KeyCombo {
+            key: Key::from_char('+'),
+            ctrl: true,
+            shift: false,
+            alt: false,
+        })
+    );
+
+    assert_eq!(KeyCombo::parse("Hyper+S"), None);
+
+    assert_eq!(KeyCombo::parse("Tabs"), None);
+
+    assert_eq!(pressed("alt+f5").to_string(), "Alt+F5");
+
+    assert_eq!(pressed("x").to_string(), "X");
+}
+
+#[test]
+fn override_bindings() {
+    let keybindings =
+        Keybindings::from_json(r#"{"split": ["Enter", "Ctrl+D"], "swap": "W", "merge": []}"#)
+            .unwrap();
+
+    assert_eq!(keybindings.command_for(pressed("s")), None);
+
+    assert_eq!(
+        keybindings.command_for(pressed("Ctrl+D")),
+        Some(Command::Split)
+    );
+
+    assert_eq!(keybindings.command_for(pressed("w")), Some(Command::Swap));
+
+    assert_eq!(keybindings.command_for(pressed("m")), None);
+
+    // Commands left out keep their defaults
+    assert_eq!(
+        keybindings.command_for(pressed("Left")),
+        Some(Command::PreviousVariantState)
+    );
+}
+
+#[test]
+fn invalid_bindings() {
+    assert!(matches!(
+        Keybindings::from_json(r#"{"explode": ["E"]}"#),
+        Err(KeybindingsError::UnknownCommand(command)) if command == "explode"
+    ));
+
+    assert!(matches!(
+        Keybindings::from_json(r#"{"split": ["Ctrl+"]}"#),
+        Err(KeybindingsError::InvalidKey { .. })
+    ));
+
+    assert!(matches!(
+        Keybindings::from_json(r#"["split"]"#),
+        Err(KeybindingsError::NotAnObject)
+    ));
+}
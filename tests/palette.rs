@@ -0,0 +1,35 @@
+use himewm_layout_editor::palette;
+
+fn labels(labels: &[&str]) -> Vec<String> {
+    return labels.iter().map(|label| label.to_string()).collect();
+}
+
+#[test]
+fn subsequences_match() {
+    assert!(palette::fuzzy_score("svas", "File > Save As...").is_some());
+
+    assert!(palette::fuzzy_score("SAVE", "File > Save As...").is_some());
+
+    assert_eq!(palette::fuzzy_score("saved", "File > Save As..."), None);
+}
+
+#[test]
+fn word_starts_rank_first() {
+    let candidates = labels(&[
+        "State > Delete variant state",
+        "Zone > Split evenly",
+        "Zone > Split",
+        "File > Save As...",
+    ]);
+
+    assert_eq!(palette::rank("sa", &candidates)[0], 3);
+
+    assert_eq!(palette::rank("split", &candidates), vec![2, 1]);
+}
+
+#[test]
+fn empty_query_keeps_everything_in_order() {
+    let candidates = labels(&["File > New", "File > Open...", "Edit > Undo"]);
+
+    assert_eq!(palette::rank("", &candidates), vec![0, 1, 2]);
+}
@@ -0,0 +1,68 @@
+use himewm_layout_editor::geometry::fit_aspect_ratio;
+use himewm_layout_editor::panels::{PanelSizes, MIN_CANVAS_WIDTH, MIN_PANEL_WIDTH};
+
+#[test]
+fn round_trip() {
+    let sizes = PanelSizes {
+        window_w: 1600,
+        window_h: 900,
+        sidebar_w: 200,
+        inspector_w: 420,
+    };
+
+    assert_eq!(PanelSizes::from_json(&sizes.to_json()), Some(sizes));
+}
+
+#[test]
+fn missing_sizes_keep_defaults() {
+    assert_eq!(
+        PanelSizes::from_json(r#"{"sidebar_width": 240}"#),
+        Some(PanelSizes {
+            sidebar_w: 240,
+            ..PanelSizes::default()
+        })
+    );
+
+    assert_eq!(PanelSizes::from_json(r#"{"sidebar_width": "wide"}"#), None);
+
+    assert_eq!(PanelSizes::from_json("[]"), None);
+}
+
+#[test]
+fn panels_leave_room_for_the_canvas() {
+    let sizes = PanelSizes {
+        sidebar_w: 300,
+        inspector_w: 600,
+        ..PanelSizes::default()
+    };
+
+    assert_eq!(sizes.fitted(1920), sizes);
+
+    let fitted = sizes.fitted(700);
+
+    assert_eq!(
+        fitted.sidebar_w + fitted.inspector_w,
+        700 - MIN_CANVAS_WIDTH
+    );
+
+    assert_eq!(fitted.sidebar_w, 180);
+
+    let fitted = sizes.fitted(100);
+
+    assert_eq!(fitted.sidebar_w, MIN_PANEL_WIDTH);
+
+    assert_eq!(fitted.inspector_w, MIN_PANEL_WIDTH);
+}
+
+#[test]
+fn canvas_keeps_the_monitor_shape() {
+    assert_eq!(
+        fit_aspect_ratio(1920, 1080, 0, 0, 800, 800),
+        (0, 175, 800, 450)
+    );
+
+    assert_eq!(
+        fit_aspect_ratio(1080, 1920, 100, 50, 800, 960),
+        (230, 50, 540, 960)
+    );
+}
@@ -0,0 +1,60 @@
+use himewm_layout::*;
+use himewm_layout_editor::playback::{self, Progression};
+
+fn two_states() -> Variant {
+    let mut layout = Layout::new(1920, 1200);
+
+    let variant = &mut layout.get_variants_mut()[0];
+
+    variant.new_zone_vec(1920, 1200);
+
+    variant.split(1, 0, SplitDirection::Horizontal(960));
+
+    return variant.to_owned();
+}
+
+#[test]
+fn manual_states_come_first() {
+    let progression = Progression::new(&two_states(), 5);
+
+    assert_eq!(progression.variant_state_idx(1), Some(0));
+
+    assert_eq!(progression.variant_state_idx(2), Some(1));
+
+    assert!(!progression.is_extended(1));
+
+    assert!(!progression.is_extended(2));
+
+    assert_eq!(progression.variant_state_idx(0), None);
+
+    assert_eq!(progression.variant_state_idx(6), None);
+}
+
+#[test]
+fn extended_states_follow() {
+    let progression = Progression::new(&two_states(), 5);
+
+    assert_eq!(progression.max_window_count(), 5);
+
+    for window_count in 1..=5 {
+        assert!(progression.zones(window_count).unwrap().len() >= window_count);
+    }
+
+    assert!(progression.is_extended(3));
+
+    assert!(progression.is_extended(5));
+}
+
+#[test]
+fn window_counts_start_over() {
+    assert_eq!(playback::next_window_count(1, 4), 2);
+
+    assert_eq!(playback::next_window_count(4, 4), 1);
+
+    assert_eq!(playback::next_window_count(7, 4), 1);
+
+    assert_eq!(
+        playback::default_max_window_count(&two_states()),
+        2 + playback::EXTRA_WINDOWS
+    );
+}
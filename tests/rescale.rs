@@ -0,0 +1,98 @@
+use himewm_layout::*;
+use himewm_layout_editor::{
+    editor::{Action, EditError, LayoutEditor},
+    rescale,
+};
+
+fn edges(zones: &[Zone]) -> Vec<(i32, i32, i32, i32)> {
+    return zones
+        .iter()
+        .map(|zone| (zone.left, zone.top, zone.right, zone.bottom))
+        .collect();
+}
+
+fn three_column_layout() -> Layout {
+    let mut layout = Layout::new(1920, 1200);
+
+    let variant = &mut layout.get_variants_mut()[0];
+
+    variant.new_zone_vec(1920, 1200);
+
+    variant.split(1, 0, SplitDirection::Horizontal(640));
+
+    variant.split(1, 1, SplitDirection::Horizontal(1280));
+
+    return layout;
+}
+
+#[test]
+fn parse_monitor_size() {
+    assert_eq!(rescale::parse_monitor_size("2560x1440"), Some((2560, 1440)));
+
+    assert_eq!(
+        rescale::parse_monitor_size(" 1080 X 1920 "),
+        Some((1080, 1920))
+    );
+
+    assert_eq!(rescale::parse_monitor_size("0x1080"), None);
+
+    assert_eq!(rescale::parse_monitor_size("1920"), None);
+}
+
+#[test]
+fn rescale_layout() {
+    let layout = rescale::rescale_layout(&three_column_layout(), 2560, 1440);
+
+    assert_eq!(layout.get_monitor_rect().w(), 2560);
+
+    assert_eq!(layout.get_monitor_rect().h(), 1440);
+
+    let variant = &layout.get_variants()[0];
+
+    assert_eq!(edges(&variant.get_zones()[0]), vec![(0, 0, 2560, 1440)]);
+
+    assert_eq!(
+        edges(&variant.get_zones()[1]),
+        vec![
+            (0, 0, 853, 1440),
+            (853, 0, 1707, 1440),
+            (1707, 0, 2560, 1440)
+        ]
+    );
+}
+
+#[test]
+fn shared_edges_stay_shared() {
+    let layout = rescale::rescale_layout(&three_column_layout(), 1000, 700);
+
+    let zones = &layout.get_variants()[0].get_zones()[1];
+
+    assert_eq!(zones[0].right, zones[1].left);
+
+    assert_eq!(zones[1].right, zones[2].left);
+
+    assert_eq!(zones[2].right, 1000);
+
+    assert!(zones.iter().all(|zone| zone.bottom == 700));
+}
+
+#[test]
+fn set_monitor_size_can_be_undone() {
+    let mut editor = LayoutEditor::new(three_column_layout());
+
+    editor.apply(Action::SetMonitorSize(1080, 1920)).unwrap();
+
+    assert_eq!(
+        edges(&editor.layout().get_variants()[0].get_zones()[1]),
+        vec![(0, 0, 360, 1920), (360, 0, 720, 1920), (720, 0, 1080, 1920)]
+    );
+
+    editor.apply(Action::Undo).unwrap();
+
+    assert_eq!(editor.layout().get_monitor_rect().w(), 1920);
+
+    assert_eq!(
+        editor.apply(Action::SetMonitorSize(0, 1080)),
+        Err(EditError::InvalidMonitorSize(0, 1080))
+    );
+}
@@ -1,6 +1,7 @@
 use himewm_layout::*;
 
 #[test]
+#[ignore = "opens a window"]
 fn show_layout() {
     let mut layout_group = Layout::new(1920, 1200);
 
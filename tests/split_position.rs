@@ -0,0 +1,58 @@
+use himewm_layout_editor::split_position::parse_split_position;
+
+#[test]
+fn parse_percentage() {
+    assert_eq!(parse_split_position("50%", 1920), Some(960));
+
+    assert_eq!(parse_split_position(" 25 % ", 1200), Some(300));
+}
+
+#[test]
+fn parse_fraction() {
+    assert_eq!(parse_split_position("1/3", 1920), Some(640));
+
+    assert_eq!(parse_split_position("2 / 3", 1920), Some(1280));
+}
+
+#[test]
+fn parse_ratio() {
+    assert_eq!(parse_split_position("2:1", 1920), Some(1280));
+
+    assert_eq!(parse_split_position("1 : 1", 1200), Some(600));
+}
+
+#[test]
+fn parse_decimal_proportion() {
+    assert_eq!(parse_split_position("0.618", 1000), Some(618));
+
+    assert_eq!(parse_split_position("0.5", 1920), Some(960));
+}
+
+#[test]
+fn parse_pixel_offset() {
+    assert_eq!(parse_split_position("640", 1920), Some(640));
+
+    assert_eq!(parse_split_position(" 1920 ", 1920), Some(1920));
+}
+
+#[test]
+fn reject_empty_input() {
+    assert_eq!(parse_split_position("", 1920), None);
+
+    assert_eq!(parse_split_position("   ", 1920), None);
+}
+
+#[test]
+fn reject_invalid_input() {
+    assert_eq!(parse_split_position("1/0", 1920), None);
+
+    assert_eq!(parse_split_position("150%", 1920), None);
+
+    assert_eq!(parse_split_position("-3", 1920), None);
+
+    assert_eq!(parse_split_position("abc", 1920), None);
+
+    assert_eq!(parse_split_position("2000", 1920), None);
+
+    assert_eq!(parse_split_position("0:0", 1920), None);
+}
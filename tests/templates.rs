@@ -0,0 +1,189 @@
+use himewm_layout::*;
+use himewm_layout_editor::templates::{Side, Template, TemplateParams};
+use himewm_layout_editor::validation;
+
+fn state(layout: &Layout, variant_state_idx: usize) -> Vec<(i32, i32, i32, i32)> {
+    return layout.get_variants()[0].get_zones()[variant_state_idx]
+        .iter()
+        .map(|zone| (zone.left, zone.top, zone.right, zone.bottom))
+        .collect();
+}
+
+fn params(max_manual_states: usize) -> TemplateParams {
+    return TemplateParams {
+        max_manual_states,
+        ..TemplateParams::default()
+    };
+}
+
+#[test]
+fn one_variant_state_per_window_count() {
+    for template in Template::ALL {
+        let layout = template.generate(&params(6), 1920, 1200);
+
+        let variant = &layout.get_variants()[0];
+
+        let expected_states = match template {
+            Template::Monocle => 1,
+            _ => 6,
+        };
+
+        assert_eq!(
+            variant.manual_zones_until(),
+            expected_states,
+            "{template:?}"
+        );
+
+        for (i, zones) in variant.get_zones().iter().enumerate() {
+            assert_eq!(zones.len(), i + 1, "{template:?}");
+        }
+
+        assert_eq!(validation::validate(&layout), Vec::new(), "{template:?}");
+    }
+}
+
+#[test]
+fn master_stack() {
+    let layout = Template::MasterStack(Side::Left).generate(&params(3), 1920, 1200);
+
+    assert_eq!(state(&layout, 0), vec![(0, 0, 1920, 1200)]);
+
+    assert_eq!(
+        state(&layout, 1),
+        vec![(0, 0, 960, 1200), (960, 0, 1920, 1200)]
+    );
+
+    assert_eq!(
+        state(&layout, 2),
+        vec![
+            (0, 0, 960, 1200),
+            (960, 0, 1920, 600),
+            (960, 600, 1920, 1200)
+        ]
+    );
+
+    match layout.get_variants()[0].get_end_tiling_behaviour() {
+        EndTilingBehaviour::Directional {
+            direction: Direction::Vertical,
+            from_zones: None,
+            zone_idx: 2,
+        } => {}
+        _ => panic!("new windows should be stacked below the last zone"),
+    }
+}
+
+#[test]
+fn master_on_the_right_comes_first() {
+    let layout = Template::MasterStack(Side::Right).generate(&params(3), 1920, 1200);
+
+    assert_eq!(
+        state(&layout, 2),
+        vec![(960, 0, 1920, 1200), (0, 0, 960, 600), (0, 600, 960, 1200)]
+    );
+}
+
+#[test]
+fn master_ratio_and_count() {
+    let params = TemplateParams {
+        master_ratio: 0.25,
+        master_count: 2,
+        max_manual_states: 3,
+    };
+
+    let layout = Template::MasterStack(Side::Top).generate(&params, 1920, 1200);
+
+    assert_eq!(
+        state(&layout, 1),
+        vec![(0, 0, 960, 1200), (960, 0, 1920, 1200)]
+    );
+
+    assert_eq!(
+        state(&layout, 2),
+        vec![(0, 0, 960, 300), (960, 0, 1920, 300), (0, 300, 1920, 1200)]
+    );
+}
+
+#[test]
+fn centered_master() {
+    let layout = Template::CenteredMaster.generate(&params(4), 1920, 1200);
+
+    assert_eq!(
+        state(&layout, 1),
+        vec![(0, 0, 960, 1200), (960, 0, 1920, 1200)]
+    );
+
+    assert_eq!(
+        state(&layout, 2),
+        vec![
+            (480, 0, 1440, 1200),
+            (1440, 0, 1920, 1200),
+            (0, 0, 480, 1200)
+        ]
+    );
+
+    assert_eq!(
+        state(&layout, 3),
+        vec![
+            (480, 0, 1440, 1200),
+            (1440, 0, 1920, 600),
+            (1440, 600, 1920, 1200),
+            (0, 0, 480, 1200)
+        ]
+    );
+}
+
+#[test]
+fn spiral_and_dwindle() {
+    let spiral = Template::Spiral.generate(&params(5), 1920, 1200);
+
+    assert_eq!(
+        state(&spiral, 4),
+        vec![
+            (0, 0, 960, 1200),
+            (960, 0, 1920, 600),
+            (1440, 600, 1920, 1200),
+            (960, 900, 1440, 1200),
+            (960, 600, 1440, 900)
+        ]
+    );
+
+    let dwindle = Template::Dwindle.generate(&params(5), 1920, 1200);
+
+    assert_eq!(
+        state(&dwindle, 4),
+        vec![
+            (0, 0, 960, 1200),
+            (960, 0, 1920, 600),
+            (960, 600, 1440, 1200),
+            (1440, 600, 1920, 900),
+            (1440, 900, 1920, 1200)
+        ]
+    );
+}
+
+#[test]
+fn grid_and_columns() {
+    let grid = Template::Grid.generate(&params(5), 1920, 1200);
+
+    assert_eq!(
+        state(&grid, 4),
+        vec![
+            (0, 0, 640, 600),
+            (640, 0, 1280, 600),
+            (1280, 0, 1920, 600),
+            (0, 600, 960, 1200),
+            (960, 600, 1920, 1200)
+        ]
+    );
+
+    let columns = Template::Columns.generate(&params(3), 1920, 1200);
+
+    assert_eq!(
+        state(&columns, 2),
+        vec![
+            (0, 0, 640, 1200),
+            (640, 0, 1280, 1200),
+            (1280, 0, 1920, 1200)
+        ]
+    );
+}
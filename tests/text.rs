@@ -0,0 +1,66 @@
+use himewm_layout::*;
+use himewm_layout_editor::text::ZoneGrid;
+
+fn master_stack_layout() -> Layout {
+    let mut layout = Layout::new(1920, 1200);
+
+    let variant = &mut layout.get_variants_mut()[0];
+
+    variant.new_zone_vec(1920, 1200);
+
+    variant.split(1, 0, SplitDirection::Horizontal(960));
+
+    variant.split(1, 1, SplitDirection::Vertical(600));
+
+    return layout;
+}
+
+#[test]
+fn box_drawing() {
+    let layout = master_stack_layout();
+
+    let grid = ZoneGrid::from_layout(&layout, 0, 1)
+        .unwrap()
+        .with_columns(17);
+
+    assert_eq!(
+        grid.to_string(),
+        "\
+┌───────┬───────┐
+│       │   2   │
+│   1   ├───────┤
+│       │   3   │
+└───────┴───────┘
+"
+    );
+}
+
+#[test]
+fn ascii() {
+    let layout = master_stack_layout();
+
+    let grid = ZoneGrid::from_layout(&layout, 0, 1)
+        .unwrap()
+        .with_columns(17)
+        .with_ascii(true);
+
+    assert_eq!(
+        grid.to_string(),
+        "\
++-------+-------+
+|       |   2   |
+|   1   +-------+
+|       |   3   |
++-------+-------+
+"
+    );
+}
+
+#[test]
+fn missing_variant_state() {
+    let layout = master_stack_layout();
+
+    assert!(ZoneGrid::from_layout(&layout, 0, 2).is_none());
+
+    assert!(ZoneGrid::from_layout(&layout, 1, 0).is_none());
+}
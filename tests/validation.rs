@@ -0,0 +1,134 @@
+use himewm_layout::*;
+use himewm_layout_editor::validation::{self, Diagnostic, Problem, Severity};
+
+fn two_zone_layout() -> Layout {
+    let mut layout = Layout::new(1920, 1200);
+
+    let variant = &mut layout.get_variants_mut()[0];
+
+    variant.new_zone_vec(1920, 1200);
+
+    variant.split(1, 0, SplitDirection::Horizontal(960));
+
+    return layout;
+}
+
+#[test]
+fn valid_layout() {
+    assert_eq!(validation::validate(&two_zone_layout()), Vec::new());
+}
+
+#[test]
+fn overlapping_zones() {
+    let mut layout = two_zone_layout();
+
+    layout.get_variants_mut()[0].get_zones_mut()[1][0].right = 1000;
+
+    assert_eq!(
+        validation::validate(&layout),
+        vec![Diagnostic {
+            variant_idx: 0,
+            variant_state_idx: Some(1),
+            zone_idx: Some(0),
+            problem: Problem::OverlappingZones(1),
+        }]
+    );
+}
+
+#[test]
+fn uncovered_area() {
+    let mut layout = two_zone_layout();
+
+    layout.get_variants_mut()[0].get_zones_mut()[1][0].right = 900;
+
+    let diagnostics = validation::validate(&layout);
+
+    assert_eq!(diagnostics.len(), 1);
+
+    assert_eq!(diagnostics[0].problem, Problem::UncoveredArea(60 * 1200));
+
+    assert_eq!(diagnostics[0].severity(), Severity::Warning);
+
+    assert!(!validation::has_errors(&diagnostics));
+}
+
+#[test]
+fn zone_outside_monitor() {
+    let mut layout = two_zone_layout();
+
+    layout.get_variants_mut()[0].get_zones_mut()[1][1].right = 2000;
+
+    let diagnostics = validation::validate(&layout);
+
+    assert_eq!(diagnostics[0].zone_idx, Some(1));
+
+    assert_eq!(diagnostics[0].problem, Problem::ZoneOutsideMonitor);
+
+    assert!(validation::has_errors(&diagnostics));
+}
+
+#[test]
+fn zone_count_not_increasing() {
+    let mut layout = two_zone_layout();
+
+    layout.get_variants_mut()[0].new_zone_vec(1920, 1200);
+
+    assert_eq!(
+        validation::validate(&layout),
+        vec![Diagnostic {
+            variant_idx: 0,
+            variant_state_idx: Some(2),
+            zone_idx: None,
+            problem: Problem::ZoneCountNotIncreasing {
+                previous: 2,
+                current: 1
+            },
+        }]
+    );
+}
+
+#[test]
+fn end_zone_idx_out_of_range() {
+    let mut layout = two_zone_layout();
+
+    layout.get_variants_mut()[0].set_end_zone_idx(2);
+
+    assert_eq!(
+        validation::validate(&layout),
+        vec![Diagnostic {
+            variant_idx: 0,
+            variant_state_idx: Some(1),
+            zone_idx: None,
+            problem: Problem::EndZoneIdxOutOfRange {
+                end_zone_idx: 2,
+                zone_count: 2
+            },
+        }]
+    );
+}
+
+#[test]
+fn repeating_split_zone_out_of_range() {
+    let mut layout = two_zone_layout();
+
+    let variant = &mut layout.get_variants_mut()[0];
+
+    variant.set_end_tiling_behaviour(EndTilingBehaviour::default_repeating());
+
+    variant.add_repeating_split(Direction::Vertical, 0.5, 1, false);
+
+    variant.add_repeating_split(Direction::Horizontal, 0.5, 9, false);
+
+    assert_eq!(
+        validation::validate(&layout),
+        vec![Diagnostic {
+            variant_idx: 0,
+            variant_state_idx: None,
+            zone_idx: None,
+            problem: Problem::RepeatingSplitZoneOutOfRange {
+                split_idx: 1,
+                zone_idx: 9
+            },
+        }]
+    );
+}